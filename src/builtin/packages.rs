@@ -1,6 +1,6 @@
 //! Package definitions
 
-use crate::domain::PackageConfig;
+use crate::domain::{ArchiveType, Arch, Os, PackageConfig, Repository, UrlSource};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
@@ -40,6 +40,29 @@ pub static BUILTIN_PACKAGES: Lazy<HashMap<&'static str, PackageConfig>> = Lazy::
     packages.insert("pyenv", PackageConfig::new().description("Python version manager for switching between versions").name("pyenv"));
     packages.insert("rbenv", PackageConfig::new().description("Ruby version manager for switching between versions").name("rbenv"));
 
+    // Infrastructure tools
+    packages.insert("gh", PackageConfig::new().description("GitHub's official command-line tool").name("gh").pacman("github-cli")
+        .repository("github-cli", Repository::Apt { url: "https://cli.github.com/packages".into(), key: "https://cli.github.com/packages/githubcli-archive-keyring.gpg".into(), suite: "stable".into(), components: vec!["main".into()] })
+        .repository("gh-cli", Repository::Dnf { url: "https://cli.github.com/packages/rpm/gh-cli.repo".into() }));
+    packages.insert("terraform", PackageConfig::new().description("Infrastructure as code provisioning tool").brew("terraform").tap("hashicorp/tap")
+        .url(UrlSource::new("https://releases.hashicorp.com/terraform/{version}/terraform_{version}_{os}_{arch}.zip").version("1.9.8").archive(ArchiveType::Zip).binary("terraform")
+            .checksum_url("https://releases.hashicorp.com/terraform/{version}/terraform_{version}_SHA256SUMS").os_name(Os::MacOs, "darwin").arch_name(Arch::X86_64, "amd64").arch_name(Arch::Armv7, "arm")));
+    packages.insert("kubectl", PackageConfig::new().description("Kubernetes command-line tool").brew("kubernetes-cli")
+        .url(UrlSource::new("https://dl.k8s.io/release/v{version}/bin/{os}/{arch}/kubectl").version("1.31.2")
+            .checksum_url("https://dl.k8s.io/release/v{version}/bin/{os}/{arch}/kubectl.sha256").os_name(Os::MacOs, "darwin").arch_name(Arch::X86_64, "amd64").arch_name(Arch::Armv7, "arm")));
+    packages.insert("helm", PackageConfig::new().description("Package manager for Kubernetes").brew("helm")
+        .url(UrlSource::new("https://get.helm.sh/helm-v{version}-{os}-{arch}.tar.gz").version("3.16.2").archive(ArchiveType::TarGz).binary("{os}-{arch}/helm")
+            .checksum_url("https://get.helm.sh/helm-v{version}-{os}-{arch}.tar.gz.sha256sum").os_name(Os::MacOs, "darwin").arch_name(Arch::X86_64, "amd64").arch_name(Arch::Armv7, "arm")));
+
+    // Desktop applications (Homebrew casks)
+    packages.insert("iterm2", PackageConfig::new().description("Terminal emulator for macOS").brew("iterm2").cask());
+    packages.insert("vscode", PackageConfig::new().description("Visual Studio Code editor").brew("visual-studio-code").cask().aur("visual-studio-code-bin"));
+
+    // Python tools
+    packages.insert("ruff", PackageConfig::new().description("Extremely fast Python linter and formatter").uv("ruff").pipx("ruff"));
+    packages.insert("poetry", PackageConfig::new().description("Python dependency management and packaging").pipx("poetry"));
+    packages.insert("pre-commit", PackageConfig::new().description("Framework for managing git pre-commit hooks").pipx("pre-commit"));
+
     // Build tools
     packages.insert("make", PackageConfig::new().description("Build automation tool for compiling programs").name("make"));
     packages.insert("cmake", PackageConfig::new().description("Cross-platform build system generator").name("cmake"));
@@ -3,7 +3,7 @@
 //! Defines the command-line interface structure and argument parsing logic.
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Universal development environment bootstrapper
 #[derive(Parser, Debug)]
@@ -14,9 +14,13 @@ use std::path::PathBuf;
     long_about = None
 )]
 pub struct Cli {
-    /// Path to config.toml file
+    /// Path to config.toml file; repeat to layer configs, later ones overriding earlier ones
     #[arg(short, long, default_value = "config.toml", global = true)]
-    pub config: PathBuf,
+    pub config: Vec<PathBuf>,
+
+    /// Apply a `[profiles.<name>]` block; repeat for several
+    #[arg(long = "profile", value_name = "NAME", global = true)]
+    pub profiles: Vec<String>,
 
     /// Dry run - show what would be done without making changes
     #[arg(long, global = true)]
@@ -45,8 +49,56 @@ pub enum Commands {
         /// Update lockfile to actual latest versions
         #[arg(long)]
         refresh: bool,
+
+        /// Install exactly the package versions recorded in devstrap.lock
+        #[arg(long, conflicts_with = "refresh")]
+        locked: bool,
+
+        /// Like --locked, but fail if config and devstrap.lock disagree
+        #[arg(long, conflicts_with_all = ["refresh", "locked"])]
+        frozen: bool,
+    },
+
+    /// Re-resolve locked versions and install the newer ones
+    Update {
+        /// Runtimes or packages to update (all when omitted)
+        names: Vec<String>,
+
+        /// Lock versions for another platform, e.g. linux-x86_64, without installing
+        #[arg(long = "platform", value_name = "OS-ARCH")]
+        platforms: Vec<String>,
     },
 
     /// List all available packages
     List,
+
+    /// Show active profiles and what differs from the last sync
+    Status,
+
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Show the config files in load order, includes first
+    Show {
+        /// Print the merged config, noting which file each entry came from
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
+impl Cli {
+    /// Directory of the last config, where devstrap.lock and devstrap.state live
+    pub fn config_dir(&self) -> &Path {
+        self.config
+            .last()
+            .and_then(|path| path.parent())
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+    }
 }
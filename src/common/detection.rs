@@ -2,7 +2,7 @@
 //!
 //! Functions for detecting how packages are currently installed.
 
-use super::command::run_command_output;
+use super::command::{run_command, run_command_output};
 use crate::domain::{PackageManager, SystemInfo};
 use crate::domain::system::is_command_available;
 use crate::domain::InstallMethod;
@@ -34,33 +34,78 @@ fn detect_from_path(package_name: &str) -> Option<InstallMethod> {
     None
 }
 
+/// Package manager specific check for an installed package
+type InstalledCheck = fn(&str) -> bool;
+
 /// Check package managers for installed package
 fn check_package_managers(package_name: &str, system_info: &SystemInfo) -> Option<InstallMethod> {
-    use super::command::run_command;
+    let checks: [(PackageManager, InstalledCheck, InstallMethod); 4] = [
+        (
+            PackageManager::Brew,
+            is_brew_installed,
+            InstallMethod::SystemDefault(PackageManager::Brew),
+        ),
+        (PackageManager::Npm, is_npm_installed, InstallMethod::Npm),
+        (PackageManager::Uv, is_uv_tool_installed, InstallMethod::Uv),
+        (PackageManager::Pipx, is_pipx_installed, InstallMethod::Pipx),
+    ];
+
+    checks
+        .into_iter()
+        .find(|(pm, check, _)| system_info.has_package_manager(*pm) && check(package_name))
+        .map(|(_, _, method)| method)
+}
 
-    if system_info.has_package_manager(PackageManager::Brew)
-        && run_command("brew", &["list", package_name]).is_ok()
-    {
-        return Some(InstallMethod::SystemDefault(PackageManager::Brew));
-    }
+fn is_brew_installed(package_name: &str) -> bool {
+    run_command("brew", &["list", package_name]).is_ok()
+}
 
-    if system_info.has_package_manager(PackageManager::Npm) {
-        if let Ok(output) = run_command_output("npm", &["list", "-g", "--depth=0"]) {
-            if output.contains(package_name) {
-                return Some(InstallMethod::Npm);
-            }
-        }
-    }
+fn is_npm_installed(package_name: &str) -> bool {
+    run_command_output("npm", &["list", "-g", "--depth=0"])
+        .is_ok_and(|output| output.contains(package_name))
+}
 
-    if system_info.has_package_manager(PackageManager::Pipx) {
-        if let Ok(output) = run_command_output("pipx", &["list"]) {
-            if output.contains(package_name) {
-                return Some(InstallMethod::Pipx);
-            }
-        }
-    }
+fn is_pipx_installed(package_name: &str) -> bool {
+    run_command_output("pipx", &["list"]).is_ok_and(|output| output.contains(package_name))
+}
 
-    None
+fn is_uv_tool_installed(package_name: &str) -> bool {
+    run_command_output("uv", &["tool", "list"])
+        .is_ok_and(|output| uv_tool_list_contains(&output, package_name))
+}
+
+/// Check `uv tool list` output for a tool or one of its executables
+///
+/// Output lists each tool as `name vX.Y.Z` followed by `- executable` lines.
+fn uv_tool_list_contains(output: &str, package_name: &str) -> bool {
+    output.lines().map(str::trim).any(|line| {
+        line.strip_prefix("- ").map_or_else(
+            || line.split_whitespace().next() == Some(package_name),
+            |executable| executable == package_name,
+        )
+    })
+}
+
+/// Detect whether a Homebrew cask is installed
+///
+/// Casks are usually GUI applications that never appear on `PATH`, so they
+/// are looked up with `brew list --cask` instead of by executable.
+#[must_use]
+pub fn detect_cask_installation(cask: &str, system_info: &SystemInfo) -> Option<InstallMethod> {
+    (system_info.has_package_manager(PackageManager::Brew)
+        && run_command("brew", &["list", "--cask", cask]).is_ok())
+    .then_some(InstallMethod::SystemDefault(PackageManager::Brew))
+}
+
+/// Detect whether a package was installed from the AUR
+///
+/// AUR binaries land in `/usr/bin` like official packages, so they are told
+/// apart by being foreign to the sync repositories (`pacman -Qm`).
+#[must_use]
+pub fn detect_aur_installation(package_name: &str, system_info: &SystemInfo) -> Option<InstallMethod> {
+    (system_info.default_package_manager == Some(PackageManager::Pacman)
+        && run_command("pacman", &["-Qm", package_name]).is_ok())
+    .then_some(InstallMethod::Aur)
 }
 
 /// Detect how a package was installed by checking its location and package managers
@@ -90,3 +135,18 @@ pub fn detect_installation_method(
 
     Some(InstallMethod::System)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uv_tool_list_contains() {
+        let output = "black v24.10.0\n- black\n- blackd\nruff v0.7.0\n- ruff\n";
+
+        assert!(uv_tool_list_contains(output, "ruff"));
+        assert!(uv_tool_list_contains(output, "blackd"));
+        assert!(!uv_tool_list_contains(output, "v0.7.0"));
+        assert!(!uv_tool_list_contains(output, "poetry"));
+    }
+}
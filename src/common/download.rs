@@ -0,0 +1,32 @@
+//! HTTP download helpers
+
+use crate::common::error::Result;
+use anyhow::Context;
+
+/// User agent sent with every request (GitHub rejects anonymous clients)
+const USER_AGENT: &str = concat!("devstrap/", env!("CARGO_PKG_VERSION"));
+
+/// Download a URL into memory, failing on non-success status codes
+pub fn download_bytes(url: &str) -> Result<Vec<u8>> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let response = client
+        .get(url)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .with_context(|| format!("Failed to download {url}"))?;
+
+    let bytes = response
+        .bytes()
+        .with_context(|| format!("Failed to read response body from {url}"))?;
+    Ok(bytes.to_vec())
+}
+
+/// Download a URL as UTF-8 text
+pub fn download_text(url: &str) -> Result<String> {
+    let bytes = download_bytes(url)?;
+    String::from_utf8(bytes).with_context(|| format!("Response from {url} is not valid UTF-8"))
+}
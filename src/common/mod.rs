@@ -2,14 +2,20 @@
 
 pub mod command;
 pub mod detection;
+pub mod download;
 pub mod error;
 pub mod path;
+pub mod persist;
+pub mod schema;
+pub mod system_file;
 pub mod ui;
 pub mod uninstall;
 
 pub use command::run_command;
-pub use detection::detect_installation_method;
+pub use detection::{
+    detect_aur_installation, detect_cask_installation, detect_installation_method,
+};
 pub use error::Result;
-pub use path::{expand_tilde, home_dir, local_bin_dir};
+pub use path::{expand_tilde, home_dir, local_bin_dir, make_executable};
 pub use ui::{confirm, print_system_info, show_banner, show_post_install_instructions};
-pub use uninstall::uninstall_package;
+pub use uninstall::{uninstall_builtin_package, uninstall_package};
@@ -4,7 +4,8 @@
 
 use crate::common::error::Result;
 use anyhow::{anyhow, Context};
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Expand tilde in path to home directory
 ///
@@ -45,6 +46,20 @@ pub fn local_bin_dir() -> Result<PathBuf> {
     Ok(home.join(".local").join("bin"))
 }
 
+/// Mark a file as executable
+#[cfg(unix)]
+pub fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+/// Mark a file as executable
+#[cfg(not(unix))]
+pub fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
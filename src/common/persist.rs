@@ -0,0 +1,183 @@
+//! Crash-safe persistence for devstrap's own files
+//!
+//! The state file and lockfile are written to a temp file in the same
+//! directory, fsynced and renamed over the original, so an interrupted write
+//! leaves the previous version in place. Each write that changes a file first
+//! rotates the old contents into numbered backups (`devstrap.state.bak.1` is
+//! the newest), which loading falls back to when the file no longer parses.
+//! A sync holds an advisory lock so concurrent runs don't interleave writes.
+
+use crate::common::error::Result;
+use crate::common::schema::NewerSchema;
+use anyhow::Context;
+use colored::Colorize;
+use fs4::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Number of previous versions kept next to each file
+pub const BACKUP_COUNT: usize = 3;
+
+/// Replace a file's contents atomically, backing up the previous version
+///
+/// Writing the same contents again is a no-op, so repeated syncs don't rotate
+/// good backups away.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    if fs::read(path).ok().as_deref() == Some(contents) {
+        return Ok(());
+    }
+    let dir = parent_dir(path);
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+    let temp = sibling(path, &format!("tmp.{}", std::process::id()));
+    let result = write_synced(&temp, contents).and_then(|()| {
+        rotate_backups(path)?;
+        fs::rename(&temp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result?;
+
+    // Persist the rename itself; directories can't be opened on every platform
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Load a file, falling back to its newest readable backup
+///
+/// A missing file is a fresh start. When the file exists but can't be read,
+/// each backup is tried in turn and the recovery is reported; if none can be
+/// read, the original error is returned rather than starting empty. A file
+/// from a newer devstrap is refused outright, since its backups are older
+/// than what it records.
+pub fn load_recovering<T: Default>(path: &Path, read: impl Fn(&Path) -> Result<T>) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let error = match read(path) {
+        Ok(value) => return Ok(value),
+        Err(error) if error.downcast_ref::<NewerSchema>().is_some() => return Err(error),
+        Err(error) => error,
+    };
+
+    for backup in backup_paths(path).iter().filter(|backup| backup.exists()) {
+        if let Ok(value) = read(backup) {
+            eprintln!("{} {:#}", "⚠".yellow(), error);
+            eprintln!("  Recovered from backup {}", backup.display());
+            return Ok(value);
+        }
+    }
+    Err(error.context(format!("No readable backup of {} to recover from", path.display())))
+}
+
+/// Backups of a file, newest first
+#[must_use]
+pub fn backup_paths(path: &Path) -> Vec<PathBuf> {
+    (1..=BACKUP_COUNT).map(|n| sibling(path, &format!("bak.{n}"))).collect()
+}
+
+/// Advisory lock held while a sync modifies devstrap's files
+///
+/// Released when dropped, including when the process exits.
+#[derive(Debug)]
+pub struct SyncLock {
+    _file: File,
+}
+
+impl SyncLock {
+    /// Take the lock at `path`, waiting for another run to release it
+    pub fn acquire(path: &Path) -> Result<Self> {
+        let dir = parent_dir(path);
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file: {}", path.display()))?;
+
+        if FileExt::try_lock(&file).is_err() {
+            println!("{} Waiting for another devstrap run to finish...", "⏳".yellow());
+            FileExt::lock(&file).with_context(|| format!("Failed to lock {}", path.display()))?;
+        }
+        Ok(Self { _file: file })
+    }
+}
+
+/// Write and fsync a new file
+fn write_synced(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(contents)
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Shift backups down by one and copy the current file into the newest slot
+fn rotate_backups(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let backups = backup_paths(path);
+    for pair in backups.windows(2).rev() {
+        if pair[0].exists() {
+            fs::rename(&pair[0], &pair[1])
+                .with_context(|| format!("Failed to rotate backup {}", pair[0].display()))?;
+        }
+    }
+    fs::copy(path, &backups[0]).with_context(|| format!("Failed to back up {}", path.display()))?;
+    Ok(())
+}
+
+/// Path next to `path` with an extra extension (`devstrap.state.bak.1`)
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{suffix}"));
+    path.with_file_name(name)
+}
+
+/// Directory containing `path`, the working directory for bare file names
+fn parent_dir(path: &Path) -> &Path {
+    path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_rotates_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("devstrap.state");
+        for contents in ["one", "two", "two", "three", "four", "five"] {
+            write_atomic(&path, contents.as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "five");
+        let backups: Vec<String> = backup_paths(&path).iter().map(|p| fs::read_to_string(p).unwrap()).collect();
+        assert_eq!(backups, ["four", "three", "two"]);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1 + BACKUP_COUNT);
+    }
+
+    #[test]
+    fn test_load_recovering_falls_back_to_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("devstrap.lock");
+        let read = |p: &Path| -> Result<u32> { Ok(fs::read_to_string(p)?.trim().parse()?) };
+
+        assert_eq!(load_recovering(&path, read).unwrap(), 0);
+
+        write_atomic(&path, b"1").unwrap();
+        write_atomic(&path, b"2").unwrap();
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(load_recovering(&path, read).unwrap(), 1);
+
+        for backup in backup_paths(&path) {
+            let _ = fs::write(backup, "garbage");
+        }
+        assert!(load_recovering(&path, read).is_err());
+    }
+}
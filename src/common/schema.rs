@@ -0,0 +1,152 @@
+//! Schema versions of devstrap's own files
+//!
+//! `devstrap.state` and `devstrap.lock` record the `schema_version` they were
+//! written with; files from before it was recorded are version 0. On load,
+//! older documents are upgraded one migration at a time while still raw TOML.
+//! Only once the upgraded document is saved is a copy of the original kept next
+//! to the file, so read-only commands leave it alone. Documents from a newer
+//! devstrap are refused rather than misread.
+
+use crate::common::error::Result;
+use anyhow::Context;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Upgrades a document from the version at its index to the next one
+pub type Migration = fn(&mut toml::Table);
+
+/// A file written by a newer devstrap, which this one refuses to read
+#[derive(Debug)]
+pub struct NewerSchema {
+    /// Schema version in the file
+    pub version: usize,
+    /// Newest schema version this devstrap knows
+    pub supported: usize,
+}
+
+impl fmt::Display for NewerSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "schema version {} is newer than this devstrap supports ({}); upgrade devstrap to read it",
+            self.version, self.supported
+        )
+    }
+}
+
+impl std::error::Error for NewerSchema {}
+
+/// Upgrade a document to the newest schema, returning the version it had
+///
+/// The newest schema version is the number of migrations.
+pub fn migrate(document: &mut toml::Table, migrations: &[Migration]) -> Result<usize> {
+    let current = migrations.len();
+    let version = match document.get("schema_version") {
+        None => 0,
+        Some(value) => value
+            .as_integer()
+            .and_then(|v| usize::try_from(v).ok())
+            .context("schema_version must be a non-negative integer")?,
+    };
+    if version > current {
+        return Err(NewerSchema { version, supported: current }.into());
+    }
+
+    for migration in &migrations[version..] {
+        migration(document);
+    }
+    document.insert("schema_version".to_string(), toml::Value::try_from(current)?);
+    Ok(version)
+}
+
+/// Keep the file as it was before migrating it (`devstrap.state.v0.bak`)
+///
+/// An existing copy is left alone, so it always holds the oldest original;
+/// returns the copy only when it was made now.
+pub fn keep_premigration_copy(path: &Path, version: usize) -> Result<Option<PathBuf>> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{version}.bak"));
+    let copy = path.with_file_name(name);
+    if copy.exists() || !path.exists() {
+        return Ok(None);
+    }
+    fs::copy(path, &copy).with_context(|| format!("Failed to back up {}", path.display()))?;
+    Ok(Some(copy))
+}
+
+/// Keep the original of a file loaded from an older schema before it is written over
+///
+/// `migrated_from` is the version [`load_migrated`] upgraded the file from.
+pub fn keep_original(path: &Path, name: &str, migrated_from: Option<usize>) -> Result<()> {
+    let Some(version) = migrated_from else {
+        return Ok(());
+    };
+    if let Some(copy) = keep_premigration_copy(path, version)? {
+        println!("  Upgraded {name} from schema version {version}; the original is kept at {}", copy.display());
+    }
+    Ok(())
+}
+
+/// Read a file, migrate it and deserialize the result
+///
+/// Returns the schema version the file was upgraded from, if it was, to be
+/// passed to [`keep_original`] when the file is saved. `name` is used in
+/// messages (`devstrap.lock`).
+pub fn load_migrated<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    name: &str,
+    migrations: &[Migration],
+) -> Result<(T, Option<usize>)> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {name}: {}", path.display()))?;
+    let mut document: toml::Table =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {name}: {}", path.display()))?;
+
+    let version =
+        migrate(&mut document, migrations).with_context(|| format!("Can't read {name}: {}", path.display()))?;
+    let value = toml::Value::Table(document)
+        .try_into()
+        .with_context(|| format!("Failed to parse {name}: {}", path.display()))?;
+    Ok((value, (version < migrations.len()).then_some(version)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_tools(document: &mut toml::Table) {
+        if let Some(tools) = document.remove("tools") {
+            document.insert("packages".to_string(), tools);
+        }
+    }
+
+    fn add_taps(document: &mut toml::Table) {
+        document.entry("taps").or_insert_with(|| toml::Value::Array(Vec::new()));
+    }
+
+    #[test]
+    fn test_migrate_applies_remaining_migrations() {
+        let migrations: [Migration; 2] = [rename_tools, add_taps];
+        let cases = [
+            ("tools = [\"jq\"]", 0, vec!["packages", "schema_version", "taps"]),
+            ("schema_version = 1\ntools = [\"jq\"]", 1, vec!["schema_version", "taps", "tools"]),
+            ("schema_version = 2\ntaps = []", 2, vec!["schema_version", "taps"]),
+        ];
+
+        for (contents, version, keys) in cases {
+            let mut document: toml::Table = toml::from_str(contents).unwrap();
+            assert_eq!(migrate(&mut document, &migrations).unwrap(), version, "{contents}");
+            assert_eq!(document.keys().collect::<Vec<_>>(), keys, "{contents}");
+            assert_eq!(document["schema_version"].as_integer(), Some(2));
+        }
+    }
+
+    #[test]
+    fn test_migrate_refuses_newer_versions() {
+        let migrations: [Migration; 2] = [rename_tools, add_taps];
+        let mut document: toml::Table = toml::from_str("schema_version = 3").unwrap();
+        let error = migrate(&mut document, &migrations).unwrap_err().to_string();
+        assert!(error.contains("schema version 3 is newer than this devstrap supports (2)"));
+    }
+}
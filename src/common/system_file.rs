@@ -0,0 +1,45 @@
+//! Root-owned file helpers
+//!
+//! Repository definitions live under `/etc`, so writes go through a private
+//! temp file with an unpredictable name that is copied into place with
+//! `sudo install`.
+
+use super::command::run_command;
+use crate::common::error::Result;
+use anyhow::Context;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// Read a system file, treating a missing or unreadable file as empty
+#[must_use]
+pub fn read_system_file(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+/// Write a root-owned file with mode 0644
+pub fn write_system_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut temp = NamedTempFile::new().context("Failed to create temporary file")?;
+    temp.write_all(contents)
+        .and_then(|()| temp.flush())
+        .with_context(|| format!("Failed to write temporary file: {}", temp.path().display()))?;
+
+    run_command(
+        "sudo",
+        &["install", "-m", "0644", &temp.path().to_string_lossy(), &path.to_string_lossy()],
+    )
+    .with_context(|| format!("Failed to install {}", path.display()))?;
+    Ok(())
+}
+
+/// Remove root-owned files, ignoring ones that don't exist
+pub fn remove_system_files(paths: &[PathBuf]) -> Result<()> {
+    let mut args = vec!["rm".to_string(), "-f".to_string()];
+    args.extend(paths.iter().map(|p| p.to_string_lossy().into_owned()));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    run_command("sudo", &args)?;
+    Ok(())
+}
+
@@ -3,9 +3,11 @@
 //! Functions for removing packages via different package managers.
 
 use super::command::run_command;
+use crate::builtin;
 use crate::domain::PackageManager;
 use crate::common::error::Result;
-use crate::domain::InstallMethod;
+use crate::common::path::local_bin_dir;
+use crate::domain::{InstallMethod, PackageConfig};
 use anyhow::{anyhow, Context};
 
 /// Uninstall a package using the specified method
@@ -46,6 +48,17 @@ pub fn uninstall_package(package_name: &str, method: &InstallMethod) -> Result<(
         InstallMethod::Pipx => {
             run_command("pipx", &["uninstall", package_name]).ok();
         }
+        InstallMethod::Uv => {
+            run_command("uv", &["tool", "uninstall", package_name]).ok();
+        }
+        InstallMethod::Aur => {
+            run_command("sudo", &["pacman", "-R", "--noconfirm", package_name]).ok();
+        }
+        InstallMethod::Url => {
+            let path = local_bin_dir()?.join(package_name);
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
         InstallMethod::System | InstallMethod::GitHub => {
             return Err(anyhow!(
                 "Cannot uninstall system packages: {} ({})",
@@ -57,3 +70,32 @@ pub fn uninstall_package(package_name: &str, method: &InstallMethod) -> Result<(
 
     Ok(())
 }
+
+/// Uninstall a builtin package by ID, honouring its method-specific options
+///
+/// Homebrew casks must be removed with `brew uninstall --cask`, AUR
+/// packages by their AUR name and url downloads by their installed binary;
+/// everything else goes through [`uninstall_package`].
+pub fn uninstall_builtin_package(package_id: &str, method: &InstallMethod) -> Result<()> {
+    let Some(config) = builtin::get_package(package_id) else {
+        return uninstall_package(package_id, method);
+    };
+
+    if config.cask && *method == InstallMethod::SystemDefault(PackageManager::Brew) {
+        let cask = config.name_for_method("brew").unwrap_or(package_id);
+        run_command("brew", &["uninstall", "--cask", cask])
+            .with_context(|| format!("Failed to uninstall cask {cask}"))?;
+        return Ok(());
+    }
+
+    uninstall_package(&removal_name(package_id, config, method), method)
+}
+
+/// Name the uninstaller needs for a builtin package and method
+fn removal_name(package_id: &str, config: &PackageConfig, method: &InstallMethod) -> String {
+    match (method, &config.url, &config.aur) {
+        (InstallMethod::Url, Some(source), _) => source.installed_binary_name(package_id),
+        (InstallMethod::Aur, _, Some(aur)) => aur.clone(),
+        _ => package_id.to_string(),
+    }
+}
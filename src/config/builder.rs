@@ -1,6 +1,6 @@
 //! Builder methods for `PackageConfig`
 
-use crate::domain::PackageConfig;
+use crate::domain::{PackageConfig, Repository, UrlSource};
 
 impl PackageConfig {
     /// Create a new package config with all fields None
@@ -44,6 +44,31 @@ impl PackageConfig {
         self
     }
 
+    /// Set the uv tool package name
+    #[must_use]
+    pub fn uv(mut self, name: impl Into<String>) -> Self {
+        self.uv = Some(name.into());
+        self
+    }
+
+    /// Set the Python version used for uv tool installs (e.g. "3.12")
+    #[must_use]
+    pub fn python(mut self, version: impl Into<String>) -> Self {
+        self.python = Some(version.into());
+        self
+    }
+
+    /// Set extra packages injected into the uv tool environment
+    #[must_use]
+    pub fn with<I, S>(mut self, extras: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with = extras.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Set the GitHub repository (format: "owner/repo")
     #[must_use]
     pub fn github(mut self, repo: impl Into<String>) -> Self {
@@ -58,6 +83,20 @@ impl PackageConfig {
         self
     }
 
+    /// Set the Homebrew tap providing the formula (format: "owner/repo")
+    #[must_use]
+    pub fn tap(mut self, tap: impl Into<String>) -> Self {
+        self.tap = Some(tap.into());
+        self
+    }
+
+    /// Install the Homebrew package as a cask
+    #[must_use]
+    pub fn cask(mut self) -> Self {
+        self.cask = true;
+        self
+    }
+
     /// Set the APT package name
     #[must_use]
     pub fn apt(mut self, name: impl Into<String>) -> Self {
@@ -78,4 +117,25 @@ impl PackageConfig {
         self.dnf = Some(name.into());
         self
     }
+
+    /// Set the AUR package name
+    #[must_use]
+    pub fn aur(mut self, name: impl Into<String>) -> Self {
+        self.aur = Some(name.into());
+        self
+    }
+
+    /// Set the direct download source
+    #[must_use]
+    pub fn url(mut self, source: UrlSource) -> Self {
+        self.url = Some(source);
+        self
+    }
+
+    /// Add a third-party repository required by the system package
+    #[must_use]
+    pub fn repository(mut self, name: impl Into<String>, repository: Repository) -> Self {
+        self.repositories.insert(name.into(), repository);
+        self
+    }
 }
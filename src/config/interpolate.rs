@@ -0,0 +1,177 @@
+//! Variable interpolation in config values
+//!
+//! After layering and profiles, every string value in the config is expanded:
+//!
+//! - `${env:NAME}` - an environment variable, which must be set
+//! - `${var:NAME}` - an entry of the `[vars]` table
+//! - `${os}`, `${arch}`, `${distro}` - the detected system (`linux`, `arm64`, `ubuntu`)
+//! - a leading `~/` - the home directory
+//!
+//! `$${` writes a literal `${`. Other `$` signs, such as `$HOME` in a shell
+//! command, are left alone. `[vars]` entries can use everything except other
+//! vars.
+
+use crate::common::error::Result;
+use crate::config::profiles::Host;
+use anyhow::anyhow;
+use std::collections::BTreeMap;
+
+/// Values `${...}` expands to
+pub struct Variables<'a> {
+    /// Entries of the `[vars]` table, already expanded
+    vars: BTreeMap<String, String>,
+    /// The machine, for system names, environment and home directory
+    host: &'a Host,
+}
+
+impl<'a> Variables<'a> {
+    /// Take the `[vars]` table out of a document and expand its entries
+    ///
+    /// # Errors
+    /// Fails on a non-string entry or one that can't be expanded.
+    pub fn from_document(document: &mut toml::Table, host: &'a Host) -> Result<Self> {
+        let mut variables = Self { vars: BTreeMap::new(), host };
+        let Some(vars) = document.remove("vars") else {
+            return Ok(variables);
+        };
+        let toml::Value::Table(vars) = vars else {
+            return Err(anyhow!("`vars` must be a table of strings"));
+        };
+
+        let mut expanded = BTreeMap::new();
+        for (name, value) in vars {
+            let key = format!("vars.{name}");
+            let toml::Value::String(value) = value else {
+                return Err(anyhow!("{key} must be a string"));
+            };
+            expanded.insert(name, variables.expand(&value, &key)?);
+        }
+        variables.vars = expanded;
+        Ok(variables)
+    }
+
+    /// Expand every string in a value, `key` naming it in errors (`runtimes.node.version`)
+    ///
+    /// # Errors
+    /// Fails on an undefined or unknown variable.
+    pub fn expand_value(&self, value: &mut toml::Value, key: &str) -> Result<()> {
+        match value {
+            toml::Value::String(text) => *text = self.expand(text, key)?,
+            toml::Value::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    self.expand_value(item, &format!("{key}[{index}]"))?;
+                }
+            }
+            toml::Value::Table(table) => {
+                for (name, item) in table.iter_mut() {
+                    self.expand_value(item, &format!("{key}.{name}"))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Expand the variables and leading tilde in one string
+    fn expand(&self, text: &str, key: &str) -> Result<String> {
+        let home = self.host.env.get("HOME");
+        let mut rest: &str = &shellexpand::tilde_with_context(text, || home);
+        let mut out = String::new();
+
+        while let Some(start) = rest.find('$') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            if let Some(escaped) = after.strip_prefix("${") {
+                out.push_str("${");
+                rest = escaped;
+            } else if let Some(reference) = after.strip_prefix('{') {
+                let end = reference.find('}').ok_or_else(|| anyhow!("Unterminated `${{` in {key}"))?;
+                out.push_str(&self.lookup(&reference[..end], key)?);
+                rest = &reference[end + 1..];
+            } else {
+                out.push('$');
+                rest = after;
+            }
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Value of one `${...}` reference
+    fn lookup(&self, reference: &str, key: &str) -> Result<String> {
+        let value = match reference.split_once(':') {
+            Some(("env", name)) => self.host.env.get(name).cloned().ok_or_else(|| {
+                anyhow!("Environment variable {name} is not set, but {key} uses `${{env:{name}}}`")
+            })?,
+            Some(("var", name)) if key.starts_with("vars.") => {
+                return Err(anyhow!("{key} uses `${{var:{name}}}`, but vars can't refer to other vars"))
+            }
+            Some(("var", name)) => self.vars.get(name).cloned().ok_or_else(|| {
+                anyhow!("Undefined variable `${{var:{name}}}` in {key}; define it under [vars]")
+            })?,
+            None if reference == "os" => self.host.os.clone(),
+            None if reference == "arch" => self.host.arch.clone(),
+            None if reference == "distro" => self.host.distro.clone(),
+            _ => {
+                return Err(anyhow!(
+                    "Unknown variable `${{{reference}}}` in {key} \
+                     (expected ${{env:NAME}}, ${{var:NAME}}, ${{os}}, ${{arch}} or ${{distro}})"
+                ))
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn linux_host() -> Host {
+        Host {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            distro: "ubuntu".to_string(),
+            env: HashMap::from([("HOME".to_string(), "/home/dev".to_string()), ("USER".to_string(), "dev".to_string())]),
+            ..Host::default()
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        let host = linux_host();
+        let mut document: toml::Table = toml::from_str("[vars]\nnode = \"20\"\nbin = \"~/bin/${os}\"").unwrap();
+        let variables = Variables::from_document(&mut document, &host).unwrap();
+        let cases = [
+            ("${var:node}", "20"),
+            ("~/.config/${env:USER}", "/home/dev/.config/dev"),
+            ("https://example.com/${os}-${arch}/${distro}.tar.gz", "https://example.com/linux-x86_64/ubuntu.tar.gz"),
+            ("${var:bin}/tool", "/home/dev/bin/linux/tool"),
+            ("echo $HOME $$${os}", "echo $HOME $${os}"),
+            ("~1.2", "~1.2"),
+        ];
+
+        assert!(document.is_empty());
+        for (text, expanded) in cases {
+            assert_eq!(variables.expand(text, "key").unwrap(), expanded, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_expand_errors_name_the_key() {
+        let host = linux_host();
+        let variables = Variables::from_document(&mut toml::Table::new(), &host).unwrap();
+        let cases = [
+            ("${var:missing}", "Undefined variable `${var:missing}` in runtimes.node.version"),
+            ("${env:MISSING}", "Environment variable MISSING is not set, but runtimes.node.version uses"),
+            ("${platform}", "Unknown variable `${platform}` in runtimes.node.version"),
+            ("${os", "Unterminated `${` in runtimes.node.version"),
+        ];
+
+        for (text, message) in cases {
+            let error = variables.expand(text, "runtimes.node.version").unwrap_err().to_string();
+            assert!(error.contains(message), "{error}");
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Merging config files and profile blocks into the layers loaded so far
+
+use super::ConfigLayers;
+use crate::common::error::Result;
+use crate::domain::Config;
+use anyhow::{anyhow, Context};
+use std::collections::btree_map::Entry;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+impl ConfigLayers {
+    /// Merge a file after the files it includes
+    ///
+    /// `chain` holds the files including this one, to report cycles.
+    pub(super) fn load_file(&mut self, path: &Path, chain: &mut Vec<PathBuf>) -> Result<()> {
+        let canonical = fs::canonicalize(path).with_context(|| read_error(path, chain))?;
+        if let Some(start) = chain.iter().position(|file| *file == canonical) {
+            let cycle: Vec<String> = chain[start..].iter().chain([&canonical]).map(|f| f.display().to_string()).collect();
+            return Err(anyhow!("Config include cycle: {}", cycle.join(" -> ")));
+        }
+        if self.files.iter().any(|file| fs::canonicalize(file).ok().as_ref() == Some(&canonical)) {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(path).with_context(|| read_error(path, chain))?;
+        let mut document: toml::Table =
+            toml::from_str(&contents).with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
+
+        chain.push(canonical);
+        for include in take_strings(&mut document, "include", &path.display().to_string())? {
+            let included = path.parent().unwrap_or_else(|| Path::new("")).join(include);
+            self.load_file(&included, chain)?;
+        }
+        chain.pop();
+
+        self.files.push(path.to_path_buf());
+        self.merge(document, &path.display().to_string())
+    }
+
+    /// Merge one layer's document over the layers loaded so far
+    ///
+    /// `source` names the file or profile in messages and provenance.
+    pub(super) fn merge(&mut self, mut document: toml::Table, source: &str) -> Result<()> {
+        for package in take_strings(&mut document, "remove", source)? {
+            self.remove_package(&package);
+        }
+        if let Some(packages) = document.remove("packages") {
+            self.append_packages(packages, source)?;
+        }
+
+        for (key, value) in document {
+            self.merge_entry(key, value, source);
+        }
+        Ok(())
+    }
+
+    /// Override a top-level entry, merging tables key by key
+    fn merge_entry(&mut self, key: String, value: toml::Value, source: &str) {
+        let source_keys: Vec<String> = match &value {
+            toml::Value::Table(table) => table.keys().map(|name| format!("{key}.{name}")).collect(),
+            _ => vec![key.clone()],
+        };
+        match (self.document.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => existing.extend(table),
+            (_, value) => {
+                self.sources.retain(|source, _| !source.starts_with(&format!("{key}.")));
+                self.document.insert(key, value);
+            }
+        }
+        for key in source_keys {
+            self.sources.insert(key, source.to_string());
+        }
+    }
+
+    /// Append a file's package groups, skipping packages already listed
+    fn append_packages(&mut self, packages: toml::Value, source: &str) -> Result<()> {
+        let mut table = toml::Table::new();
+        table.insert("packages".to_string(), packages);
+        let parsed: Config =
+            toml::Value::Table(table).try_into().with_context(|| format!("Invalid packages in {source}"))?;
+
+        for group in parsed.packages {
+            let mut added: Vec<String> = Vec::new();
+            for package in group {
+                if let Entry::Vacant(entry) = self.sources.entry(format!("packages.{package}")) {
+                    entry.insert(source.to_string());
+                    added.push(package);
+                }
+            }
+            if !added.is_empty() {
+                self.packages.push(added);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop an inherited package, and its group if that empties it
+    fn remove_package(&mut self, package: &str) {
+        for group in &mut self.packages {
+            group.retain(|p| p != package);
+        }
+        self.packages.retain(|group| !group.is_empty());
+        self.sources.remove(&format!("packages.{package}"));
+    }
+}
+
+/// Remove a list of strings (`include`, `remove`) from a document
+fn take_strings(document: &mut toml::Table, key: &str, source: &str) -> Result<Vec<String>> {
+    let Some(value) = document.remove(key) else {
+        return Ok(Vec::new());
+    };
+    value.try_into().with_context(|| format!("`{key}` in {source} must be a list of strings"))
+}
+
+/// Error for a config file that can't be read, naming the file including it
+fn read_error(path: &Path, chain: &[PathBuf]) -> String {
+    match chain.last() {
+        Some(parent) => format!("Failed to read config file: {} (included from {})", path.display(), parent.display()),
+        None => format!("Failed to read config file: {}", path.display()),
+    }
+}
@@ -0,0 +1,232 @@
+//! Layered configuration files
+//!
+//! A config can be assembled from several files: each `--config` flag adds a
+//! layer, and a file's `include = ["../team.toml"]` loads other files first,
+//! relative to the including file. Later layers win: package groups are
+//! concatenated with packages already listed dropped, tables such as
+//! `[runtimes]` are overridden key by key, and `remove = ["ripgrep"]` drops
+//! packages inherited from earlier layers. Profiles active on this machine are
+//! applied last, as further layers, and variables are expanded once everything
+//! is merged. Every entry remembers the file or profile it came from, for
+//! `devstrap config show --resolved`.
+
+mod merge;
+mod profiles;
+mod render;
+
+use crate::common::error::Result;
+use crate::config::interpolate::Variables;
+use crate::config::profiles::{ActiveProfile, Host};
+use crate::domain::{config_hash, Config};
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Config files merged in load order, with where each entry came from
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    /// Files in the order they were merged, includes before their includer
+    pub files: Vec<PathBuf>,
+    /// Merged document, without `include`, `remove` and `packages`
+    pub document: toml::Table,
+    /// File or profile each entry was taken from, keyed `packages.<id>` or `<table>.<key>`
+    pub sources: BTreeMap<String, String>,
+    /// Profiles applied on top of the files
+    pub profiles: Vec<ActiveProfile>,
+    /// Merged package groups
+    packages: Vec<Vec<String>>,
+}
+
+impl ConfigLayers {
+    /// Load and merge config files, each after the files it includes
+    ///
+    /// # Errors
+    /// Fails on an unreadable file, an include cycle or a malformed `packages`,
+    /// `include` or `remove`.
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        let mut layers = Self::default();
+        for path in paths {
+            layers.load_file(path, &mut Vec::new())?;
+        }
+        Ok(layers)
+    }
+
+    /// Apply profiles, then expand variables, for the host devstrap runs on
+    ///
+    /// # Errors
+    /// Fails like [`Self::apply_profiles`] and [`Self::interpolate`].
+    pub fn resolve(&mut self, selected: &[String], host: &Host) -> Result<()> {
+        self.apply_profiles(selected, host)?;
+        self.interpolate(host)
+    }
+
+    /// Expand `${...}` references and leading tildes in every value, removing `[vars]`
+    ///
+    /// # Errors
+    /// Fails on an undefined or unknown variable, naming the key and its file.
+    pub fn interpolate(&mut self, host: &Host) -> Result<()> {
+        let variables = Variables::from_document(&mut self.document, host)?;
+        self.sources.retain(|key, _| !key.starts_with("vars."));
+
+        for (name, value) in &mut self.document {
+            let entries: Vec<(String, &mut toml::Value)> = match value {
+                toml::Value::Table(table) => table.iter_mut().map(|(key, item)| (format!("{name}.{key}"), item)).collect(),
+                _ => vec![(name.clone(), value)],
+            };
+            for (key, item) in entries {
+                let source = self.sources.get(&key).map_or("", String::as_str);
+                variables.expand_value(item, &key).with_context(|| format!("Failed to expand {key} from {source}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Build and validate the config the layers describe
+    ///
+    /// # Errors
+    /// Fails when the merged document isn't a valid config.
+    pub fn to_config(&self) -> Result<Config> {
+        let config: Config = toml::Value::Table(self.merged_document())
+            .try_into()
+            .with_context(|| format!("Failed to parse TOML in {}", self.describe_files()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Hash of the merged config, recorded in the lockfile to detect staleness
+    ///
+    /// Taken on the resolved document, after profiles and variables, so a
+    /// profile that starts applying or a changed `${env:...}` value changes it,
+    /// while comments, formatting and `[vars]` that expand the same don't.
+    #[must_use]
+    pub fn config_hash(&self) -> String {
+        config_hash(self.merged_document().to_string().as_bytes())
+    }
+
+    /// Merged document with the package groups, as the config is parsed from
+    fn merged_document(&self) -> toml::Table {
+        let mut document = self.document.clone();
+        document.remove("profiles");
+        let groups = self.packages.iter().map(|group| group.iter().cloned().map(toml::Value::String).collect());
+        document.insert("packages".to_string(), toml::Value::Array(groups.map(toml::Value::Array).collect()));
+        document
+    }
+
+    /// Files joined for messages (`base.toml, config.toml`)
+    #[must_use]
+    pub fn describe_files(&self) -> String {
+        let names: Vec<String> = self.files.iter().map(|file| file.display().to_string()).collect();
+        names.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn write_files(dir: &Path, files: &[(&str, &str)]) -> std::io::Result<()> {
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents)?;
+        }
+        Ok(())
+    }
+
+    /// A base config included by a team config, plus a personal layer
+    fn team_layers(dir: &Path) -> Result<ConfigLayers> {
+        write_files(
+            dir,
+            &[
+                ("base.toml", "packages = [[\"git\", \"curl\"], [\"ripgrep\"]]\n[runtimes]\nnode = \"lts\"\npython = \"3.12\"\n"),
+                ("team.toml", "include = [\"base.toml\"]\nremove = [\"curl\"]\npackages = [\"git\", \"bat\"]\n[runtimes]\nnode = \"20\"\n"),
+                ("me.toml", "packages = [\"jq\"]\n[settings]\npipx_via_uv = true\n"),
+            ],
+        )?;
+        ConfigLayers::load(&[dir.join("team.toml"), dir.join("me.toml")])
+    }
+
+    #[test]
+    fn test_layers_merge_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = team_layers(dir.path()).unwrap().to_config().unwrap();
+
+        assert_eq!(config.packages, [vec!["git"], vec!["ripgrep"], vec!["bat"], vec!["jq"]]);
+        assert_eq!(config.runtimes["node"].get_default_version(), "20");
+        assert_eq!(config.runtimes["python"].get_default_version(), "3.12");
+        assert!(config.settings.pipx_via_uv);
+    }
+
+    #[test]
+    fn test_layers_record_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let layers = team_layers(dir.path()).unwrap();
+        let cases = [
+            ("packages.git", "base.toml"),
+            ("packages.bat", "team.toml"),
+            ("runtimes.node", "team.toml"),
+            ("runtimes.python", "base.toml"),
+            ("settings.pipx_via_uv", "me.toml"),
+        ];
+
+        for (key, file) in cases {
+            assert_eq!(layers.sources[key], dir.path().join(file).display().to_string(), "{key}");
+        }
+        assert!(!layers.sources.contains_key("packages.curl"));
+        assert_eq!(layers.files, ["base.toml", "team.toml", "me.toml"].map(|file| dir.path().join(file)));
+    }
+
+    /// Config hash of a single file with the given contents
+    fn hash_of(dir: &Path, contents: &str) -> Result<String> {
+        fs::write(dir.join("config.toml"), contents)?;
+        Ok(ConfigLayers::load(&[dir.join("config.toml")])?.config_hash())
+    }
+
+    #[test]
+    fn test_config_hash_ignores_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let hashes = [
+            "packages = [\"git\"]\n[runtimes]\nnode = \"20\"\n",
+            "# Team tools\npackages = [ \"git\" ]\n\n[runtimes]\nnode = \"20\"  # LTS\n",
+            "packages = [\"git\"]\n[runtimes]\nnode = \"22\"\n",
+        ]
+        .map(|contents| hash_of(dir.path(), contents).unwrap());
+
+        assert_eq!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+    }
+
+    /// Config hash of a resolved file, with `VERSION` set in the environment
+    fn resolved_hash_of(dir: &Path, contents: &str, version: &str) -> Result<String> {
+        fs::write(dir.join("config.toml"), contents)?;
+        let host = Host { env: [("VERSION".to_string(), version.to_string())].into(), ..Host::default() };
+        let mut layers = ConfigLayers::load(&[dir.join("config.toml")])?;
+        layers.resolve(&[], &host)?;
+        Ok(layers.config_hash())
+    }
+
+    #[test]
+    fn test_config_hash_is_taken_after_interpolation() {
+        let dir = tempfile::tempdir().unwrap();
+        let hashes = [
+            ("[runtimes]\nnode = \"${env:VERSION}\"\n", "20"),
+            ("[vars]\nnode = \"${env:VERSION}\"\n[runtimes]\nnode = \"${var:node}\"\n", "20"),
+            ("[runtimes]\nnode = \"20\"\n", "22"),
+            ("[runtimes]\nnode = \"${env:VERSION}\"\n", "22"),
+        ]
+        .map(|(contents, version)| resolved_hash_of(dir.path(), contents, version).unwrap());
+
+        assert!(hashes[1..3].iter().all(|hash| *hash == hashes[0]), "{hashes:?}");
+        assert_ne!(hashes[0], hashes[3]);
+    }
+
+    #[test]
+    fn test_include_cycles_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        write_files(dir.path(), &[("a.toml", "include = [\"b.toml\"]\n"), ("b.toml", "include = [\"a.toml\"]\n")])
+            .unwrap();
+
+        let error = format!("{:#}", ConfigLayers::load(&[dir.path().join("a.toml")]).unwrap_err());
+        assert!(error.contains("Config include cycle"), "{error}");
+    }
+}
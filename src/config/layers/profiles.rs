@@ -0,0 +1,121 @@
+//! Applying `[profiles.<name>]` blocks on top of the config files
+
+use super::ConfigLayers;
+use crate::common::error::Result;
+use crate::config::profiles::{Activation, ActiveProfile, Host};
+use anyhow::{anyhow, Context};
+
+impl ConfigLayers {
+    /// Apply the profiles selected with `--profile` and those whose `activate` matches the host
+    ///
+    /// Profiles are applied in name order, after every file.
+    ///
+    /// # Errors
+    /// Fails on an unknown selected profile or a malformed profile block.
+    pub fn apply_profiles(&mut self, selected: &[String], host: &Host) -> Result<()> {
+        let profiles = match self.document.remove("profiles") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => return Err(anyhow!("`profiles` must be a table of [profiles.<name>] blocks")),
+            None => toml::Table::new(),
+        };
+        self.sources.retain(|key, _| !key.starts_with("profiles."));
+        if let Some(unknown) = selected.iter().find(|name| !profiles.contains_key(*name)) {
+            let defined: Vec<&String> = profiles.keys().collect();
+            return Err(anyhow!("Unknown profile '{unknown}' (defined: {})", format_names(&defined)));
+        }
+
+        for (name, block) in profiles {
+            let toml::Value::Table(mut block) = block else {
+                return Err(anyhow!("Profile '{name}' must be a table"));
+            };
+            if let Some(reason) = profile_reason(&name, &mut block, selected, host)? {
+                self.merge(block, &format!("profile {name}"))
+                    .with_context(|| format!("Invalid profile '{name}'"))?;
+                self.profiles.push(ActiveProfile { name, reason });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a profile applies, and why; takes its `activate` table out of the block
+fn profile_reason(name: &str, block: &mut toml::Table, selected: &[String], host: &Host) -> Result<Option<String>> {
+    if let Some(key) = ["include", "profiles"].into_iter().find(|key| block.contains_key(*key)) {
+        return Err(anyhow!("Profile '{name}' can't set `{key}`"));
+    }
+    let activation = match block.remove("activate") {
+        Some(activate) => {
+            Activation::parse(activate).with_context(|| format!("Invalid activate in profile '{name}'"))?
+        }
+        None => Activation::default(),
+    };
+    if selected.iter().any(|s| s == name) {
+        return Ok(Some("--profile".to_string()));
+    }
+    Ok(activation.matches(host))
+}
+
+/// Names joined for messages, or "none"
+fn format_names(names: &[&String]) -> String {
+    if names.is_empty() {
+        return "none".to_string();
+    }
+    names.iter().map(|name| name.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    /// A config with three profiles, loaded on a Linux CI host
+    fn profile_layers(dir: &Path, selected: &[&str]) -> Result<ConfigLayers> {
+        fs::write(
+            dir.join("config.toml"),
+            "packages = [\"git\", \"bat\"]\n\
+             [profiles.ci]\nactivate = { env = [\"CI\"] }\nremove = [\"bat\"]\n\
+             [profiles.mac]\nactivate = { os = \"macos\" }\npackages = [\"jq\"]\n\
+             [profiles.work]\npackages = [\"ripgrep\"]\n[profiles.work.runtimes]\nnode = \"20\"\n",
+        )?;
+        let host = Host {
+            os: "linux".to_string(),
+            env: std::collections::HashMap::from([("CI".to_string(), "1".to_string())]),
+            ..Host::default()
+        };
+        let selected: Vec<String> = selected.iter().map(ToString::to_string).collect();
+        let mut layers = ConfigLayers::load(&[dir.join("config.toml")])?;
+        layers.apply_profiles(&selected, &host)?;
+        Ok(layers)
+    }
+
+    #[test]
+    fn test_profiles_apply_after_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let layers = profile_layers(dir.path(), &["work"]).unwrap();
+
+        let applied: Vec<(&str, &str)> = layers.profiles.iter().map(|p| (p.name.as_str(), p.reason.as_str())).collect();
+        assert_eq!(applied, [("ci", "env CI"), ("work", "--profile")]);
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.get_all_packages(), ["git", "ripgrep"]);
+        assert_eq!(config.runtimes["node"].get_default_version(), "20");
+        assert_eq!(layers.sources["runtimes.node"], "profile work");
+    }
+
+    #[test]
+    fn test_unknown_profiles_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let error = profile_layers(dir.path(), &["home"]).unwrap_err().to_string();
+        assert!(error.contains("Unknown profile 'home' (defined: ci, mac, work)"), "{error}");
+    }
+
+    #[test]
+    fn test_config_hash_covers_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = profile_layers(dir.path(), &[]).unwrap().config_hash();
+        let work = profile_layers(dir.path(), &["work"]).unwrap().config_hash();
+
+        assert_ne!(plain, work);
+        assert_eq!(plain, profile_layers(dir.path(), &[]).unwrap().config_hash());
+    }
+}
@@ -0,0 +1,85 @@
+//! Rendering the merged config with the file or profile of every entry
+
+use super::ConfigLayers;
+use crate::common::error::Result;
+use std::fmt::Write;
+
+impl ConfigLayers {
+    /// The merged config as TOML, each entry commented with the file it came from
+    ///
+    /// # Errors
+    /// Fails when a value can't be written as TOML.
+    pub fn render_resolved(&self) -> Result<String> {
+        let mut out = format!("# Resolved from {}\n", self.describe_files());
+        for profile in &self.profiles {
+            writeln!(out, "# Profile {} ({})", profile.name, profile.reason)?;
+        }
+        out.push('\n');
+        self.render_packages(&mut out);
+        for (key, value) in &self.document {
+            match value {
+                toml::Value::Table(table) => {
+                    writeln!(out, "\n[{}]", toml_key(key))?;
+                    for (name, value) in table {
+                        let line = format!("{} = {}", toml_key(name), value);
+                        writeln!(out, "{line}  # {}", self.source(&format!("{key}.{name}")))?;
+                    }
+                }
+                _ => writeln!(out, "{} = {}  # {}", toml_key(key), value, self.source(key))?,
+            }
+        }
+        Ok(out)
+    }
+
+    /// Write the package groups, one package per line
+    fn render_packages(&self, out: &mut String) {
+        out.push_str("packages = [\n");
+        for group in &self.packages {
+            out.push_str("    [\n");
+            for package in group {
+                let source = self.source(&format!("packages.{package}"));
+                let _ = writeln!(out, "        {},  # {source}", toml_string(package));
+            }
+            out.push_str("    ],\n");
+        }
+        out.push_str("]\n");
+    }
+
+    /// File or profile an entry came from
+    fn source(&self, key: &str) -> &str {
+        self.sources.get(key).map_or("", String::as_str)
+    }
+}
+
+/// Key as written in TOML, quoted unless it is a bare key
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if bare {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+/// String as a TOML basic string, escaped by the toml serializer
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_render_resolved_parses_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "packages = [\"git\", \"we\\\"ird\\u001b\"]\n[runtimes]\n\"no\\u001bde\" = \"20\"\n").unwrap();
+        let layers = ConfigLayers::load(&[path]).unwrap();
+
+        let rendered: toml::Table = toml::from_str(&layers.render_resolved().unwrap()).unwrap();
+        assert_eq!(rendered["packages"], toml::Value::try_from([["git", "we\"ird\u{1b}"]]).unwrap());
+        assert_eq!(rendered["runtimes"]["no\u{1b}de"].as_str(), Some("20"));
+    }
+}
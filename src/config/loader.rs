@@ -2,25 +2,30 @@
 //!
 //! Implements loading, parsing, and validating configuration files.
 
-use crate::domain::Config;
+use crate::domain::{Config, RefreshAfter, SystemInfo, VersionConstraint};
 use crate::builtin;
+use crate::config::{ConfigLayers, Host};
 use crate::common::error::Result;
+use crate::service::package_manager::aur::AUR_HELPERS;
+use crate::domain::runtime::RuntimeSpec;
+use crate::service::runtime::corepack::COREPACK_PACKAGE_MANAGERS;
+use crate::service::runtime::manager::rustup::PROFILES;
+use crate::service::runtime::manager::sdkman::{java_vendor, JAVA_DISTRIBUTIONS};
+use crate::service::runtime::{
+    manager_name_for, require_manager, Ecosystem, GlobalPackage, GLOBAL_PACKAGE_RUNTIMES,
+};
 use anyhow::{anyhow, Context};
-use std::fs;
 use std::path::Path;
 
 impl Config {
-    /// Load configuration from a TOML file
+    /// Load configuration from a TOML file and the files it includes
+    ///
+    /// Profiles whose `activate` matches this machine are applied and
+    /// variables are expanded, as for a run without `--profile`.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
-        let contents = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-
-        let config: Config = toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
-
-        config.validate()?;
-        Ok(config)
+        let mut layers = ConfigLayers::load(&[path.as_ref().to_path_buf()])?;
+        layers.resolve(&[], &Host::detect(&SystemInfo::detect()?))?;
+        layers.to_config()
     }
 
     /// Validate that all packages are available in builtin packages
@@ -38,12 +43,164 @@ impl Config {
         Ok(())
     }
 
+    /// Validate repository names and identifiers
+    fn validate_repositories(&self) -> Result<()> {
+        for (name, repository) in &self.repositories {
+            repository.validate(name)?;
+        }
+        Ok(())
+    }
+
+    /// Validate runtime managers, version constraints and the `requires` graph
+    fn validate_runtimes(&self) -> Result<()> {
+        for (name, spec) in &self.runtimes {
+            if let Some(manager) = spec.get_manager() {
+                require_manager(&manager)
+                    .with_context(|| format!("Invalid manager for runtime '{name}'"))?;
+            }
+            let mut versions = spec.get_versions();
+            versions.push(spec.get_default_version());
+            for version in &versions {
+                VersionConstraint::parse(version)
+                    .with_context(|| format!("Invalid version for runtime '{name}'"))?;
+            }
+            validate_toolchain_options(name, spec)?;
+            validate_global_packages(name, spec)?;
+            validate_distribution(name, spec)?;
+            validate_corepack(name, spec)?;
+            if let Some(interval) = spec.get_refresh_after() {
+                RefreshAfter::parse(interval).with_context(|| format!("Invalid runtime '{name}'"))?;
+            }
+        }
+        self.runtime_install_order()?;
+        Ok(())
+    }
+
+    /// Validate the AUR helper named for bootstrapping and installer overrides
+    fn validate_settings(&self) -> Result<()> {
+        if let Some(helper) = self.settings.aur_bootstrap.as_deref() {
+            if !AUR_HELPERS.contains(&helper) {
+                return Err(anyhow!(
+                    "Unsupported aur_bootstrap helper '{helper}' (expected one of: {})",
+                    AUR_HELPERS.join(", ")
+                ));
+            }
+        }
+        if let Some(interval) = self.settings.refresh_after.as_deref() {
+            RefreshAfter::parse(interval).context("Invalid [settings] refresh_after")?;
+        }
+        self.validate_installer_overrides()
+    }
+
+    /// Validate `[settings.installers]` manager names and checksums
+    fn validate_installer_overrides(&self) -> Result<()> {
+        for (manager, installer) in &self.settings.installers {
+            require_manager(manager).context("Invalid [settings.installers] entry")?;
+            if let Some(sha256) = &installer.sha256 {
+                if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(anyhow!("Installer sha256 for '{manager}' must be 64 hex characters"));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Validate the entire configuration
     ///
-    /// Ensures all package references are valid builtin packages.
+    /// Ensures all package references are valid builtin packages and that
+    /// repository definitions are well-formed.
     pub fn validate(&self) -> Result<()> {
         self.validate_packages()?;
+        self.validate_repositories()?;
+        self.validate_runtimes()?;
+        self.validate_settings()?;
         Ok(())
     }
 
 }
+
+/// Components, targets and profiles only exist for rustup toolchains
+fn validate_toolchain_options(name: &str, spec: &RuntimeSpec) -> Result<()> {
+    if !spec.has_toolchain_options() {
+        return Ok(());
+    }
+    if manager_name_for(name, spec) != "rustup" {
+        return Err(anyhow!(
+            "Runtime '{name}' sets components, targets or profile, which need the rustup manager"
+        ));
+    }
+    match spec.get_profile() {
+        Some(profile) if !PROFILES.contains(&profile) => Err(anyhow!(
+            "Unknown rustup profile '{profile}' for runtime '{name}' (expected one of: {})",
+            PROFILES.join(", ")
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Global packages need a runtime with its own package tooling
+fn validate_global_packages(name: &str, spec: &RuntimeSpec) -> Result<()> {
+    let packages = spec.get_global_packages();
+    if packages.is_empty() {
+        return Ok(());
+    }
+    let ecosystem = Ecosystem::for_runtime(name).ok_or_else(|| {
+        anyhow!(
+            "Runtime '{name}' sets global_packages, which are only supported for: {}",
+            GLOBAL_PACKAGE_RUNTIMES.join(", ")
+        )
+    })?;
+    for package in packages {
+        GlobalPackage::parse(ecosystem, package).with_context(|| format!("Invalid global_packages for runtime '{name}'"))?;
+    }
+    Ok(())
+}
+
+/// Distributions are SDKMAN Java vendors
+fn validate_distribution(name: &str, spec: &RuntimeSpec) -> Result<()> {
+    let Some(distribution) = spec.get_distribution() else {
+        return Ok(());
+    };
+    if name != "java" || manager_name_for(name, spec) != "sdkman" {
+        return Err(anyhow!(
+            "Runtime '{name}' sets distribution, which needs java with the sdkman manager"
+        ));
+    }
+    if java_vendor(distribution).is_none() {
+        let names: Vec<&str> = JAVA_DISTRIBUTIONS.iter().map(|(name, _)| *name).collect();
+        return Err(anyhow!(
+            "Unknown java distribution '{distribution}' (expected one of: {})",
+            names.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Corepack runs inside fnm or mise Node versions, and pins need it enabled
+fn validate_corepack(name: &str, spec: &RuntimeSpec) -> Result<()> {
+    let pins = spec.get_package_managers().map_or(0, std::collections::BTreeMap::len);
+    if !spec.uses_corepack() {
+        return match pins {
+            0 => Ok(()),
+            _ => Err(anyhow!("Runtime '{name}' sets package_managers, which need corepack = true")),
+        };
+    }
+    let manager = manager_name_for(name, spec);
+    if !matches!(name, "node" | "nodejs") || !matches!(manager.as_str(), "fnm" | "mise") {
+        return Err(anyhow!(
+            "Runtime '{name}' enables corepack, which needs node with the fnm or mise manager"
+        ));
+    }
+    let unknown = spec
+        .get_package_managers()
+        .into_iter()
+        .flat_map(|pins| pins.keys())
+        .find(|pm| !COREPACK_PACKAGE_MANAGERS.contains(&pm.as_str()));
+    match unknown {
+        Some(pm) => Err(anyhow!(
+            "Unknown package manager '{pm}' for runtime '{name}' (expected one of: {})",
+            COREPACK_PACKAGE_MANAGERS.join(", ")
+        )),
+        None => Ok(()),
+    }
+}
@@ -1,9 +1,14 @@
 //! Configuration loading and parsing
 
 pub mod builder;
+pub mod interpolate;
+pub mod layers;
 pub mod loader;
+pub mod profiles;
 pub mod statefile;
 
 // Builder methods are implemented directly on PackageConfig in domain
 // Loader methods are implemented directly on Config in domain
-pub use statefile::StateFile;
+pub use layers::ConfigLayers;
+pub use profiles::{ActiveProfile, Host};
+pub use statefile::{CorepackExtras, StateFile, ToolchainExtras, STATE_SCHEMA_VERSION};
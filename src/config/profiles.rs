@@ -0,0 +1,213 @@
+//! Config profiles
+//!
+//! `[profiles.<name>]` blocks hold packages, `remove`, runtimes, settings and
+//! other config entries that only apply on some machines. A profile is applied
+//! when selected with `--profile`, or automatically when every condition in its
+//! `activate` table matches the machine:
+//!
+//! ```toml
+//! [profiles.work]
+//! activate = { hostname = ["work-*"], os = "macos" }
+//! packages = ["kubectl"]
+//!
+//! [profiles.ci]
+//! activate = { env = ["CI=true"] }
+//! remove = ["neovim"]
+//! ```
+
+use crate::common::error::Result;
+use crate::domain::SystemInfo;
+use crate::service::os_detection::detect_hostname;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Conditions under which a profile applies without `--profile`
+///
+/// Every condition given must match; a list matches when any entry does.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Activation {
+    /// Hostname patterns, `*` matching any run of characters (`"work-*"`)
+    #[serde(default)]
+    pub hostname: Vec<String>,
+    /// Environment variables that must be set (`"CI"`) or equal a value (`"CI=true"`)
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// Operating system (`"macos"`, `"linux"`)
+    #[serde(default)]
+    pub os: Option<String>,
+    /// Architecture (`"x86_64"`, `"arm64"`)
+    #[serde(default)]
+    pub arch: Option<String>,
+    /// Linux distribution, its os-release ID (`"ubuntu"`, `"fedora"`)
+    #[serde(default)]
+    pub distro: Option<String>,
+    /// Whether running inside WSL
+    #[serde(default)]
+    pub wsl: Option<bool>,
+}
+
+/// The machine profiles are activated against
+#[derive(Debug, Clone, Default)]
+pub struct Host {
+    /// Hostname
+    pub hostname: String,
+    /// Operating system name (`"macos"`)
+    pub os: String,
+    /// Architecture name (`"arm64"`)
+    pub arch: String,
+    /// Distribution name (`"ubuntu"`)
+    pub distro: String,
+    /// Whether running inside WSL
+    pub wsl: bool,
+    /// Environment variables
+    pub env: HashMap<String, String>,
+}
+
+impl Host {
+    /// Describe the machine devstrap runs on
+    #[must_use]
+    pub fn detect(system_info: &SystemInfo) -> Self {
+        Self {
+            hostname: detect_hostname(),
+            os: system_info.os.as_str().to_string(),
+            arch: system_info.arch.as_str().to_string(),
+            distro: system_info.distro.as_str().to_string(),
+            wsl: system_info.is_wsl,
+            env: std::env::vars().collect(),
+        }
+    }
+}
+
+/// A profile applied to the config, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveProfile {
+    /// Profile name
+    pub name: String,
+    /// Why it applies (`--profile`, `hostname work-*`)
+    pub reason: String,
+}
+
+impl Activation {
+    /// Parse the `activate` table of a profile
+    ///
+    /// # Errors
+    /// Fails on unknown conditions or values of the wrong type.
+    pub fn parse(value: toml::Value) -> Result<Self> {
+        Ok(value.try_into()?)
+    }
+
+    /// The conditions that matched, or None when the profile doesn't apply
+    ///
+    /// A profile without conditions never applies on its own.
+    #[must_use]
+    pub fn matches(&self, host: &Host) -> Option<String> {
+        let conditions = [
+            list_condition("hostname", &self.hostname, |pattern| wildcard_match(pattern, &host.hostname)),
+            list_condition("env", &self.env, |variable| env_matches(variable, &host.env)),
+            self.os.as_deref().map(|os| (os.eq_ignore_ascii_case(&host.os), format!("os {os}"))),
+            self.arch.as_deref().map(|arch| (arch.eq_ignore_ascii_case(&host.arch), format!("arch {arch}"))),
+            self.distro.as_deref().map(|distro| (distro.eq_ignore_ascii_case(&host.distro), format!("distro {distro}"))),
+            self.wsl.map(|wsl| (wsl == host.wsl, format!("wsl = {wsl}"))),
+        ];
+
+        let given: Vec<(bool, String)> = conditions.into_iter().flatten().collect();
+        if given.is_empty() || given.iter().any(|(matched, _)| !matched) {
+            return None;
+        }
+        let reasons: Vec<String> = given.into_iter().map(|(_, reason)| reason).collect();
+        Some(reasons.join(", "))
+    }
+}
+
+/// Match a list condition, naming the entry that matched
+fn list_condition(name: &str, entries: &[String], matches: impl Fn(&str) -> bool) -> Option<(bool, String)> {
+    if entries.is_empty() {
+        return None;
+    }
+    let matched = entries.iter().find(|entry| matches(entry));
+    Some((matched.is_some(), format!("{name} {}", matched.map_or("", String::as_str))))
+}
+
+/// Whether an environment variable is set (`CI`) or has a value (`CI=true`)
+fn env_matches(condition: &str, env: &HashMap<String, String>) -> bool {
+    match condition.split_once('=') {
+        Some((name, value)) => env.get(name).is_some_and(|actual| actual == value),
+        None => env.get(condition).is_some_and(|actual| !actual.is_empty()),
+    }
+}
+
+/// Case-insensitive match where `*` stands for any run of characters
+#[must_use]
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.to_lowercase(), text.to_lowercase());
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work_laptop() -> Host {
+        Host {
+            hostname: "Work-Laptop.corp.example.com".to_string(),
+            os: "macos".to_string(),
+            arch: "arm64".to_string(),
+            distro: "unknown".to_string(),
+            wsl: false,
+            env: HashMap::from([("CI".to_string(), "true".to_string()), ("EMPTY".to_string(), String::new())]),
+        }
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        let cases = [
+            ("work-*", "work-laptop", true),
+            ("*.corp.example.com", "build.corp.example.com", true),
+            ("*laptop*", "my-laptop-2", true),
+            ("work-*-2", "work-laptop", false),
+            ("home", "home", true),
+            ("home", "homelab", false),
+            ("*", "", true),
+        ];
+
+        for (pattern, text, matched) in cases {
+            assert_eq!(wildcard_match(pattern, text), matched, "{pattern} {text}");
+        }
+    }
+
+    #[test]
+    fn test_activation_matches() {
+        let host = work_laptop();
+        let cases = [
+            ("hostname = [\"home\", \"work-*\"]", Some("hostname work-*")),
+            ("hostname = [\"work-*\"]\nos = \"linux\"", None),
+            ("env = [\"CI=true\"]\nos = \"macos\"", Some("env CI=true, os macos")),
+            ("env = [\"EMPTY\"]", None),
+            ("env = [\"MISSING\"]", None),
+            ("arch = \"arm64\"\nwsl = false", Some("arch arm64, wsl = false")),
+            ("distro = \"ubuntu\"", None),
+            ("", None),
+        ];
+
+        for (activate, reason) in cases {
+            let activation = Activation::parse(toml::from_str(activate).unwrap()).unwrap();
+            assert_eq!(activation.matches(&host).as_deref(), reason, "{activate}");
+        }
+    }
+}
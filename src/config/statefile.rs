@@ -3,11 +3,12 @@
 //! Tracks which packages and runtimes devstrap has installed to enable
 //! safe sync operations with --prune flag.
 
-use crate::domain::InstallMethod;
+use crate::common::persist::{load_recovering, write_atomic};
+use crate::common::schema::{keep_original, load_migrated, Migration};
+use crate::domain::{InstallMethod, Repository};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 
 /// Record of a package installation
@@ -32,9 +33,70 @@ pub struct RuntimeRecord {
     pub installed_at: String,
 }
 
+/// Record of a version manager bootstrapped by devstrap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagerRecord {
+    /// URL of the installer script
+    pub installer_url: String,
+    /// sha256 of the installer script
+    pub sha256: String,
+    /// Timestamp of installation
+    pub installed_at: String,
+}
+
+/// Rustup profile, components and targets applied by devstrap
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolchainExtras {
+    /// Profile set with `rustup set profile`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Components added to each toolchain
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub components: BTreeSet<String>,
+    /// Targets added to each toolchain
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub targets: BTreeSet<String>,
+}
+
+impl ToolchainExtras {
+    /// Whether nothing is recorded
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Corepack shims and package managers enabled by devstrap for a Node runtime
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorepackExtras {
+    /// Whether `corepack enable` was run
+    #[serde(default)]
+    pub enabled: bool,
+    /// Package managers pinned with `corepack install --global`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub package_managers: BTreeSet<String>,
+}
+
+/// Upgrades from each earlier state file format, oldest first
+///
+/// - 0: no `schema_version`
+/// - 1: same records, with `schema_version`
+const STATE_MIGRATIONS: &[Migration] = &[state_v0_to_v1];
+
+/// State file format written by this version of devstrap
+pub const STATE_SCHEMA_VERSION: usize = STATE_MIGRATIONS.len();
+
 /// State file tracking what devstrap has installed
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StateFile {
+    /// Format of the file, see [`STATE_SCHEMA_VERSION`]
+    #[serde(default)]
+    pub schema_version: usize,
+
+    /// Schema version the file was upgraded from when it was loaded
+    #[serde(skip)]
+    pub migrated_from: Option<usize>,
+
     /// Packages installed by devstrap
     #[serde(default)]
     pub packages: HashMap<String, PackageRecord>,
@@ -42,6 +104,34 @@ pub struct StateFile {
     /// Runtimes installed by devstrap
     #[serde(default)]
     pub runtimes: HashMap<String, RuntimeRecord>,
+
+    /// Homebrew taps added by devstrap
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub taps: BTreeSet<String>,
+
+    /// Third-party repositories added by devstrap
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repositories: BTreeMap<String, Repository>,
+
+    /// Version managers bootstrapped by devstrap
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub managers: BTreeMap<String, ManagerRecord>,
+
+    /// Rustup components and targets devstrap added
+    #[serde(default, skip_serializing_if = "ToolchainExtras::is_empty")]
+    pub rustup: ToolchainExtras,
+
+    /// Global packages devstrap installed, by runtime
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub global_packages: BTreeMap<String, BTreeSet<String>>,
+
+    /// Corepack shims and package managers devstrap enabled, by runtime
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub corepack: BTreeMap<String, CorepackExtras>,
+
+    /// Profiles applied to the config at the last sync
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub profiles: BTreeSet<String>,
 }
 
 impl StateFile {
@@ -52,22 +142,25 @@ impl StateFile {
             return Ok(Self::default());
         }
 
-        let contents = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read state file: {}", path.display()))?;
-
-        let state: StateFile = toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse state file: {}", path.display()))?;
-
+        let (mut state, migrated_from): (Self, _) = load_migrated(path, "state file", STATE_MIGRATIONS)?;
+        state.migrated_from = migrated_from;
         Ok(state)
     }
 
+    /// Load state file from path, recovering from a backup if it is corrupt
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        load_recovering(path.as_ref(), |path| Self::from_file(path))
+    }
+
     /// Save state file to path
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let path = path.as_ref();
-        let contents = toml::to_string_pretty(self)
+        let state = Self { schema_version: STATE_SCHEMA_VERSION, ..self.clone() };
+        let contents = toml::to_string_pretty(&state)
             .context("Failed to serialize state file")?;
 
-        fs::write(path, contents)
+        keep_original(path, "state file", self.migrated_from)?;
+        write_atomic(path, contents.as_bytes())
             .with_context(|| format!("Failed to write state file: {}", path.display()))?;
 
         Ok(())
@@ -103,6 +196,91 @@ impl StateFile {
         self.runtimes.remove(name);
     }
 
+    /// Record a Homebrew tap added by devstrap
+    pub fn add_tap(&mut self, tap: String) {
+        self.taps.insert(tap);
+    }
+
+    /// Remove a Homebrew tap from the state
+    pub fn remove_tap(&mut self, tap: &str) {
+        self.taps.remove(tap);
+    }
+
+    /// Record a repository added by devstrap
+    pub fn add_repository(&mut self, name: String, repository: Repository) {
+        self.repositories.insert(name, repository);
+    }
+
+    /// Remove a repository from the state
+    pub fn remove_repository(&mut self, name: &str) {
+        self.repositories.remove(name);
+    }
+
+    /// Record a version manager bootstrapped by devstrap
+    pub fn add_manager(&mut self, name: String, installer_url: String, sha256: String) {
+        let record = ManagerRecord {
+            installer_url,
+            sha256,
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        };
+        self.managers.insert(name, record);
+    }
+
+    /// Record rustup extras applied to the toolchains
+    ///
+    /// Components and targets accumulate until pruned, so ones dropped from
+    /// config can still be removed later.
+    pub fn add_toolchain_extras(&mut self, extras: &ToolchainExtras) {
+        if extras.profile.is_some() {
+            self.rustup.profile.clone_from(&extras.profile);
+        }
+        self.rustup.components.extend(extras.components.iter().cloned());
+        self.rustup.targets.extend(extras.targets.iter().cloned());
+    }
+
+    /// Record global packages installed into a runtime
+    pub fn add_global_packages(&mut self, runtime: &str, packages: &BTreeSet<String>) {
+        self.global_packages
+            .entry(runtime.to_string())
+            .or_default()
+            .extend(packages.iter().cloned());
+    }
+
+    /// Remove a global package from the state
+    pub fn remove_global_package(&mut self, runtime: &str, package: &str) {
+        if let Some(packages) = self.global_packages.get_mut(runtime) {
+            packages.remove(package);
+            if packages.is_empty() {
+                self.global_packages.remove(runtime);
+            }
+        }
+    }
+
+    /// Record Corepack shims and package managers enabled for a runtime
+    ///
+    /// Package managers accumulate until pruned, like rustup extras.
+    pub fn add_corepack(&mut self, runtime: &str, extras: &CorepackExtras) {
+        let recorded = self.corepack.entry(runtime.to_string()).or_default();
+        recorded.enabled |= extras.enabled;
+        recorded.package_managers.extend(extras.package_managers.iter().cloned());
+    }
+
+    /// Remove a Corepack package manager, or the shims altogether, from the state
+    pub fn remove_corepack(&mut self, runtime: &str, package_manager: Option<&str>) {
+        let Some(extras) = self.corepack.get_mut(runtime) else {
+            return;
+        };
+        match package_manager {
+            Some(name) => {
+                extras.package_managers.remove(name);
+            }
+            None => extras.enabled = false,
+        }
+        if *extras == CorepackExtras::default() {
+            self.corepack.remove(runtime);
+        }
+    }
+
     /// Check if a package was installed by devstrap
     #[must_use]
     pub fn has_package(&self, id: &str) -> bool {
@@ -127,3 +305,6 @@ impl StateFile {
         self.runtimes.keys().cloned().collect()
     }
 }
+
+/// The first versioned format kept every record as it was
+fn state_v0_to_v1(_document: &mut toml::Table) {}
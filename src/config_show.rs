@@ -0,0 +1,32 @@
+//! Config command - inspect the layered configuration
+
+use crate::cli::Cli;
+use crate::init::{detect_system, load_config_layers};
+use colored::Colorize;
+use std::process;
+
+/// Run `devstrap config show`
+///
+/// Lists the config files in load order and the profiles applied after them, or with `resolved` prints the
+/// merged config, profiles applied, annotated with each entry's file or profile.
+pub fn run_config_show(cli: &Cli, resolved: bool) {
+    let layers = load_config_layers(cli, &detect_system());
+    if !resolved {
+        println!("{}", "Config files, in load order:".bold());
+        for (index, file) in layers.files.iter().enumerate() {
+            println!("  {}. {}", index + 1, file.display());
+        }
+        for profile in &layers.profiles {
+            println!("  + profile {} ({})", profile.name, profile.reason);
+        }
+        return;
+    }
+
+    match layers.render_resolved() {
+        Ok(rendered) => print!("{rendered}"),
+        Err(e) => {
+            eprintln!("{} Failed to render the resolved config: {:#}", "✗".red(), e);
+            process::exit(1);
+        }
+    }
+}
@@ -1,8 +1,24 @@
 //! Main configuration structure
 
+use super::dependency_graph::dependency_order;
+use super::refresh_after::RefreshAfter;
+use super::repository::Repository;
 use super::runtime::RuntimeSpec;
+use super::settings::Settings;
+use crate::common::error::Result;
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Parse a nested package group, rejecting non-string entries
+fn parse_package_group<E: serde::de::Error>(arr: Vec<toml::Value>) -> Result<Vec<String>, E> {
+    arr.into_iter()
+        .map(|v| match v {
+            toml::Value::String(s) => Ok(s),
+            _ => Err(E::custom("Expected string in package array")),
+        })
+        .collect()
+}
 
 /// Custom deserializer for packages field
 /// Supports both flat array and nested arrays
@@ -51,14 +67,7 @@ where
                         }
                         is_nested = Some(true);
 
-                        let group: Vec<String> = arr
-                            .into_iter()
-                            .map(|v| match v {
-                                toml::Value::String(s) => Ok(s),
-                                _ => Err(de::Error::custom("Expected string in package array")),
-                            })
-                            .collect::<Result<_, _>>()?;
-                        result.push(group);
+                        result.push(parse_package_group(arr)?);
                     }
                     _ => {
                         return Err(de::Error::custom("Expected string or array in packages field"))
@@ -82,7 +91,7 @@ where
 }
 
 /// Main configuration structure matching config.toml format
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Config {
     /// Packages to install - supports both flat and nested arrays
     ///
@@ -121,6 +130,22 @@ pub struct Config {
     /// System-provided compilers/languages to install
     #[serde(default)]
     pub system_languages: HashMap<String, bool>,
+
+    /// Third-party package repositories configured before installing
+    ///
+    /// # Example
+    ///
+    /// ```toml
+    /// [repositories.neovim]
+    /// type = "ppa"
+    /// ppa = "neovim-ppa/unstable"
+    /// ```
+    #[serde(default)]
+    pub repositories: BTreeMap<String, Repository>,
+
+    /// Global sync settings
+    #[serde(default)]
+    pub settings: Settings,
 }
 
 impl Config {
@@ -141,4 +166,30 @@ impl Config {
     pub fn get_package_version(&self, package_id: &str) -> Option<&str> {
         self.package_versions.get(package_id).map(String::as_str)
     }
+
+    /// Re-resolution interval of a runtime, its own or the global one
+    ///
+    /// # Errors
+    /// Fails on an interval that doesn't parse.
+    pub fn refresh_after_for(&self, runtime: &str) -> Result<Option<RefreshAfter>> {
+        self.runtimes
+            .get(runtime)
+            .and_then(RuntimeSpec::get_refresh_after)
+            .or(self.settings.refresh_after.as_deref())
+            .map(RefreshAfter::parse)
+            .transpose()
+    }
+
+    /// Get runtime names in installation order, requirements first
+    ///
+    /// # Errors
+    /// Fails on a `requires` naming an unconfigured runtime or on a cycle.
+    pub fn runtime_install_order(&self) -> Result<Vec<String>> {
+        let requires = self
+            .runtimes
+            .iter()
+            .map(|(name, spec)| (name.clone(), spec.get_requires()))
+            .collect();
+        dependency_order(&requires).context("Invalid runtime requirements")
+    }
 }
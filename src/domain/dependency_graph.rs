@@ -0,0 +1,85 @@
+//! Dependency ordering for runtimes and frameworks
+//!
+//! Each entry may name one requirement (`requires = "node"`). Entries are
+//! ordered so requirements come first; ties are broken by name so the order
+//! is stable between runs.
+
+use crate::common::error::Result;
+use anyhow::bail;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Order entries so every requirement precedes its dependents
+///
+/// # Errors
+/// Fails when an entry requires something that is not configured, or when
+/// requirements form a cycle.
+pub fn dependency_order(requires: &BTreeMap<String, Option<String>>) -> Result<Vec<String>> {
+    let mut ordered = Vec::with_capacity(requires.len());
+    let mut done = BTreeSet::new();
+
+    for name in requires.keys() {
+        let mut chain = Vec::new();
+        let mut current = name.as_str();
+
+        while !done.contains(current) {
+            if chain.contains(&current) {
+                chain.push(current);
+                bail!("Dependency cycle: {}", chain.join(" -> "));
+            }
+            chain.push(current);
+
+            match requires.get(current) {
+                Some(Some(required)) => current = required,
+                Some(None) => break,
+                None => bail!("'{}' requires '{current}', which is not configured", chain[chain.len() - 2]),
+            }
+        }
+
+        for entry in chain.into_iter().rev() {
+            if done.insert(entry) {
+                ordered.push(entry.to_string());
+            }
+        }
+    }
+
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(entries: &[(&str, Option<&str>)]) -> BTreeMap<String, Option<String>> {
+        entries
+            .iter()
+            .map(|(name, requires)| ((*name).to_string(), requires.map(String::from)))
+            .collect()
+    }
+
+    #[test]
+    fn test_requirements_come_first() {
+        let order = dependency_order(&graph(&[
+            ("angular", Some("typescript")),
+            ("typescript", Some("node")),
+            ("node", None),
+            ("go", None),
+        ]))
+        .unwrap();
+
+        assert_eq!(order, ["node", "typescript", "angular", "go"]);
+    }
+
+    #[test]
+    fn test_invalid_graphs_are_rejected() {
+        let cases = [
+            (graph(&[("typescript", Some("node"))]), "'typescript' requires 'node'"),
+            (graph(&[("a", Some("b")), ("b", Some("a"))]), "cycle: a -> b -> a"),
+            (graph(&[("a", Some("a"))]), "cycle: a -> a"),
+        ];
+
+        for (requires, expected) in cases {
+            let error = dependency_order(&requires).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+}
@@ -14,6 +14,18 @@ pub enum Os {
     Unknown,
 }
 
+impl Os {
+    /// Canonical lowercase name used in URL templates and checksum keys
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MacOs => "macos",
+            Self::Linux => "linux",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 /// Linux distributions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Distro {
@@ -39,6 +51,25 @@ pub enum Distro {
     Unknown,
 }
 
+impl Distro {
+    /// Canonical lowercase name, the os-release ID
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ubuntu => "ubuntu",
+            Self::Debian => "debian",
+            Self::Fedora => "fedora",
+            Self::Rhel => "rhel",
+            Self::CentOs => "centos",
+            Self::Rocky => "rocky",
+            Self::Alma => "almalinux",
+            Self::Arch => "arch",
+            Self::Manjaro => "manjaro",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 /// System architecture
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Arch {
@@ -52,6 +83,19 @@ pub enum Arch {
     Unknown,
 }
 
+impl Arch {
+    /// Canonical lowercase name used in URL templates and checksum keys
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::X86_64 => "x86_64",
+            Self::Arm64 => "arm64",
+            Self::Armv7 => "armv7",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 /// Available package managers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PackageManager {
@@ -71,6 +115,8 @@ pub enum PackageManager {
     Npm,
     /// pipx package manager (Python)
     Pipx,
+    /// uv tool installer (Python)
+    Uv,
 }
 
 impl PackageManager {
@@ -86,6 +132,7 @@ impl PackageManager {
             Self::Cargo => "cargo",
             Self::Npm => "npm",
             Self::Pipx => "pipx",
+            Self::Uv => "uv",
         }
     }
 
@@ -101,6 +148,7 @@ impl PackageManager {
             Self::Cargo => "Cargo",
             Self::Npm => "npm",
             Self::Pipx => "pipx",
+            Self::Uv => "uv",
         }
     }
 }
@@ -0,0 +1,29 @@
+//! Upgrading lockfiles written by earlier versions of devstrap
+
+use super::platform::host_platform;
+use crate::common::schema::Migration;
+
+/// Upgrades from each earlier lockfile format, oldest first
+///
+/// - 0: runtimes and packages at the top level, no `schema_version`
+/// - 1: runtimes and packages under `platforms.<os>-<arch>`
+pub(super) const LOCKFILE_MIGRATIONS: &[Migration] = &[lockfile_v0_to_v1];
+
+/// Move the top-level locks into the section of the machine reading the file
+///
+/// Lockfiles before per-platform sections were only ever used on one machine.
+fn lockfile_v0_to_v1(document: &mut toml::Table) {
+    let section: toml::Table = ["runtimes", "packages"]
+        .into_iter()
+        .filter_map(|key| Some((key.to_string(), document.remove(key)?)))
+        .collect();
+    if section.is_empty() {
+        return;
+    }
+    if let toml::Value::Table(platforms) = document
+        .entry("platforms")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+    {
+        platforms.entry(host_platform()).or_insert(toml::Value::Table(section));
+    }
+}
@@ -0,0 +1,178 @@
+//! Runtime and package version lockfile
+//!
+//! Manages the lockfile that pins "latest", "lts", "3.11", "^20" and other
+//! version constraints to specific resolved versions, and records the
+//! version each package was installed at, for reproducible installations.
+//!
+//! Resolved identifiers differ between machines (SDKMAN builds, Homebrew vs
+//! APT package names), so runtimes and packages are locked per platform
+//! (`macos-arm64`, `linux-x86_64`). The lockfile also carries a hash of the
+//! config it was written for, so a sync can tell when it is stale.
+
+mod migrations;
+mod platform;
+mod runtime_extras;
+mod runtimes;
+
+pub use platform::{host_platform, parse_platform, platform_key, LockedPackage, PlatformLock};
+pub use runtimes::ResolvedRuntime;
+
+use crate::common::error::Result;
+use crate::common::persist::{load_recovering, write_atomic};
+use crate::common::schema::{keep_original, load_migrated};
+use anyhow::Context;
+use migrations::LOCKFILE_MIGRATIONS;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Lockfile format written by this version of devstrap
+pub const LOCKFILE_SCHEMA_VERSION: usize = LOCKFILE_MIGRATIONS.len();
+
+/// Lockfile containing resolved runtime versions
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct Lockfile {
+    /// Format of the file, see [`LOCKFILE_SCHEMA_VERSION`]
+    #[serde(default)]
+    pub schema_version: usize,
+
+    /// Schema version the file was upgraded from when it was loaded
+    #[serde(skip)]
+    pub migrated_from: Option<usize>,
+
+    /// sha256 of the config the lockfile was last synced against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_hash: Option<String>,
+
+    /// Platform whose section the accessors read and write (the host when unset)
+    #[serde(skip)]
+    pub platform: Option<String>,
+
+    /// Runtime and package locks per platform (`macos-arm64`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub platforms: BTreeMap<String, PlatformLock>,
+
+    /// Resolved framework versions
+    #[serde(default)]
+    pub frameworks: HashMap<String, ResolvedFramework>,
+
+    /// Checksums of the installer scripts used to bootstrap version managers
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub installers: BTreeMap<String, LockedInstaller>,
+}
+
+/// Installer script a version manager was bootstrapped from
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LockedInstaller {
+    /// URL the script was downloaded from
+    pub url: String,
+    /// sha256 of the script
+    pub sha256: String,
+}
+
+/// Resolved framework with pinned version
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResolvedFramework {
+    /// The version that was requested
+    pub requested: String,
+    /// The actual resolved version
+    pub resolved: String,
+    /// Timestamp of resolution
+    #[serde(default)]
+    pub resolved_at: Option<String>,
+}
+
+impl Lockfile {
+    /// Load lockfile from path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let (mut lockfile, migrated_from): (Self, _) = load_migrated(path, "lockfile", LOCKFILE_MIGRATIONS)?;
+        lockfile.migrated_from = migrated_from;
+        Ok(lockfile)
+    }
+
+    /// Load lockfile from path, recovering from a backup if it is corrupt
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        load_recovering(path.as_ref(), |path| Self::from_file(path))
+    }
+
+    /// Save lockfile to path
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut lockfile = self.clone();
+        lockfile.schema_version = LOCKFILE_SCHEMA_VERSION;
+        lockfile.platforms.retain(|_, section| !section.runtimes.is_empty() || !section.packages.is_empty());
+        let contents = toml::to_string_pretty(&lockfile).context("Failed to serialize lockfile")?;
+
+        keep_original(path, "lockfile", self.migrated_from)?;
+        write_atomic(path, contents.as_bytes())
+            .with_context(|| format!("Failed to write lockfile: {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Why the lockfile no longer matches the config or this platform, if it doesn't
+    ///
+    /// A lockfile that was never synced isn't stale, just empty.
+    #[must_use]
+    pub fn staleness(&self, config_hash: &str) -> Option<String> {
+        if self.config_hash.is_none() && self.platforms.is_empty() {
+            return None;
+        }
+        if self.config_hash.as_deref() != Some(config_hash) {
+            return Some("the config changed since devstrap.lock was last synced".to_string());
+        }
+        if self.platform_lock().is_none() {
+            return Some(format!("devstrap.lock has no locks for {}", self.current_platform()));
+        }
+        None
+    }
+
+    /// Get resolved version for a framework
+    #[must_use]
+    pub fn get_framework_version(&self, name: &str) -> Option<&str> {
+        self.frameworks.get(name).map(|f| f.resolved.as_str())
+    }
+
+    /// Update or add a framework resolution
+    pub fn set_framework(&mut self, name: String, requested: String, resolved: String) {
+        let resolved_at = chrono::Utc::now().to_rfc3339();
+        self.frameworks.insert(
+            name,
+            ResolvedFramework {
+                requested,
+                resolved,
+                resolved_at: Some(resolved_at),
+            },
+        );
+    }
+
+    /// Checksum pinned for a manager's installer script
+    #[must_use]
+    pub fn installer_sha256(&self, manager: &str) -> Option<&str> {
+        self.installers.get(manager).map(|i| i.sha256.as_str())
+    }
+
+    /// Pin the installer script a manager was bootstrapped from
+    pub fn lock_installer(&mut self, manager: &str, url: &str, sha256: &str) {
+        self.installers.insert(
+            manager.to_string(),
+            LockedInstaller {
+                url: url.to_string(),
+                sha256: sha256.to_string(),
+            },
+        );
+    }
+}
+
+/// sha256 of a config file's contents
+#[must_use]
+pub fn config_hash(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
@@ -0,0 +1,105 @@
+//! Per-platform sections of the lockfile and the package locks they hold
+
+use super::runtimes::ResolvedRuntime;
+use super::Lockfile;
+use crate::domain::{Arch, Os};
+use crate::service::os_detection::{detect_arch, detect_os};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Runtime and package locks of one platform
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PlatformLock {
+    /// Resolved runtime versions
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub runtimes: HashMap<String, ResolvedRuntime>,
+
+    /// Installed package versions, keyed by package id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, LockedPackage>,
+}
+
+/// Package version installed with a specific method
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedPackage {
+    /// Installation method (`brew`, `apt-get`, `cargo`, `npm`, ...)
+    pub method: String,
+    /// Package name used by that method
+    pub name: String,
+    /// Version the method installed
+    pub version: String,
+}
+
+impl Lockfile {
+    /// Lock the given platform's section instead of the host's
+    #[must_use]
+    pub fn for_platform(mut self, platform: &str) -> Self {
+        self.platform = Some(platform.to_string());
+        self
+    }
+
+    /// Platform whose section is read and written
+    #[must_use]
+    pub fn current_platform(&self) -> String {
+        self.platform.clone().unwrap_or_else(host_platform)
+    }
+
+    /// Locks of the current platform, if it has any
+    #[must_use]
+    pub fn platform_lock(&self) -> Option<&PlatformLock> {
+        self.platforms.get(&self.current_platform())
+    }
+
+    /// Locks of the current platform, created when missing
+    pub fn platform_lock_mut(&mut self) -> &mut PlatformLock {
+        self.platforms.entry(self.current_platform()).or_default()
+    }
+
+    /// Drop the current platform's locks, keeping installer pins and other platforms
+    pub fn clear_platform(&mut self) {
+        self.platforms.remove(&self.current_platform());
+    }
+
+    /// Version locked for a package, if it was locked with this method and name
+    #[must_use]
+    pub fn locked_package_version(&self, id: &str, method: &str, name: &str) -> Option<&str> {
+        self.platform_lock()?
+            .packages
+            .get(id)
+            .filter(|locked| locked.method == method && locked.name == name)
+            .map(|locked| locked.version.as_str())
+    }
+
+    /// Record the version a package is installed at
+    pub fn lock_package(&mut self, id: &str, method: &str, name: &str, version: &str) {
+        self.platform_lock_mut().packages.insert(
+            id.to_string(),
+            LockedPackage {
+                method: method.to_string(),
+                name: name.to_string(),
+                version: version.to_string(),
+            },
+        );
+    }
+}
+
+/// Section key of a platform (`macos-arm64`)
+#[must_use]
+pub fn platform_key(os: Os, arch: Arch) -> String {
+    format!("{}-{}", os.as_str(), arch.as_str())
+}
+
+/// Section key of the machine devstrap runs on
+#[must_use]
+pub fn host_platform() -> String {
+    platform_key(detect_os(), detect_arch())
+}
+
+/// Split a section key back into its os and arch
+#[must_use]
+pub fn parse_platform(key: &str) -> Option<(Os, Arch)> {
+    let (os, arch) = key.split_once('-')?;
+    let os = [Os::MacOs, Os::Linux].into_iter().find(|o| o.as_str() == os)?;
+    let arch = [Arch::X86_64, Arch::Arm64, Arch::Armv7].into_iter().find(|a| a.as_str() == arch)?;
+    Some((os, arch))
+}
@@ -0,0 +1,50 @@
+//! Global packages and Corepack package managers locked per runtime version
+
+use super::Lockfile;
+use std::collections::BTreeMap;
+
+impl Lockfile {
+    /// Version a global package spec resolved to for one runtime version
+    #[must_use]
+    pub fn locked_global_package(&self, name: &str, version: &str, spec: &str) -> Option<&str> {
+        self.runtime(name)?
+            .global_packages
+            .get(version)?
+            .get(spec)
+            .map(String::as_str)
+    }
+
+    /// Replace the global packages locked for one runtime version
+    pub fn lock_global_packages(&mut self, name: &str, version: &str, packages: BTreeMap<String, String>) {
+        let Some(runtime) = self.platform_lock_mut().runtimes.get_mut(name) else {
+            return;
+        };
+        if packages.is_empty() {
+            runtime.global_packages.remove(version);
+        } else {
+            runtime.global_packages.insert(version.to_string(), packages);
+        }
+    }
+
+    /// Version a Corepack package manager (`pnpm@9`) resolved to for one Node version
+    #[must_use]
+    pub fn locked_package_manager(&self, name: &str, version: &str, pin: &str) -> Option<&str> {
+        self.runtime(name)?
+            .package_managers
+            .get(version)?
+            .get(pin)
+            .map(String::as_str)
+    }
+
+    /// Replace the Corepack package managers locked for one Node version
+    pub fn lock_package_managers(&mut self, name: &str, version: &str, pins: BTreeMap<String, String>) {
+        let Some(runtime) = self.platform_lock_mut().runtimes.get_mut(name) else {
+            return;
+        };
+        if pins.is_empty() {
+            runtime.package_managers.remove(version);
+        } else {
+            runtime.package_managers.insert(version.to_string(), pins);
+        }
+    }
+}
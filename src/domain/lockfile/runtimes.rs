@@ -0,0 +1,135 @@
+//! Runtime locks of the current platform
+
+use super::Lockfile;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Resolved runtime with pinned version
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ResolvedRuntime {
+    /// The default version constraint ("latest", "lts", "3.11", "^20", ...)
+    pub requested: String,
+    /// The actual resolved default version
+    pub resolved: String,
+    /// Manager used for installation
+    pub manager: String,
+    /// Vendor distribution the versions were resolved within
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<String>,
+    /// Timestamp of resolution
+    #[serde(default)]
+    pub resolved_at: Option<String>,
+    /// Every installed constraint mapped to the version it resolved to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub versions: BTreeMap<String, String>,
+    /// Global packages per installed version, each spec mapped to its resolved version
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub global_packages: BTreeMap<String, BTreeMap<String, String>>,
+    /// Corepack package managers per installed version, `name@requirement` mapped to its resolved version
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub package_managers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Lockfile {
+    /// Runtime locked on the current platform
+    pub(super) fn runtime(&self, name: &str) -> Option<&ResolvedRuntime> {
+        self.platform_lock()?.runtimes.get(name)
+    }
+
+    /// Get resolved version for a runtime
+    #[must_use]
+    pub fn get_runtime_version(&self, name: &str) -> Option<&str> {
+        self.runtime(name).map(|r| r.resolved.as_str())
+    }
+
+    /// Resolved version locked for a runtime constraint
+    #[must_use]
+    pub fn locked_version(&self, name: &str, constraint: &str) -> Option<&str> {
+        let runtime = self.runtime(name)?;
+        runtime
+            .versions
+            .get(constraint)
+            .or_else(|| (runtime.requested == constraint).then_some(&runtime.resolved))
+            .map(String::as_str)
+    }
+
+    /// Record the version a runtime constraint resolved to
+    pub fn lock_version(&mut self, name: &str, constraint: &str, resolved: &str, manager: &str) {
+        let resolved_at = chrono::Utc::now().to_rfc3339();
+        let runtime = self
+            .platform_lock_mut()
+            .runtimes
+            .entry(name.to_string())
+            .or_insert_with(|| ResolvedRuntime {
+                requested: constraint.to_string(),
+                resolved: resolved.to_string(),
+                manager: manager.to_string(),
+                ..ResolvedRuntime::default()
+            });
+
+        runtime.manager = manager.to_string();
+        runtime.resolved_at = Some(resolved_at);
+        runtime
+            .versions
+            .insert(constraint.to_string(), resolved.to_string());
+    }
+
+    /// When a runtime's versions were last resolved (RFC 3339)
+    #[must_use]
+    pub fn resolved_at(&self, name: &str) -> Option<&str> {
+        self.runtime(name)?.resolved_at.as_deref()
+    }
+
+    /// Update or add a runtime's default resolution, keeping other locked versions
+    ///
+    /// Reinstalling an unchanged lock keeps its `resolved_at`, so
+    /// `refresh_after` counts from when the version was actually resolved.
+    pub fn set_runtime(&mut self, name: &str, requested: &str, resolved: &str, manager: &str) {
+        let unchanged = self.runtime(name).is_some_and(|runtime| runtime.manager == manager)
+            && self.locked_version(name, requested) == Some(resolved);
+        if !unchanged {
+            self.lock_version(name, requested, resolved, manager);
+        }
+        if let Some(runtime) = self.platform_lock_mut().runtimes.get_mut(name) {
+            runtime.requested = requested.to_string();
+            runtime.resolved = resolved.to_string();
+        }
+    }
+
+    /// Put back a runtime's entry as another lockfile has it, dropping it when absent there
+    pub fn restore_runtime(&mut self, name: &str, previous: &Lockfile) {
+        match previous.runtime(name).cloned() {
+            Some(runtime) => self.platform_lock_mut().runtimes.insert(name.to_string(), runtime),
+            None => self.platform_lock_mut().runtimes.remove(name),
+        };
+    }
+
+    /// Record the distribution a runtime resolves within
+    ///
+    /// Versions locked for another distribution name that vendor's builds,
+    /// so switching distributions drops the runtime's locks.
+    pub fn lock_distribution(&mut self, name: &str, distribution: Option<&str>) {
+        let current = self.runtime(name).map(|r| r.distribution.as_deref());
+        if current == Some(distribution) {
+            return;
+        }
+        match distribution {
+            Some(distribution) => {
+                let runtime = ResolvedRuntime {
+                    distribution: Some(distribution.to_string()),
+                    ..ResolvedRuntime::default()
+                };
+                self.platform_lock_mut().runtimes.insert(name.to_string(), runtime);
+            }
+            None => {
+                self.platform_lock_mut().runtimes.remove(name);
+            }
+        }
+    }
+
+    /// Check if a runtime constraint needs resolution (not locked yet)
+    #[must_use]
+    pub fn needs_resolution(&self, name: &str, requested: &str) -> bool {
+        self.locked_version(name, requested).is_none()
+    }
+}
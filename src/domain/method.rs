@@ -16,6 +16,12 @@ pub enum InstallMethod {
     Cargo,
     /// Pipx application
     Pipx,
+    /// uv tool application
+    Uv,
+    /// Arch User Repository package built by yay or paru
+    Aur,
+    /// Verified binary downloaded from a templated URL
+    Url,
     /// System package (already installed)
     System,
     /// GitHub release
@@ -25,7 +31,7 @@ pub enum InstallMethod {
 impl InstallMethod {
     /// Get priority for this installation method
     ///
-    /// Priority hierarchy: OS default (10) > npm (8) > cargo (6) > pipx (4) > system (2) > github (1)
+    /// Priority hierarchy: OS default (10) > npm (8) > url (7) > cargo (6) > uv (5) > pipx (4) > AUR (3) > system (2) > github (1)
     #[must_use]
     pub fn priority(&self, system_default: Option<PackageManager>) -> u8 {
         match self {
@@ -37,8 +43,11 @@ impl InstallMethod {
                 }
             }
             Self::Npm => 8,
+            Self::Url => 7,
             Self::Cargo => 6,
+            Self::Uv => 5,
             Self::Pipx => 4,
+            Self::Aur => 3,
             Self::System => 2,
             Self::GitHub => 1,
         }
@@ -52,6 +61,9 @@ impl InstallMethod {
             Self::Npm => "npm",
             Self::Cargo => "cargo",
             Self::Pipx => "pipx",
+            Self::Uv => "uv",
+            Self::Aur => "aur",
+            Self::Url => "url",
             Self::System => "system",
             Self::GitHub => "github",
         }
@@ -65,6 +77,9 @@ impl InstallMethod {
             Self::Npm => "npm".to_string(),
             Self::Cargo => "Cargo".to_string(),
             Self::Pipx => "pipx".to_string(),
+            Self::Uv => "uv".to_string(),
+            Self::Aur => "AUR".to_string(),
+            Self::Url => "URL".to_string(),
             Self::System => "System".to_string(),
             Self::GitHub => "GitHub".to_string(),
         }
@@ -77,9 +92,12 @@ impl InstallMethod {
             "npm" => Some(Self::Npm),
             "cargo" => Some(Self::Cargo),
             "pipx" => Some(Self::Pipx),
+            "uv" => Some(Self::Uv),
+            "aur" => Some(Self::Aur),
+            "url" => Some(Self::Url),
             "system" => Some(Self::System),
             "github" => Some(Self::GitHub),
-            "brew" => Some(Self::SystemDefault(PackageManager::Brew)),
+            "brew" | "homebrew" => Some(Self::SystemDefault(PackageManager::Brew)),
             "apt" => Some(Self::SystemDefault(PackageManager::Apt)),
             "pacman" => Some(Self::SystemDefault(PackageManager::Pacman)),
             "dnf" => Some(Self::SystemDefault(PackageManager::Dnf)),
@@ -1,6 +1,7 @@
 //! Domain models and entities
 
 pub mod config;
+pub mod dependency_graph;
 pub mod enums;
 pub mod framework;
 pub mod lockfile;
@@ -8,17 +9,33 @@ pub mod method;
 pub mod package;
 pub mod package_config;
 pub mod priority;
+pub mod refresh_after;
+pub mod repository;
 pub mod runtime;
+pub mod settings;
 pub mod system;
+pub mod url_source;
+pub mod version;
+pub mod version_constraint;
 
 #[cfg(test)]
 mod package_config_tests;
 
 pub use config::Config;
+pub use dependency_graph::dependency_order;
 pub use enums::{Arch, Distro, Os, PackageManager};
-pub use lockfile::Lockfile;
+pub use lockfile::{
+    config_hash, host_platform, parse_platform, platform_key, LockedPackage, Lockfile, PlatformLock,
+    LOCKFILE_SCHEMA_VERSION,
+};
 pub use method::InstallMethod;
 pub use package::Package;
 pub use package_config::PackageConfig;
 pub use priority::determine_best_method;
+pub use refresh_after::RefreshAfter;
+pub use repository::Repository;
+pub use settings::{InstallerOverride, Settings};
 pub use system::SystemInfo;
+pub use url_source::{ArchiveType, UrlSource};
+pub use version::Version;
+pub use version_constraint::VersionConstraint;
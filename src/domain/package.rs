@@ -17,6 +17,10 @@ pub struct Package {
     pub current_method: Option<InstallMethod>,
     /// Preferred installation method
     pub preferred_method: InstallMethod,
+    /// Version pinned in `package_versions` (used by the url method)
+    pub version: Option<String>,
+    /// Version recorded in `devstrap.lock` to install exactly (`sync --locked`)
+    pub locked_version: Option<String>,
 }
 
 impl Package {
@@ -28,6 +32,8 @@ impl Package {
             config,
             current_method: None,
             preferred_method,
+            version: None,
+            locked_version: None,
         }
     }
 
@@ -39,6 +45,9 @@ impl Package {
             InstallMethod::Npm => "npm",
             InstallMethod::Cargo => "cargo",
             InstallMethod::Pipx => "pipx",
+            InstallMethod::Uv => "uv",
+            InstallMethod::Aur => "aur",
+            InstallMethod::Url => "url",
             InstallMethod::GitHub => "github",
             InstallMethod::System => return Some(self.id.clone()),
         };
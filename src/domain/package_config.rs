@@ -1,6 +1,12 @@
 //! Package configuration and builder
 
+use super::repository::Repository;
+use super::url_source::UrlSource;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Methods available to any package that declares a generic `name`
+const SYSTEM_METHODS: [&str; 5] = ["brew", "apt", "pacman", "dnf", "yum"];
 
 /// Package configuration defining installation method
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -25,6 +31,18 @@ pub struct PackageConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pipx: Option<String>,
 
+    /// uv tool package name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uv: Option<String>,
+
+    /// Python version for uv tool installs (e.g. "3.12")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub python: Option<String>,
+
+    /// Extra packages installed into the uv tool environment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub with: Vec<String>,
+
     /// GitHub repository (format: "owner/repo")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github: Option<String>,
@@ -33,6 +51,14 @@ pub struct PackageConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub brew: Option<String>,
 
+    /// Homebrew tap providing the formula or cask (format: "owner/repo")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tap: Option<String>,
+
+    /// Install the Homebrew package as a cask (GUI application)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cask: bool,
+
     /// APT package name (if different from package ID)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub apt: Option<String>,
@@ -44,6 +70,18 @@ pub struct PackageConfig {
     /// DNF/YUM package name (if different from package ID)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dnf: Option<String>,
+
+    /// AUR package name (Arch/Manjaro, built via yay or paru)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aur: Option<String>,
+
+    /// Direct download source (templated URL plus checksum)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<UrlSource>,
+
+    /// Third-party repositories required by the system package
+    #[serde(default, rename = "repository", skip_serializing_if = "BTreeMap::is_empty")]
+    pub repositories: BTreeMap<String, Repository>,
 }
 
 impl PackageConfig {
@@ -54,6 +92,8 @@ impl PackageConfig {
             "npm" => self.npm.as_deref(),
             "cargo" => self.cargo.as_deref(),
             "pipx" => self.pipx.as_deref(),
+            "uv" => self.uv.as_deref(),
+            "aur" => self.aur.as_deref(),
             "github" => self.github.as_deref(),
             "brew" => self.brew.as_deref().or(self.name.as_deref()),
             "apt" => self.apt.as_deref().or(self.name.as_deref()),
@@ -69,31 +109,41 @@ impl PackageConfig {
         let mut methods = Vec::new();
 
         if self.name.is_some() {
-            methods.extend(vec![
-                "brew".to_string(),
-                "apt".to_string(),
-                "pacman".to_string(),
-                "dnf".to_string(),
-                "yum".to_string(),
-            ]);
-        }
-        if self.npm.is_some() {
-            methods.push("npm".to_string());
-        }
-        if self.cargo.is_some() {
-            methods.push("cargo".to_string());
-        }
-        if self.pipx.is_some() {
-            methods.push("pipx".to_string());
-        }
-        if self.github.is_some() {
-            methods.push("github".to_string());
+            methods.extend(SYSTEM_METHODS.iter().map(ToString::to_string));
         }
-        if self.brew.is_some() {
-            methods.push("brew".to_string());
+
+        let specific = [
+            ("npm", &self.npm),
+            ("cargo", &self.cargo),
+            ("pipx", &self.pipx),
+            ("uv", &self.uv),
+            ("aur", &self.aur),
+            ("github", &self.github),
+            ("brew", &self.brew),
+        ];
+        methods.extend(
+            specific
+                .iter()
+                .filter(|(_, name)| name.is_some())
+                .map(|(method, _)| (*method).to_string()),
+        );
+
+        if self.url.is_some() {
+            methods.push("url".to_string());
         }
 
         methods
     }
-}
 
+    /// Route a pipx-defined package through `uv tool` (see `Settings::pipx_via_uv`)
+    ///
+    /// Packages that already name a uv tool are left untouched.
+    #[must_use]
+    pub fn with_pipx_via_uv(&self) -> Self {
+        let mut config = self.clone();
+        if config.uv.is_none() {
+            config.uv.clone_from(&config.pipx);
+        }
+        config
+    }
+}
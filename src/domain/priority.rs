@@ -4,7 +4,7 @@
 
 use super::method::InstallMethod;
 use crate::domain::PackageConfig;
-use crate::domain::{PackageManager, SystemInfo};
+use crate::domain::{Arch, Os, PackageManager, SystemInfo};
 
 /// Determine the best installation method for a package
 ///
@@ -32,6 +32,10 @@ pub fn determine_best_method(
                 InstallMethod::Npm => system_info.has_package_manager(PackageManager::Npm),
                 InstallMethod::Cargo => system_info.has_package_manager(PackageManager::Cargo),
                 InstallMethod::Pipx => system_info.has_package_manager(PackageManager::Pipx),
+                InstallMethod::Uv => system_info.has_package_manager(PackageManager::Uv),
+                // A helper can be bootstrapped, so only the platform matters here
+                InstallMethod::Aur => system_default == Some(PackageManager::Pacman),
+                InstallMethod::Url => system_info.os != Os::Unknown && system_info.arch != Arch::Unknown,
                 InstallMethod::GitHub | InstallMethod::System => true, // Always available
             };
 
@@ -54,7 +58,7 @@ pub fn determine_best_method(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Arch, Distro, Os};
+    use crate::domain::Distro;
 
     fn create_test_system_info() -> SystemInfo {
         SystemInfo {
@@ -87,6 +91,7 @@ mod tests {
             apt: None,
             pacman: None,
             dnf: None,
+            ..PackageConfig::default()
         };
 
         let method = determine_best_method(&package_config, &system_info).unwrap();
@@ -96,4 +101,27 @@ mod tests {
             InstallMethod::SystemDefault(PackageManager::Brew)
         ));
     }
+
+    #[test]
+    fn test_aur_only_on_arch_and_below_official_repos() {
+        let arch = SystemInfo {
+            os: Os::Linux,
+            distro: Distro::Arch,
+            arch: Arch::X86_64,
+            default_package_manager: Some(PackageManager::Pacman),
+            available_package_managers: vec![PackageManager::Pacman],
+            is_wsl: false,
+            is_apple_silicon: false,
+        };
+
+        let aur_only = PackageConfig::new().aur("visual-studio-code-bin");
+        assert_eq!(determine_best_method(&aur_only, &arch), Some(InstallMethod::Aur));
+        assert_eq!(determine_best_method(&aur_only, &create_test_system_info()), None);
+
+        let both = PackageConfig::new().name("neovim").aur("neovim-git");
+        assert_eq!(
+            determine_best_method(&both, &arch),
+            Some(InstallMethod::SystemDefault(PackageManager::Pacman))
+        );
+    }
 }
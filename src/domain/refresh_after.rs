@@ -0,0 +1,84 @@
+//! Time-based re-resolution of floating runtime versions
+//!
+//! `refresh_after = "7d"` lets a sync re-resolve "latest", "lts", "3.12" and
+//! other floating constraints once their lock is older than the interval,
+//! instead of keeping them until `sync --refresh`.
+
+use crate::common::error::Result;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Duration, Utc};
+
+/// How long a floating version stays locked before a sync re-resolves it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshAfter(Duration);
+
+impl RefreshAfter {
+    /// Parse an interval of hours, days or weeks (`"12h"`, `"7d"`, `"2w"`)
+    pub fn parse(interval: &str) -> Result<Self> {
+        let invalid = || anyhow!("Invalid refresh_after '{interval}' (expected hours, days or weeks, like \"7d\")");
+        let split = interval.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let (count, unit) = interval.split_at(split);
+        let count: i64 = count.parse().with_context(invalid)?;
+
+        let duration = match unit {
+            "h" => Duration::hours(count),
+            "d" => Duration::days(count),
+            "w" => Duration::weeks(count),
+            _ => return Err(invalid()),
+        };
+        if count == 0 {
+            return Err(invalid());
+        }
+        Ok(Self(duration))
+    }
+
+    /// Whether a lock resolved at `resolved_at` (RFC 3339) has expired
+    ///
+    /// Locks without a readable timestamp are treated as expired.
+    #[must_use]
+    pub fn is_due(&self, resolved_at: Option<&str>, now: DateTime<Utc>) -> bool {
+        resolved_at
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map_or(true, |at| now.signed_duration_since(at) >= self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let cases = [
+            ("12h", Some(Duration::hours(12))),
+            ("7d", Some(Duration::days(7))),
+            ("2w", Some(Duration::days(14))),
+            ("0d", None),
+            ("7", None),
+            ("d", None),
+            ("7 days", None),
+            ("1m", None),
+        ];
+
+        for (interval, expected) in cases {
+            assert_eq!(RefreshAfter::parse(interval).ok(), expected.map(RefreshAfter), "{interval}");
+        }
+    }
+
+    #[test]
+    fn test_is_due() {
+        let policy = RefreshAfter::parse("7d").unwrap();
+        let now = DateTime::parse_from_rfc3339("2026-10-18T12:00:00Z").unwrap().with_timezone(&Utc);
+        let cases = [
+            (Some("2026-10-17T12:00:00+00:00"), false),
+            (Some("2026-10-11T12:00:00Z"), true),
+            (Some("2026-09-01T08:00:00Z"), true),
+            (Some("yesterday"), true),
+            (None, true),
+        ];
+
+        for (resolved_at, due) in cases {
+            assert_eq!(policy.is_due(resolved_at, now), due, "{resolved_at:?}");
+        }
+    }
+}
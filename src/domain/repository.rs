@@ -0,0 +1,112 @@
+//! Third-party package repository definitions
+//!
+//! Repositories are declared in the `[repositories]` config table or on a
+//! builtin package, and are configured before the package manager runs.
+
+use super::enums::{Distro, PackageManager};
+use crate::common::error::Result;
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// A third-party package repository
+///
+/// # Example
+///
+/// ```toml
+/// [repositories.github-cli]
+/// type = "apt"
+/// url = "https://cli.github.com/packages"
+/// key = "https://cli.github.com/packages/githubcli-archive-keyring.gpg"
+/// suite = "stable"
+///
+/// [repositories.neovim]
+/// type = "ppa"
+/// ppa = "neovim-ppa/unstable"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Repository {
+    /// APT source whose signing key is stored in `/etc/apt/keyrings`
+    Apt {
+        /// Repository base URL
+        url: String,
+        /// Signing key URL (ASCII-armored or binary)
+        key: String,
+        /// Suite; `{codename}` expands to the distribution codename
+        #[serde(default = "default_suite")]
+        suite: String,
+        /// Components to enable
+        #[serde(default = "default_components")]
+        components: Vec<String>,
+    },
+    /// Ubuntu PPA (format: "owner/name")
+    Ppa {
+        /// PPA identifier
+        ppa: String,
+    },
+    /// DNF/YUM `.repo` file downloaded from a URL
+    Dnf {
+        /// URL of the `.repo` file
+        url: String,
+    },
+    /// Fedora COPR project (format: "owner/project")
+    Copr {
+        /// COPR project identifier
+        copr: String,
+    },
+    /// Pacman repository section in `/etc/pacman.conf`
+    Pacman {
+        /// Server URL; defaults to the system mirrorlist (e.g. for multilib)
+        #[serde(default)]
+        server: Option<String>,
+        /// Optional `SigLevel` for the section
+        #[serde(default)]
+        sig_level: Option<String>,
+    },
+}
+
+fn default_suite() -> String {
+    "{codename}".to_string()
+}
+
+fn default_components() -> Vec<String> {
+    vec!["main".to_string()]
+}
+
+impl Repository {
+    /// Whether this repository can be used by the given package manager
+    #[must_use]
+    pub fn applies_to(&self, pm: PackageManager, distro: Distro) -> bool {
+        match self {
+            Self::Apt { .. } => pm == PackageManager::Apt,
+            Self::Ppa { .. } => pm == PackageManager::Apt && distro == Distro::Ubuntu,
+            Self::Dnf { .. } => matches!(pm, PackageManager::Dnf | PackageManager::Yum),
+            Self::Copr { .. } => pm == PackageManager::Dnf,
+            Self::Pacman { .. } => pm == PackageManager::Pacman,
+        }
+    }
+
+    /// Short human-readable description for plans and logs
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Apt { url, .. } | Self::Dnf { url } => url.clone(),
+            Self::Ppa { ppa } => format!("ppa:{ppa}"),
+            Self::Copr { copr } => format!("copr:{copr}"),
+            Self::Pacman { server, .. } => server.clone().unwrap_or_else(|| "mirrorlist".to_string()),
+        }
+    }
+
+    /// Validate identifiers before anything touches the system
+    pub fn validate(&self, name: &str) -> Result<()> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+            bail!("Repository name '{name}' may only contain letters, digits, '-', '_' and '.'");
+        }
+        match self {
+            Self::Ppa { ppa: id } | Self::Copr { copr: id } if id.split('/').count() != 2 => {
+                bail!("Repository '{name}': '{id}' must have the form owner/name")
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Runtime specification and version management
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Runtime specification for language/tool version management
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -9,23 +10,52 @@ pub enum RuntimeSpec {
     /// Simple version string: "latest", "lts", "stable", "3.11", etc.
     Simple(String),
     /// Detailed configuration with manager and version info
-    Detailed {
-        /// Single version to install
-        #[serde(default)]
-        version: Option<String>,
-        /// Multiple versions to install
-        #[serde(default)]
-        versions: Option<Vec<String>>,
-        /// Default version to use
-        #[serde(default)]
-        default: Option<String>,
-        /// Version manager to use
-        #[serde(default)]
-        manager: Option<String>,
-        /// Required runtime dependency
-        #[serde(default)]
-        requires: Option<String>,
-    },
+    Detailed(Box<RuntimeOptions>),
+}
+
+/// Options of a detailed runtime specification
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuntimeOptions {
+    /// Single version to install
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Multiple versions to install
+    #[serde(default)]
+    pub versions: Option<Vec<String>>,
+    /// Default version to use
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Version manager to use
+    #[serde(default)]
+    pub manager: Option<String>,
+    /// Required runtime dependency
+    #[serde(default)]
+    pub requires: Option<String>,
+    /// Rustup components added to every toolchain ("clippy", "rust-src")
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// Rustup cross-compilation targets added to every toolchain
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Rustup profile ("minimal", "default" or "complete")
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Vendor build of the runtime ("temurin", "zulu", "corretto")
+    #[serde(default)]
+    pub distribution: Option<String>,
+    /// Packages installed globally into every version ("typescript@5")
+    #[serde(default)]
+    pub global_packages: Vec<String>,
+    /// Enable Corepack shims in every Node version
+    #[serde(default)]
+    pub corepack: bool,
+    /// Package managers pinned through Corepack (`pnpm = "9"`)
+    #[serde(default)]
+    pub package_managers: BTreeMap<String, String>,
+    /// Re-resolve floating versions locked longer ago than this ("7d"),
+    /// overriding `[settings] refresh_after`
+    #[serde(default)]
+    pub refresh_after: Option<String>,
 }
 
 impl RuntimeSpec {
@@ -34,12 +64,10 @@ impl RuntimeSpec {
     pub fn get_versions(&self) -> Vec<String> {
         match self {
             Self::Simple(v) => vec![v.clone()],
-            Self::Detailed {
-                version, versions, ..
-            } => {
-                if let Some(vs) = versions {
+            Self::Detailed(options) => {
+                if let Some(vs) = &options.versions {
                     vs.clone()
-                } else if let Some(v) = version {
+                } else if let Some(v) = &options.version {
                     vec![v.clone()]
                 } else {
                     vec!["latest".to_string()]
@@ -53,15 +81,11 @@ impl RuntimeSpec {
     pub fn get_default_version(&self) -> String {
         match self {
             Self::Simple(v) => v.clone(),
-            Self::Detailed {
-                default,
-                version,
-                versions,
-                ..
-            } => default
+            Self::Detailed(options) => options
+                .default
                 .clone()
-                .or_else(|| version.clone())
-                .or_else(|| versions.as_ref().and_then(|vs| vs.first().cloned()))
+                .or_else(|| options.version.clone())
+                .or_else(|| options.versions.as_ref().and_then(|vs| vs.first().cloned()))
                 .unwrap_or_else(|| "latest".to_string()),
         }
     }
@@ -71,7 +95,7 @@ impl RuntimeSpec {
     pub fn get_manager(&self) -> Option<String> {
         match self {
             Self::Simple(_) => None,
-            Self::Detailed { manager, .. } => manager.clone(),
+            Self::Detailed(options) => options.manager.clone(),
         }
     }
 
@@ -80,7 +104,82 @@ impl RuntimeSpec {
     pub fn get_requires(&self) -> Option<String> {
         match self {
             Self::Simple(_) => None,
-            Self::Detailed { requires, .. } => requires.clone(),
+            Self::Detailed(options) => options.requires.clone(),
+        }
+    }
+
+    /// Get the vendor distribution
+    #[must_use]
+    pub fn get_distribution(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(options) => options.distribution.as_deref(),
+        }
+    }
+
+    /// Get rustup components to add
+    #[must_use]
+    pub fn get_components(&self) -> &[String] {
+        match self {
+            Self::Simple(_) => &[],
+            Self::Detailed(options) => &options.components,
+        }
+    }
+
+    /// Get rustup targets to add
+    #[must_use]
+    pub fn get_targets(&self) -> &[String] {
+        match self {
+            Self::Simple(_) => &[],
+            Self::Detailed(options) => &options.targets,
+        }
+    }
+
+    /// Get the rustup profile
+    #[must_use]
+    pub fn get_profile(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(options) => options.profile.as_deref(),
+        }
+    }
+
+    /// Get the packages to install globally into each version
+    #[must_use]
+    pub fn get_global_packages(&self) -> &[String] {
+        match self {
+            Self::Simple(_) => &[],
+            Self::Detailed(options) => &options.global_packages,
+        }
+    }
+
+    /// Whether Corepack shims are enabled
+    #[must_use]
+    pub fn uses_corepack(&self) -> bool {
+        matches!(self, Self::Detailed(options) if options.corepack)
+    }
+
+    /// Get the package managers pinned through Corepack
+    #[must_use]
+    pub fn get_package_managers(&self) -> Option<&BTreeMap<String, String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(options) => Some(&options.package_managers),
+        }
+    }
+
+    /// Get the runtime's own re-resolution interval
+    #[must_use]
+    pub fn get_refresh_after(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(options) => options.refresh_after.as_deref(),
         }
     }
+
+    /// Whether any rustup-only option is set
+    #[must_use]
+    pub fn has_toolchain_options(&self) -> bool {
+        !self.get_components().is_empty() || !self.get_targets().is_empty() || self.get_profile().is_some()
+    }
 }
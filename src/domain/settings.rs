@@ -0,0 +1,53 @@
+//! Global sync settings
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Global settings from the `[settings]` table
+///
+/// # Example
+///
+/// ```toml
+/// [settings]
+/// pipx_via_uv = true
+/// aur_bootstrap = "paru"
+/// refresh_after = "7d"
+///
+/// [settings.installers.rustup]
+/// url = "https://mirror.example.com/rustup-init.sh"
+/// sha256 = "..."
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Settings {
+    /// Install packages defined with `pipx` through `uv tool` when uv is available
+    #[serde(default)]
+    pub pipx_via_uv: bool,
+
+    /// AUR helper to build when neither yay nor paru is installed ("yay" or "paru")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aur_bootstrap: Option<String>,
+
+    /// Re-resolve floating runtime versions locked longer ago than this ("7d")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_after: Option<String>,
+
+    /// Per-manager overrides for the installer script used to bootstrap it
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub installers: BTreeMap<String, InstallerOverride>,
+}
+
+/// Replacement URL, pinned checksum or trust-on-first-use for a manager's installer script
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct InstallerOverride {
+    /// Download the installer from here instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Expected sha256 of the installer script
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+
+    /// Run the script without a pinned checksum, locking whatever was downloaded
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_unpinned: bool,
+}
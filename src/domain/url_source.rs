@@ -0,0 +1,191 @@
+//! Direct download sources for the `url` installation method
+//!
+//! Many tools publish binaries at predictable URLs rather than as GitHub
+//! releases. A source describes how to build the URL for this platform and
+//! how to verify what was downloaded.
+
+use super::enums::{Arch, Os};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Archive format of a downloaded artifact
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ArchiveType {
+    /// The download is the executable itself
+    #[default]
+    #[serde(rename = "binary")]
+    Binary,
+    /// Gzip-compressed tarball
+    #[serde(rename = "tar.gz", alias = "tgz")]
+    TarGz,
+    /// XZ-compressed tarball
+    #[serde(rename = "tar.xz")]
+    TarXz,
+    /// Zip archive
+    #[serde(rename = "zip")]
+    Zip,
+}
+
+/// URL template and verification data for a downloadable binary
+///
+/// Templates may use `{version}`, `{os}` and `{arch}`. The platform names
+/// default to devstrap's own (`linux`/`macos`, `x86_64`/`arm64`/`armv7`)
+/// and can be overridden per package, e.g. `x86_64 -> amd64`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UrlSource {
+    /// Download URL template
+    pub url: String,
+
+    /// Version installed when `package_versions` doesn't pin one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Expected sha256 of the default version, keyed by `<os>-<arch>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sha256: BTreeMap<String, String>,
+
+    /// Checksum file URL template (single hash or `sha256sum` format)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_url: Option<String>,
+
+    /// Archive format of the download
+    #[serde(default)]
+    pub archive: ArchiveType,
+
+    /// Path of the executable inside the archive (templated)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+
+    /// Overrides for the `{os}` placeholder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub os_names: BTreeMap<String, String>,
+
+    /// Overrides for the `{arch}` placeholder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub arch_names: BTreeMap<String, String>,
+}
+
+impl UrlSource {
+    /// Create a source from a URL template
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the default version
+    #[must_use]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Add the expected sha256 of the default version for a platform
+    #[must_use]
+    pub fn sha256(mut self, platform: impl Into<String>, hash: impl Into<String>) -> Self {
+        self.sha256.insert(platform.into(), hash.into());
+        self
+    }
+
+    /// Set the checksum file URL template
+    #[must_use]
+    pub fn checksum_url(mut self, url: impl Into<String>) -> Self {
+        self.checksum_url = Some(url.into());
+        self
+    }
+
+    /// Set the archive format
+    #[must_use]
+    pub fn archive(mut self, archive: ArchiveType) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Set the executable path inside the archive
+    #[must_use]
+    pub fn binary(mut self, path: impl Into<String>) -> Self {
+        self.binary = Some(path.into());
+        self
+    }
+
+    /// Override the `{os}` name for an operating system
+    #[must_use]
+    pub fn os_name(mut self, os: Os, name: impl Into<String>) -> Self {
+        self.os_names.insert(os.as_str().to_string(), name.into());
+        self
+    }
+
+    /// Override the `{arch}` name for an architecture
+    #[must_use]
+    pub fn arch_name(mut self, arch: Arch, name: impl Into<String>) -> Self {
+        self.arch_names.insert(arch.as_str().to_string(), name.into());
+        self
+    }
+
+    /// Name of the executable placed in the bin directory
+    ///
+    /// The file name of `binary` unless it is templated, else the package id.
+    #[must_use]
+    pub fn installed_binary_name(&self, package_id: &str) -> String {
+        self.binary
+            .as_deref()
+            .and_then(|path| Path::new(path).file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .filter(|name| !name.contains('{'))
+            .unwrap_or_else(|| package_id.to_string())
+    }
+
+    /// Expand `{version}`, `{os}` and `{arch}` in a template
+    #[must_use]
+    pub fn render(&self, template: &str, version: &str, os: Os, arch: Arch) -> String {
+        let os_name = self.os_names.get(os.as_str()).map_or(os.as_str(), String::as_str);
+        let arch_name = self
+            .arch_names
+            .get(arch.as_str())
+            .map_or(arch.as_str(), String::as_str);
+
+        template
+            .replace("{version}", version)
+            .replace("{os}", os_name)
+            .replace("{arch}", arch_name)
+    }
+
+    /// Known sha256 for a version and platform, if the source pins one
+    #[must_use]
+    pub fn known_sha256(&self, version: &str, os: Os, arch: Arch) -> Option<&str> {
+        if self.version.as_deref() != Some(version) {
+            return None;
+        }
+        self.sha256
+            .get(&format!("{}-{}", os.as_str(), arch.as_str()))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_with_platform_overrides() {
+        let source = UrlSource::new("https://get.helm.sh/helm-v{version}-{os}-{arch}.tar.gz")
+            .version("3.16.2")
+            .os_name(Os::MacOs, "darwin")
+            .arch_name(Arch::X86_64, "amd64")
+            .sha256("linux-x86_64", "abc");
+
+        assert_eq!(
+            source.render(&source.url, "3.16.2", Os::MacOs, Arch::X86_64),
+            "https://get.helm.sh/helm-v3.16.2-darwin-amd64.tar.gz"
+        );
+        assert_eq!(
+            source.render(&source.url, "3.16.2", Os::Linux, Arch::Arm64),
+            "https://get.helm.sh/helm-v3.16.2-linux-arm64.tar.gz"
+        );
+        assert_eq!(source.known_sha256("3.16.2", Os::Linux, Arch::X86_64), Some("abc"));
+        assert_eq!(source.known_sha256("3.15.0", Os::Linux, Arch::X86_64), None);
+    }
+}
@@ -0,0 +1,148 @@
+//! Version parsing and ordering
+//!
+//! Version managers list releases in their own formats (`v20.11.0`,
+//! `3.13.0rc2`, `21.0.2-tem`, `pypy3.10-7.3.12`). This module parses the
+//! common shape so every resolver orders and filters them the same way.
+
+use std::cmp::Ordering;
+
+/// Suffixes that mark a prerelease rather than a vendor or build tag
+const PRERELEASE_MARKERS: [&str; 11] = [
+    "alpha", "beta", "rc", "pre", "preview", "dev", "ea", "nightly", "snapshot", "a", "b",
+];
+
+/// A parsed release version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    /// Original string as listed by the version manager
+    pub raw: String,
+    /// Numeric components (`3.11.4` -> `[3, 11, 4]`)
+    pub parts: Vec<u64>,
+    /// Text after the numeric components (`rc2`, `-tem`), if any
+    pub suffix: Option<String>,
+}
+
+impl Version {
+    /// Parse a version, rejecting alternative implementations like `pypy3.10`
+    ///
+    /// A leading `v` is ignored; anything else must start with a digit.
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let text = raw.strip_prefix('v').unwrap_or(raw);
+        let (parts, rest) = numeric_parts(text)?;
+
+        Some(Self {
+            raw: raw.to_string(),
+            parts,
+            suffix: (!rest.is_empty()).then(|| rest.to_string()),
+        })
+    }
+
+    /// Whether this is an alpha, beta, release candidate or similar
+    #[must_use]
+    pub fn is_prerelease(&self) -> bool {
+        self.suffix.as_deref().is_some_and(is_prerelease_suffix)
+    }
+
+    /// Numeric component at `index`, treating missing components as zero
+    #[must_use]
+    pub fn part(&self, index: usize) -> u64 {
+        self.parts.get(index).copied().unwrap_or(0)
+    }
+
+    /// Compare numeric components only, padding the shorter with zeros
+    #[must_use]
+    pub fn cmp_numeric(&self, other: &Self) -> Ordering {
+        let len = self.parts.len().max(other.parts.len());
+        (0..len)
+            .map(|i| self.part(i).cmp(&other.part(i)))
+            .find(|ord| ord.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Release line of the given depth (`3.11.4` at depth 2 -> `[3, 11]`)
+    #[must_use]
+    pub fn line(&self, depth: usize) -> Vec<u64> {
+        (0..depth).map(|i| self.part(i)).collect()
+    }
+}
+
+impl Ord for Version {
+    /// Numeric order; a prerelease sorts before its release
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_numeric(other)
+            .then_with(|| other.is_prerelease().cmp(&self.is_prerelease()))
+            .then_with(|| self.raw.cmp(&other.raw))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Split leading dot-separated numbers from the remainder
+fn numeric_parts(text: &str) -> Option<(Vec<u64>, &str)> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+
+    loop {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            break;
+        }
+        parts.push(rest[..digits].parse().ok()?);
+        rest = &rest[digits..];
+
+        match rest.strip_prefix('.') {
+            Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => rest = next,
+            _ => break,
+        }
+    }
+
+    (!parts.is_empty()).then_some((parts, rest))
+}
+
+/// Whether a suffix such as `rc1`, `-beta.2` or `a3` marks a prerelease
+fn is_prerelease_suffix(suffix: &str) -> bool {
+    let tag = suffix.trim_start_matches(['-', '.', '+', '_']).to_lowercase();
+    PRERELEASE_MARKERS.iter().any(|marker| {
+        tag.strip_prefix(marker)
+            .is_some_and(|after| after.is_empty() || !after.starts_with(|c: char| c.is_ascii_alphabetic()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_order() {
+        let mut versions: Vec<Version> = ["v20.9.0", "3.13.0rc2", "3.13.0", "3.9.18", "21.0.2-tem"]
+            .iter()
+            .filter_map(|v| Version::parse(v))
+            .collect();
+        versions.sort();
+
+        let raw: Vec<&str> = versions.iter().map(|v| v.raw.as_str()).collect();
+        assert_eq!(raw, ["3.9.18", "3.13.0rc2", "3.13.0", "v20.9.0", "21.0.2-tem"]);
+    }
+
+    #[test]
+    fn test_prereleases_and_alternative_implementations() {
+        let cases = [
+            ("3.13.0a1", Some(true)),
+            ("22.0.0-ea", Some(true)),
+            ("1.0.0-beta.2", Some(true)),
+            ("21.0.2-amzn", Some(false)),
+            ("pypy3.10-7.3.12", None),
+            ("jruby-9.4.5.0", None),
+        ];
+
+        for (raw, prerelease) in cases {
+            assert_eq!(Version::parse(raw).map(|v| v.is_prerelease()), prerelease, "{raw}");
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Version constraints for runtimes
+//!
+//! Shared by every resolver so `"3.11"`, `"^20"`, `">=3.11,<4"` and
+//! `"latest-1"` mean the same thing regardless of the version manager.
+
+use super::version::Version;
+use crate::common::error::Result;
+use anyhow::{anyhow, bail};
+use std::cmp::Ordering;
+
+/// Keywords whose meaning depends on the version manager
+const KEYWORDS: [&str; 5] = ["latest", "lts", "stable", "beta", "nightly"];
+
+/// A parsed runtime version constraint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionConstraint {
+    /// A manager keyword such as `lts` or `stable` (`latest` is `LatestMinus(0)`)
+    Keyword(String),
+    /// Newest release of the Nth release line before the latest (`latest-1`)
+    LatestMinus(usize),
+    /// A full version or manager-specific name passed through unchanged
+    /// (`3.11.4`, `21.0.2-tem`, `nightly-2026-10-01`, `pypy3.10-7.3.12`)
+    Exact(String),
+    /// Newest release matching every comparator (`3.11`, `^20`, `>=3.11,<4`)
+    Range(Vec<Comparator>),
+}
+
+/// A single bound on a version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    /// Comparison operator
+    pub op: Op,
+    /// Version being compared against
+    pub version: Version,
+}
+
+/// Comparison operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// `>=`
+    Ge,
+    /// `>`
+    Gt,
+    /// `<=`
+    Le,
+    /// `<`
+    Lt,
+    /// `=` (components that are given must match)
+    Eq,
+}
+
+impl VersionConstraint {
+    /// Parse a constraint from config
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        if text == "latest" {
+            return Ok(Self::LatestMinus(0));
+        }
+        if KEYWORDS.contains(&text) {
+            return Ok(Self::Keyword(text.to_string()));
+        }
+        if let Some(n) = text.strip_prefix("latest-") {
+            let n = n.parse().map_err(|_| anyhow!("Invalid version constraint '{text}'"))?;
+            return Ok(Self::LatestMinus(n));
+        }
+        if let Some(rest) = text.strip_prefix('^') {
+            return Ok(Self::Range(caret(parse_version(rest, text)?)));
+        }
+        if let Some(rest) = text.strip_prefix('~') {
+            return Ok(Self::Range(tilde(parse_version(rest, text)?)));
+        }
+        if text.contains(['<', '>', '=', ',']) {
+            return text.split(',').map(|c| parse_comparator(c, text)).collect::<Result<_>>().map(Self::Range);
+        }
+        partial_or_exact(text)
+    }
+
+    /// Whether this names one concrete version that needs no lookup
+    #[must_use]
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact(_))
+    }
+
+    /// Whether a version satisfies the constraint on its own
+    #[must_use]
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            Self::Range(comparators) => comparators.iter().all(|c| c.matches(version)),
+            Self::Exact(raw) => version.raw == *raw,
+            Self::LatestMinus(_) | Self::Keyword(_) => true,
+        }
+    }
+
+    /// Pick the best available version
+    ///
+    /// Prereleases and versions that don't parse (alternative
+    /// implementations such as `pypy3.10`) are ignored. `line_depth` sets
+    /// how many components make a release line for `latest-N`: 1 for
+    /// runtimes that bump the major version (Node, Java), 2 for ones that
+    /// don't (Python, Ruby, Go).
+    #[must_use]
+    pub fn select<'a, I>(&self, available: I, line_depth: usize) -> Option<String>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut candidates: Vec<Version> = available
+            .into_iter()
+            .filter_map(Version::parse)
+            .filter(|v| !v.is_prerelease() && self.matches(v))
+            .collect();
+        candidates.sort();
+
+        match self {
+            Self::LatestMinus(n) => newest_in_line(&candidates, *n, line_depth),
+            _ => candidates.last().map(|v| v.raw.clone()),
+        }
+    }
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        let ord = version.cmp_numeric(&self.version);
+        match self.op {
+            Op::Ge => ord != Ordering::Less,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Le => ord != Ordering::Greater,
+            Op::Lt => ord == Ordering::Less,
+            Op::Eq => version.line(self.version.parts.len()) == self.version.parts,
+        }
+    }
+}
+
+/// `3.11` means the newest 3.11.x; `3.11.4`, `21.0.2-tem` or a named
+/// toolchain such as `nightly-2026-10-01` is exact
+fn partial_or_exact(text: &str) -> Result<VersionConstraint> {
+    if text.is_empty() {
+        bail!("Version constraint must not be empty");
+    }
+    let Some(version) = Version::parse(text) else {
+        return Ok(VersionConstraint::Exact(text.to_string()));
+    };
+    if version.parts.len() >= 3 || version.suffix.is_some() {
+        Ok(VersionConstraint::Exact(text.to_string()))
+    } else {
+        Ok(VersionConstraint::Range(vec![Comparator { op: Op::Eq, version }]))
+    }
+}
+
+fn parse_version(text: &str, constraint: &str) -> Result<Version> {
+    Version::parse(text).ok_or_else(|| anyhow!("Invalid version constraint '{constraint}'"))
+}
+
+fn parse_comparator(text: &str, constraint: &str) -> Result<Comparator> {
+    let text = text.trim();
+    let (op, rest) = [(">=", Op::Ge), ("<=", Op::Le), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)]
+        .into_iter()
+        .find_map(|(prefix, op)| text.strip_prefix(prefix).map(|rest| (op, rest)))
+        .ok_or_else(|| anyhow!("Invalid comparator '{text}' in '{constraint}'"))?;
+
+    let version = parse_version(rest.trim(), constraint)?;
+    if version.suffix.is_some() {
+        bail!("Comparators in '{constraint}' must use plain numeric versions");
+    }
+    Ok(Comparator { op, version })
+}
+
+/// `^1.2.3` -> `>=1.2.3, <2`; `^0.3` -> `>=0.3, <0.4`
+fn caret(version: Version) -> Vec<Comparator> {
+    let significant = version.parts.iter().position(|&p| p != 0).unwrap_or(version.parts.len() - 1);
+    upper_bounded(version, significant)
+}
+
+/// `~3.11` -> `>=3.11, <3.12`; `~3` -> `>=3, <4`
+fn tilde(version: Version) -> Vec<Comparator> {
+    let index = version.parts.len().min(2) - 1;
+    upper_bounded(version, index)
+}
+
+/// `>= version` and `<` the version with component `index` bumped
+fn upper_bounded(version: Version, index: usize) -> Vec<Comparator> {
+    let mut parts = version.line(index + 1);
+    parts[index] += 1;
+    let upper = Version { raw: String::new(), parts, suffix: None };
+
+    vec![
+        Comparator { op: Op::Ge, version },
+        Comparator { op: Op::Lt, version: upper },
+    ]
+}
+
+/// Newest version of the `n`th newest release line
+fn newest_in_line(sorted: &[Version], n: usize, depth: usize) -> Option<String> {
+    let mut lines: Vec<Vec<u64>> = sorted.iter().map(|v| v.line(depth)).collect();
+    lines.dedup();
+    let line = lines.iter().rev().nth(n)?;
+
+    sorted.iter().rev().find(|v| v.line(depth) == *line).map(|v| v.raw.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PYTHON: [&str; 9] = [
+        "2.7.18", "3.11.8", "3.11.9", "3.12.4", "3.13.0", "3.14.0a1", "pypy3.10-7.3.12", "miniconda3-latest", "3.12.10",
+    ];
+
+    fn select(constraint: &str, depth: usize) -> Option<String> {
+        VersionConstraint::parse(constraint).ok()?.select(PYTHON, depth)
+    }
+
+    #[test]
+    fn test_select() {
+        let cases = [
+            ("3.11", 2, Some("3.11.9")),
+            ("3", 2, Some("3.13.0")),
+            ("latest", 2, Some("3.13.0")),
+            ("latest-1", 2, Some("3.12.10")),
+            ("latest-1", 1, Some("2.7.18")),
+            ("3.10", 2, None),
+            ("^3.11", 2, Some("3.13.0")),
+            ("~3.11", 2, Some("3.11.9")),
+            (">=3.11,<3.13", 2, Some("3.12.10")),
+            ("<3", 2, Some("2.7.18")),
+            ("^0.3", 2, None),
+        ];
+
+        for (constraint, depth, expected) in cases {
+            assert_eq!(select(constraint, depth).as_deref(), expected, "{constraint}");
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let cases = [
+            ("3.11.4", Some(true)),
+            ("21.0.2-tem", Some(true)),
+            ("lts", Some(false)),
+            ("nightly-2026-10-01", Some(true)),
+            ("", None),
+            ("^banana", None),
+            (">=3.11,~4", None),
+            ("latest-x", None),
+        ];
+
+        for (text, exact) in cases {
+            let parsed = VersionConstraint::parse(text).ok();
+            assert_eq!(parsed.map(|c| c.is_exact()), exact, "{text}");
+        }
+    }
+}
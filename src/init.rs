@@ -5,8 +5,8 @@
 use crate::cli::Cli;
 use colored::Colorize;
 use devstrap::common::{print_system_info, show_banner};
+use devstrap::config::{ConfigLayers, Host};
 use devstrap::domain::{Config, SystemInfo};
-use std::path::PathBuf;
 use std::process;
 
 /// Initialize application (logging and banner)
@@ -24,38 +24,60 @@ pub fn initialize_app(cli: &Cli) {
 /// * `cli` - CLI arguments
 ///
 /// # Returns
-/// Tuple of (`SystemInfo`, `Config`, `project_root`)
-pub fn load_system_and_config(cli: &Cli) -> (SystemInfo, Config, PathBuf) {
-    let system_info = match SystemInfo::detect() {
-        Ok(info) => info,
-        Err(e) => {
-            eprintln!("{} Failed to detect system information: {}", "✗".red(), e);
-            process::exit(1);
-        }
-    };
-
+/// Tuple of (`SystemInfo`, `Config`, the `ConfigLayers` it was merged from)
+pub fn load_system_and_config(cli: &Cli) -> (SystemInfo, Config, ConfigLayers) {
+    let system_info = detect_system();
     print_system_info(&system_info);
 
-    let config = match Config::from_file(&cli.config) {
+    let layers = load_config_layers(cli, &system_info);
+    let config = match layers.to_config() {
         Ok(cfg) => cfg,
-        Err(e) => {
-            eprintln!(
-                "{} Failed to load configuration from {}: {}",
-                "✗".red(),
-                cli.config.display(),
-                e
-            );
-            process::exit(1);
-        }
+        Err(e) => exit_config_error(&layers.describe_files(), &e),
     };
 
-    let project_root = cli.config.parent().unwrap_or(&cli.config).to_path_buf();
-
     println!(
         "\n{} Configuration loaded from {}",
         "✓".green(),
-        cli.config.display()
+        layers.describe_files()
     );
+    print_profiles(&layers);
+
+    (system_info, config, layers)
+}
+
+/// Detect the system, exiting when that fails
+pub fn detect_system() -> SystemInfo {
+    match SystemInfo::detect() {
+        Ok(info) => info,
+        Err(e) => {
+            eprintln!("{} Failed to detect system information: {}", "✗".red(), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Read and merge the `--config` files and their includes, then apply profiles and expand variables
+pub fn load_config_layers(cli: &Cli, system_info: &SystemInfo) -> ConfigLayers {
+    let paths: Vec<String> = cli.config.iter().map(|path| path.display().to_string()).collect();
+    let mut layers = ConfigLayers::load(&cli.config).unwrap_or_else(|e| exit_config_error(&paths.join(", "), &e));
+    if let Err(e) = layers.resolve(&cli.profiles, &Host::detect(system_info)) {
+        exit_config_error(&paths.join(", "), &e);
+    }
+    layers
+}
+
+/// Print the profiles applied to the config and why
+fn print_profiles(layers: &ConfigLayers) {
+    if layers.profiles.is_empty() {
+        return;
+    }
+    let profiles: Vec<String> =
+        layers.profiles.iter().map(|profile| format!("{} ({})", profile.name.bold(), profile.reason)).collect();
+    println!("{} Profiles: {}", "✓".green(), profiles.join(", "));
+}
 
-    (system_info, config, project_root)
+/// Report a configuration that can't be loaded and exit
+fn exit_config_error(files: &str, error: &anyhow::Error) -> ! {
+    eprintln!("{} Failed to load configuration from {}: {:#}", "✗".red(), files, error);
+    process::exit(1);
 }
@@ -1,10 +1,13 @@
 //! Package and runtime installation command logic
 
 use crate::cli::Cli;
-use devstrap::domain::{Config, Lockfile};
-use devstrap::usecase::{Installer, RuntimeCoordinator};
+use devstrap::common::persist::SyncLock;
+use devstrap::config::StateFile;
+use devstrap::domain::{Config, Lockfile, SystemInfo};
+use devstrap::usecase::{lock_packages, Installer, LockUpdate, RuntimeCoordinator};
 use colored::Colorize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process;
 
 /// Run the package installation process
 pub fn run_installation(installer: &Installer, _config: &Config) {
@@ -21,33 +24,115 @@ pub fn run_installation(installer: &Installer, _config: &Config) {
     println!("{}", "═".repeat(60).green());
 }
 
+/// Path of the lockfile next to the config
+pub fn lockfile_path(cli: &Cli) -> PathBuf {
+    cli.config_dir().join("devstrap.lock")
+}
+
+/// Path of the state file next to the config
+pub fn state_path(cli: &Cli) -> PathBuf {
+    cli.config_dir().join("devstrap.state")
+}
+
+/// Take the advisory lock that keeps concurrent runs from interleaving writes
+///
+/// Dry runs write nothing and don't wait for it.
+pub fn acquire_sync_lock(cli: &Cli) -> Option<SyncLock> {
+    if cli.dry_run {
+        return None;
+    }
+    let path = cli.config_dir().join(".devstrap-sync.lock");
+    match SyncLock::acquire(&path) {
+        Ok(lock) => Some(lock),
+        Err(e) => {
+            eprintln!("{} {:#}", "✗".red(), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Load the state file, exiting when neither it nor a backup can be read
+pub fn load_state(state_path: &Path) -> StateFile {
+    StateFile::load(state_path).unwrap_or_else(|e| exit_unreadable(state_path, &e))
+}
+
+/// Load the lockfile, exiting when neither it nor a backup can be read
+pub fn read_lockfile(lockfile_path: &Path) -> Lockfile {
+    Lockfile::load(lockfile_path).unwrap_or_else(|e| exit_unreadable(lockfile_path, &e))
+}
+
+/// Report a file that can't be recovered and exit
+fn exit_unreadable(path: &Path, error: &anyhow::Error) -> ! {
+    eprintln!("{} {:#}", "✗".red(), error);
+    eprintln!("Fix or remove {} to continue", path.display());
+    process::exit(1);
+}
+
+/// Load the lockfile, dropping this platform's locks when refreshing
+///
+/// Only the in-memory copy changes; the file is rewritten by the usual save
+/// after the sync, so cancelling keeps it as it was.
+pub fn load_lockfile(lockfile_path: &Path, refresh: bool) -> Lockfile {
+    let mut lockfile = read_lockfile(lockfile_path);
+    if refresh {
+        println!("  {} Refreshing version locks for {}...", "↻".cyan(), lockfile.current_platform());
+        lockfile.clear_platform();
+    }
+    lockfile
+}
+
+/// Lock the versions configured packages are installed at
+pub fn record_package_versions(config: &Config, system_info: &SystemInfo, lockfile: &mut Lockfile, dry_run: bool) {
+    if !dry_run {
+        lock_packages(config, system_info, lockfile);
+    }
+}
+
 /// Run runtime installation
-pub fn run_runtime_installation(config: &Config, cli: &Cli, refresh: bool) {
-    let lockfile_path = cli
-        .config
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .join("devstrap.lock");
-
-    let lockfile = if refresh {
-        handle_refresh(&lockfile_path, cli.dry_run);
-        Lockfile::default()
-    } else {
-        Lockfile::from_file(&lockfile_path).unwrap_or_default()
-    };
-
-    let mut runtime_coordinator = RuntimeCoordinator::new(config.clone(), lockfile, cli.dry_run);
-
-    if let Err(e) = runtime_coordinator.install_all() {
+///
+/// `updates` moves runtimes to the versions `update` resolved. The lockfile
+/// is saved afterwards unless `save` is false (`--frozen`), even when the
+/// runtime phase fails, so locks written for what did install are kept.
+pub fn run_runtime_installation(
+    config: &Config,
+    cli: &Cli,
+    lockfile: Lockfile,
+    updates: &[LockUpdate],
+    save: bool,
+    state: &mut StateFile,
+) {
+    let mut runtime_coordinator = RuntimeCoordinator::new(config.clone(), lockfile, cli.dry_run).with_updates(updates);
+
+    let result = runtime_coordinator.install_all();
+    record_runtime_state(&runtime_coordinator, state, cli.dry_run);
+
+    if let Err(e) = result {
         eprintln!("{} Runtime installation failed: {}", "✗".red(), e);
-    } else if let Err(e) = runtime_coordinator.save_lockfile(&lockfile_path) {
-        eprintln!("{} Failed to save lockfile: {}", "✗".red(), e);
+    }
+    if save {
+        if let Err(e) = runtime_coordinator.save_lockfile(lockfile_path(cli)) {
+            eprintln!("{} Failed to save lockfile: {}", "✗".red(), e);
+        }
     }
 }
 
-fn handle_refresh(lockfile_path: &Path, dry_run: bool) {
+/// Record bootstrapped managers, rustup extras, global packages and Corepack in the state file
+fn record_runtime_state(coordinator: &RuntimeCoordinator, state: &mut StateFile, dry_run: bool) {
+    for bootstrapped in coordinator.bootstrapped_managers() {
+        state.add_manager(
+            bootstrapped.manager.clone(),
+            bootstrapped.url.clone(),
+            bootstrapped.sha256.clone(),
+        );
+    }
+
     if !dry_run {
-        println!("  {} Refreshing version locks...", "↻".cyan());
-        let _ = std::fs::remove_file(lockfile_path);
+        state.add_toolchain_extras(coordinator.applied_toolchain_extras());
+        for (runtime, packages) in coordinator.applied_global_packages() {
+            state.add_global_packages(runtime, packages);
+        }
+        for (runtime, extras) in coordinator.applied_corepack() {
+            state.add_corepack(runtime, extras);
+        }
     }
 }
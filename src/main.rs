@@ -3,9 +3,13 @@
 //! Command-line interface for the devstrap development environment bootstrapper.
 
 mod cli;
+mod config_show;
 mod init;
 mod installation;
+mod plan;
+mod status;
 mod sync;
+mod update;
 
 use clap::Parser;
 use cli::Cli;
@@ -21,12 +25,36 @@ fn main() {
             list_packages();
             process::exit(0);
         }
-        Some(cli::Commands::Sync { prune, refresh }) => {
-            sync::run_sync(&cli, *prune, *refresh);
+        Some(cli::Commands::Sync {
+            prune,
+            refresh,
+            locked,
+            frozen,
+        }) => {
+            let options = sync::SyncOptions {
+                prune: *prune,
+                refresh: *refresh,
+                lock_mode: sync::LockMode::from_flags(*locked, *frozen),
+            };
+            sync::run_sync(&cli, &options);
+        }
+        Some(cli::Commands::Update { names, platforms }) if !platforms.is_empty() => {
+            update::run_platform_update(&cli, names, platforms);
+        }
+        Some(cli::Commands::Update { names, .. }) => {
+            update::run_update(&cli, names);
+        }
+        Some(cli::Commands::Status) => {
+            status::run_status(&cli);
+        }
+        Some(cli::Commands::Config {
+            command: cli::ConfigCommand::Show { resolved },
+        }) => {
+            config_show::run_config_show(&cli, *resolved);
         }
         None => {
             // No command specified - show help
-            Cli::parse_from(&["devstrap", "--help"]);
+            Cli::parse_from(["devstrap", "--help"]);
         }
     }
 }
@@ -0,0 +1,229 @@
+//! Sync plan calculation and display
+//!
+//! Compares the desired config against the state file and shows what a
+//! sync run is going to change before anything is touched.
+
+use colored::Colorize;
+use devstrap::config::StateFile;
+use devstrap::domain::{Config, InstallMethod, SystemInfo};
+use devstrap::service::runtime::manager::rustup::ToolchainItem;
+use devstrap::usecase::{
+    pending_corepack_items, pending_global_packages, required_corepack, stale_corepack_items, pending_toolchain_items, preferred_method, required_global_packages,
+    required_repositories, required_taps, required_toolchain_extras, stale_global_packages,
+    stale_repositories, stale_taps, stale_toolchain_items, VersionRefresh,
+};
+
+/// Everything a sync run is going to change
+pub struct SyncPlan {
+    /// Packages in config that devstrap hasn't installed yet
+    pub to_install: Vec<String>,
+    /// Packages to install that will be built from the AUR
+    pub from_aur: Vec<String>,
+    /// Packages devstrap installed that are no longer in config
+    pub to_remove: Vec<String>,
+    /// Homebrew taps devstrap added that no package needs anymore
+    pub stale_taps: Vec<String>,
+    /// Repositories devstrap added that nothing requires anymore
+    pub stale_repositories: Vec<String>,
+    /// Rustup components and targets to add, labelled for display
+    pub toolchain_additions: Vec<String>,
+    /// Rustup components and targets devstrap added that config dropped
+    pub stale_toolchain_items: Vec<String>,
+    /// Runtime global packages and Corepack items to install, labelled for display
+    pub global_package_additions: Vec<String>,
+    /// Runtime global packages and Corepack items devstrap applied that config dropped
+    pub stale_global_packages: Vec<String>,
+    /// Floating runtime versions that `refresh_after` moved, labelled for display
+    pub version_bumps: Vec<String>,
+}
+
+impl SyncPlan {
+    /// Calculate the diff between config and state
+    pub fn calculate(config: &Config, state: &StateFile, system_info: &SystemInfo) -> Self {
+        let desired_packages = config.get_all_packages();
+        let current_packages = state.get_package_ids();
+
+        let to_install: Vec<String> = desired_packages
+            .iter()
+            .filter(|pkg| !state.has_package(pkg))
+            .cloned()
+            .collect();
+
+        let to_remove: Vec<String> = current_packages
+            .iter()
+            .filter(|pkg| !desired_packages.contains(pkg))
+            .cloned()
+            .collect();
+
+        let from_aur = to_install
+            .iter()
+            .filter(|pkg| preferred_method(pkg, system_info, &config.settings) == Some(InstallMethod::Aur))
+            .cloned()
+            .collect();
+
+        let stale_taps = stale_taps(state, &required_taps(config, system_info));
+        let stale_repositories = stale_repositories(state, &required_repositories(config, system_info))
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        let (toolchain_additions, stale_toolchain_items) = toolchain_changes(config, state);
+        let (global_package_additions, stale_global_packages) = global_package_changes(config, state);
+
+        Self {
+            to_install,
+            from_aur,
+            to_remove,
+            stale_taps,
+            stale_repositories,
+            toolchain_additions,
+            stale_toolchain_items,
+            global_package_additions,
+            stale_global_packages,
+            version_bumps: Vec::new(),
+        }
+    }
+
+    /// Include the runtime versions re-resolved because their locks expired
+    pub fn with_version_refreshes(mut self, refreshes: &[VersionRefresh]) -> Self {
+        self.version_bumps = refreshes.iter().filter(|r| r.is_bump()).map(VersionRefresh::label).collect();
+        self
+    }
+
+    /// Whether this plan changes anything given the prune flag
+    pub fn has_changes(&self, prune: bool) -> bool {
+        !self.to_install.is_empty()
+            || !self.toolchain_additions.is_empty()
+            || !self.global_package_additions.is_empty()
+            || !self.version_bumps.is_empty()
+            || (prune && self.has_removals())
+    }
+
+    /// Whether anything could be removed with --prune
+    fn has_removals(&self) -> bool {
+        !self.to_remove.is_empty()
+            || !self.stale_taps.is_empty()
+            || !self.stale_repositories.is_empty()
+            || !self.stale_toolchain_items.is_empty()
+            || !self.stale_global_packages.is_empty()
+    }
+
+    /// Print the plan
+    ///
+    /// Returns true if sync should continue, false if everything is in sync
+    pub fn show(&self, prune: bool, dry_run: bool) -> bool {
+        if !self.has_changes(prune) {
+            println!("\n{} Everything in sync!", "✓".green().bold());
+            return !dry_run;
+        }
+
+        println!("\n{}", "Sync Plan:".bold().cyan());
+        self.show_install_plan();
+        self.show_version_bumps();
+        self.show_removal_plan(prune);
+        println!();
+        true
+    }
+
+    fn show_install_plan(&self) {
+        let mut additions = self
+            .to_install
+            .iter()
+            .chain(&self.toolchain_additions)
+            .chain(&self.global_package_additions)
+            .peekable();
+        if additions.peek().is_none() {
+            return;
+        }
+
+        println!("  {} To install:", "✓".green());
+        for pkg in additions {
+            if self.from_aur.contains(pkg) {
+                println!("    • {} {}", pkg.green(), "(AUR, untrusted)".yellow());
+            } else {
+                println!("    • {}", pkg.green());
+            }
+        }
+
+        if !self.from_aur.is_empty() {
+            println!(
+                "  {} AUR packages build user-submitted PKGBUILDs; review them before syncing",
+                "⚠".yellow()
+            );
+        }
+    }
+
+    fn show_version_bumps(&self) {
+        if self.version_bumps.is_empty() {
+            return;
+        }
+
+        println!("  {} To update (refresh_after expired):", "↻".cyan());
+        for bump in &self.version_bumps {
+            println!("    • {}", bump.cyan());
+        }
+    }
+
+    fn show_removal_plan(&self, prune: bool) {
+        if !self.has_removals() {
+            return;
+        }
+
+        if prune {
+            println!("  {} To remove:", "✗".red());
+            for item in self.removal_items() {
+                println!("    • {}", item.red());
+            }
+        } else {
+            println!(
+                "  {} Packages not in config (use --prune to remove):",
+                "⚠".yellow()
+            );
+            for item in self.removal_items() {
+                println!("    • {}", item.yellow());
+            }
+        }
+    }
+
+    /// Removable packages followed by stale taps, repositories, rust extras and global packages, labelled for display
+    fn removal_items(&self) -> Vec<String> {
+        self.to_remove
+            .iter()
+            .cloned()
+            .chain(self.stale_taps.iter().map(|tap| format!("tap {tap}")))
+            .chain(self.stale_repositories.iter().map(|name| format!("repository {name}")))
+            .chain(self.stale_toolchain_items.iter().cloned())
+            .chain(self.stale_global_packages.iter().cloned())
+            .collect()
+    }
+}
+
+/// Rustup components and targets to add and to remove, labelled for display
+fn toolchain_changes(config: &Config, state: &StateFile) -> (Vec<String>, Vec<String>) {
+    let required = required_toolchain_extras(config);
+    let label = |(kind, name): (ToolchainItem, String)| format!("rust {} {name}", kind.as_str());
+    (
+        pending_toolchain_items(state, &required).into_iter().map(label).collect(),
+        stale_toolchain_items(state, &required).into_iter().map(label).collect(),
+    )
+}
+
+/// Runtime global packages and Corepack items to install and to remove, labelled for display
+fn global_package_changes(config: &Config, state: &StateFile) -> (Vec<String>, Vec<String>) {
+    let required = required_global_packages(config);
+    let corepack = required_corepack(config);
+    let global = |(runtime, name): (String, String)| format!("{runtime} global {name}");
+    let corepack_label = |(runtime, item): (String, String)| format!("{runtime} {item}");
+    (
+        pending_global_packages(state, &required)
+            .into_iter()
+            .map(global)
+            .chain(pending_corepack_items(state, &corepack).into_iter().map(corepack_label))
+            .collect(),
+        stale_global_packages(state, &required)
+            .into_iter()
+            .map(global)
+            .chain(stale_corepack_items(state, &corepack).into_iter().map(corepack_label))
+            .collect(),
+    )
+}
@@ -3,6 +3,7 @@
 pub mod os_detection;
 pub mod package_manager;
 pub mod pm_detection;
+pub mod repository;
 pub mod runtime;
 
 pub use os_detection::detect_distro;
@@ -1,9 +1,11 @@
 //! Operating system and architecture detection
 
+use crate::common::command::run_command_output;
 use crate::domain::{Arch, Distro, Os};
 use std::fs;
 
 /// Detect the operating system
+#[must_use]
 pub fn detect_os() -> Os {
     match std::env::consts::OS {
         "macos" => Os::MacOs,
@@ -13,6 +15,7 @@ pub fn detect_os() -> Os {
 }
 
 /// Detect system architecture
+#[must_use]
 pub fn detect_arch() -> Arch {
     match std::env::consts::ARCH {
         "x86_64" | "amd64" => Arch::X86_64,
@@ -23,47 +26,73 @@ pub fn detect_arch() -> Arch {
 }
 
 /// Detect the Linux distribution
+#[must_use]
 pub fn detect_distro(os: Os) -> Distro {
     if os != Os::Linux {
         return Distro::Unknown;
     }
 
     // Check /etc/os-release first (standard on modern systems)
-    if let Ok(contents) = fs::read_to_string("/etc/os-release") {
-        for line in contents.lines() {
-            if let Some(id) = line.strip_prefix("ID=") {
-                let id = id.trim_matches('"').to_lowercase();
-                return match id.as_str() {
-                    "ubuntu" => Distro::Ubuntu,
-                    "debian" => Distro::Debian,
-                    "fedora" => Distro::Fedora,
-                    "rhel" => Distro::Rhel,
-                    "centos" => Distro::CentOs,
-                    "rocky" => Distro::Rocky,
-                    "almalinux" => Distro::Alma,
-                    "arch" => Distro::Arch,
-                    "manjaro" => Distro::Manjaro,
-                    _ => Distro::Unknown,
-                };
-            }
-        }
-    }
+    distro_from_os_release().unwrap_or_else(distro_from_marker_files)
+}
 
-    // Fallback to checking specific files
-    if fs::metadata("/etc/debian_version").is_ok() {
-        return Distro::Debian;
-    }
-    if fs::metadata("/etc/redhat-release").is_ok() {
-        return Distro::Rhel;
-    }
-    if fs::metadata("/etc/arch-release").is_ok() {
-        return Distro::Arch;
+/// Read the distribution ID from /etc/os-release
+fn distro_from_os_release() -> Option<Distro> {
+    let contents = fs::read_to_string("/etc/os-release").ok()?;
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("ID="))
+        .map(|id| distro_from_id(&id.trim_matches('"').to_lowercase()))
+}
+
+/// Detect the distribution codename (e.g. "noble", "bookworm")
+#[must_use]
+pub fn detect_codename() -> Option<String> {
+    let contents = fs::read_to_string("/etc/os-release").ok()?;
+    codename_from_os_release(&contents)
+}
+
+/// Prefer `UBUNTU_CODENAME` so derivatives resolve to their Ubuntu base
+fn codename_from_os_release(contents: &str) -> Option<String> {
+    ["UBUNTU_CODENAME=", "VERSION_CODENAME="].iter().find_map(|key| {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(|value| value.trim_matches('"').to_string())
+            .filter(|value| !value.is_empty())
+    })
+}
+
+/// Map an os-release ID to a distribution
+fn distro_from_id(id: &str) -> Distro {
+    match id {
+        "ubuntu" => Distro::Ubuntu,
+        "debian" => Distro::Debian,
+        "fedora" => Distro::Fedora,
+        "rhel" => Distro::Rhel,
+        "centos" => Distro::CentOs,
+        "rocky" => Distro::Rocky,
+        "almalinux" => Distro::Alma,
+        "arch" => Distro::Arch,
+        "manjaro" => Distro::Manjaro,
+        _ => Distro::Unknown,
     }
+}
 
-    Distro::Unknown
+/// Fallback to checking distribution-specific files
+fn distro_from_marker_files() -> Distro {
+    [
+        ("/etc/debian_version", Distro::Debian),
+        ("/etc/redhat-release", Distro::Rhel),
+        ("/etc/arch-release", Distro::Arch),
+    ]
+    .into_iter()
+    .find(|(marker, _)| fs::metadata(marker).is_ok())
+    .map_or(Distro::Unknown, |(_, distro)| distro)
 }
 
 /// Detect if running in WSL (Windows Subsystem for Linux)
+#[must_use]
 pub fn detect_wsl() -> bool {
     if let Ok(contents) = fs::read_to_string("/proc/version") {
         contents.to_lowercase().contains("microsoft")
@@ -71,3 +100,28 @@ pub fn detect_wsl() -> bool {
         false
     }
 }
+
+/// Detect the machine's hostname, empty when it can't be determined
+#[must_use]
+pub fn detect_hostname() -> String {
+    run_command_output("hostname", &[])
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codename_prefers_ubuntu_base() {
+        let mint = "NAME=\"Linux Mint\"\nVERSION_CODENAME=wilma\nUBUNTU_CODENAME=noble\n";
+        assert_eq!(codename_from_os_release(mint).as_deref(), Some("noble"));
+
+        let debian = "ID=debian\nVERSION_CODENAME=bookworm\n";
+        assert_eq!(codename_from_os_release(debian).as_deref(), Some("bookworm"));
+
+        assert_eq!(codename_from_os_release("ID=arch\n"), None);
+    }
+}
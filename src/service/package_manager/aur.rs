@@ -0,0 +1,110 @@
+//! Arch User Repository packages via yay or paru
+//!
+//! AUR packages are built from user-submitted PKGBUILDs, so devstrap never
+//! bootstraps a helper unless the config explicitly asks for one.
+
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::domain::system::is_command_available;
+use anyhow::{anyhow, bail, Context};
+use colored::Colorize;
+use std::path::Path;
+use std::process::Command;
+
+/// Supported AUR helpers, in order of preference
+pub const AUR_HELPERS: [&str; 2] = ["yay", "paru"];
+
+/// Find an installed AUR helper
+#[must_use]
+pub fn detect_helper() -> Option<&'static str> {
+    AUR_HELPERS.into_iter().find(|helper| is_command_available(helper))
+}
+
+/// Make sure an AUR helper is available, bootstrapping one if configured
+///
+/// `bootstrap` names the helper to build from the AUR when none is installed.
+pub fn ensure_helper(bootstrap: Option<&str>, dry_run: bool) -> Result<()> {
+    if detect_helper().is_some() {
+        return Ok(());
+    }
+
+    let Some(helper) = bootstrap else {
+        println!(
+            "  {} No AUR helper found; install yay or paru, or set [settings] aur_bootstrap",
+            "⚠".yellow()
+        );
+        return Ok(());
+    };
+
+    if dry_run {
+        println!("  {} Would bootstrap AUR helper {}", "[DRY-RUN]".yellow(), helper);
+        return Ok(());
+    }
+
+    bootstrap_helper(helper)
+}
+
+/// Build and install `<helper>-bin` from the AUR with makepkg
+///
+/// The PKGBUILD is cloned into a private temp directory, so no other user
+/// can edit it before `makepkg -si` runs pacman through sudo.
+fn bootstrap_helper(helper: &str) -> Result<()> {
+    println!("  {} Bootstrapping AUR helper {}...", "↓".cyan(), helper);
+
+    run_command("sudo", &["pacman", "-S", "--needed", "--noconfirm", "git", "base-devel"])
+        .context("Failed to install git and base-devel")?;
+
+    let work_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let build_dir = work_dir.path().join(format!("{helper}-bin"));
+    let repo = format!("https://aur.archlinux.org/{helper}-bin.git");
+    run_command("git", &["clone", "--depth", "1", &repo, &build_dir.to_string_lossy()])
+        .with_context(|| format!("Failed to clone {repo}"))?;
+
+    makepkg_install(&build_dir)?;
+
+    println!("  {} Installed AUR helper {}", "✓".green(), helper);
+    Ok(())
+}
+
+/// Run `makepkg -si` in a PKGBUILD directory
+fn makepkg_install(dir: &Path) -> Result<()> {
+    let status = Command::new("makepkg")
+        .args(["-si", "--noconfirm"])
+        .current_dir(dir)
+        .status()
+        .context("Failed to execute makepkg")?;
+
+    if !status.success() {
+        bail!("makepkg failed with exit code {}", status.code().unwrap_or(-1));
+    }
+    Ok(())
+}
+
+/// Install an AUR package with the detected helper
+pub fn install_with_aur(package_name: &str) -> Result<()> {
+    let helper = detect_helper().ok_or_else(|| {
+        anyhow!("No AUR helper found; install yay or paru, or set [settings] aur_bootstrap")
+    })?;
+
+    run_command(helper, &aur_install_args(package_name))
+        .with_context(|| format!("Failed to install {package_name} from the AUR via {helper}"))?;
+    Ok(())
+}
+
+/// Helper arguments for a non-interactive, idempotent install
+fn aur_install_args(package_name: &str) -> Vec<&str> {
+    vec!["-S", "--noconfirm", "--needed", package_name]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aur_install_args() {
+        assert_eq!(
+            aur_install_args("visual-studio-code-bin"),
+            vec!["-S", "--noconfirm", "--needed", "visual-studio-code-bin"]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Homebrew taps and casks
+//!
+//! Taps are added before any package is installed so formulae and casks
+//! from third-party repositories resolve by their qualified name.
+
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::common::command::run_command_output;
+use crate::domain::PackageConfig;
+use anyhow::Context;
+use colored::Colorize;
+use std::collections::BTreeSet;
+
+/// List taps currently known to Homebrew
+pub fn installed_taps() -> Result<Vec<String>> {
+    let output = run_command_output("brew", &["tap"])?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_lowercase)
+        .collect())
+}
+
+/// Tap every repository Homebrew doesn't know about yet
+///
+/// Returns the taps that were newly added, so callers can record them for pruning.
+pub fn ensure_taps(taps: &BTreeSet<String>, dry_run: bool) -> Result<Vec<String>> {
+    if taps.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let existing = installed_taps().unwrap_or_default();
+    let missing: Vec<String> = taps
+        .iter()
+        .filter(|tap| !existing.contains(&tap.to_lowercase()))
+        .cloned()
+        .collect();
+
+    for tap in &missing {
+        add_tap(tap, dry_run)?;
+    }
+
+    Ok(if dry_run { Vec::new() } else { missing })
+}
+
+/// Add a single tap
+fn add_tap(tap: &str, dry_run: bool) -> Result<()> {
+    if dry_run {
+        println!("  {} Would run: brew tap {}", "[DRY-RUN]".yellow(), tap);
+        return Ok(());
+    }
+
+    println!("  {} Tapping {}...", "↓".cyan(), tap);
+    run_command("brew", &["tap", tap]).with_context(|| format!("Failed to tap {tap}"))?;
+    Ok(())
+}
+
+/// Remove a tap that no configured package needs anymore
+pub fn remove_tap(tap: &str) -> Result<()> {
+    run_command("brew", &["untap", tap]).with_context(|| format!("Failed to untap {tap}"))?;
+    Ok(())
+}
+
+/// Install a formula or cask, qualifying it with its tap when one is set
+pub fn install_with_brew(package_name: &str, config: &PackageConfig) -> Result<()> {
+    let qualified = qualified_name(package_name, config.tap.as_deref());
+    let mut args = vec!["install"];
+    if config.cask {
+        args.push("--cask");
+    }
+    args.push(&qualified);
+
+    run_command("brew", &args)
+        .with_context(|| format!("Failed to install {qualified} via Homebrew"))?;
+    Ok(())
+}
+
+/// Qualify a formula or cask name with its tap (`owner/repo/name`)
+#[must_use]
+pub fn qualified_name(package_name: &str, tap: Option<&str>) -> String {
+    tap.map_or_else(
+        || package_name.to_string(),
+        |tap| format!("{tap}/{package_name}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qualified_name() {
+        assert_eq!(qualified_name("terraform", Some("hashicorp/tap")), "hashicorp/tap/terraform");
+        assert_eq!(qualified_name("iterm2", None), "iterm2");
+    }
+}
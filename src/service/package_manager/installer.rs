@@ -4,6 +4,7 @@ use crate::usecase::orchestration::{dispatch_installation, print_package_status}
 use crate::common::error::Result;
 use crate::domain::{InstallMethod, Package};
 use crate::common::uninstall_package;
+use super::versions::installed_version;
 use colored::Colorize;
 
 /// Check if package needs installation/reinstall
@@ -11,15 +12,24 @@ pub(super) fn should_install_package(
     package: &Package,
     default_pm: Option<crate::domain::PackageManager>,
 ) -> bool {
-    if let Some(_current_method) = &package.current_method {
+    if let Some(current_method) = &package.current_method {
         if package.should_reinstall(default_pm) {
             return true;
         }
-        return false;
+        return *current_method == package.preferred_method && differs_from_lock(package);
     }
     true
 }
 
+/// Whether the installed version is known to differ from the locked one
+fn differs_from_lock(package: &Package) -> bool {
+    let (Some(locked), Some(name)) = (&package.locked_version, package.package_name()) else {
+        return false;
+    };
+    installed_version(&package.preferred_method, &name, &package.config)
+        .is_some_and(|installed| installed != *locked)
+}
+
 /// Handle package uninstallation if needed
 pub(super) fn handle_uninstall(
     package: &Package,
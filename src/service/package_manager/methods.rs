@@ -4,7 +4,7 @@
 
 use crate::common::error::Result;
 use crate::common::run_command;
-use crate::domain::{Package, PackageManager};
+use crate::domain::{Package, PackageConfig, PackageManager};
 use anyhow::Context;
 use colored::Colorize;
 
@@ -36,7 +36,7 @@ pub fn update_package_manager(pm: PackageManager, dry_run: bool) -> Result<()> {
             "Updating YUM package metadata",
         ),
         // These don't need system-level updates
-        PackageManager::Cargo | PackageManager::Npm | PackageManager::Pipx => {
+        PackageManager::Cargo | PackageManager::Npm | PackageManager::Pipx | PackageManager::Uv => {
             return Ok(());
         }
     };
@@ -66,27 +66,21 @@ pub fn update_package_manager(pm: PackageManager, dry_run: bool) -> Result<()> {
 }
 
 /// Install a package using system package manager
+///
+/// A `version` installs exactly that release, downgrading if needed.
 pub fn install_with_system_package_manager(
     package_name: &str,
+    version: Option<&str>,
     pm: PackageManager,
 ) -> Result<()> {
-    let (cmd, args) = match pm {
-        PackageManager::Brew => ("brew", vec!["install", package_name]),
-        PackageManager::Apt => ("sudo", vec!["apt-get", "install", "-y", package_name]),
-        PackageManager::Pacman => (
-            "sudo",
-            vec!["pacman", "-S", "--noconfirm", "--needed", package_name],
-        ),
-        PackageManager::Dnf => ("sudo", vec!["dnf", "install", "-y", package_name]),
-        PackageManager::Yum => ("sudo", vec!["yum", "install", "-y", package_name]),
-        _ => {
-            return Err(anyhow::anyhow!(
-                "Unsupported package manager {} for package {}",
-                pm.display_name(),
-                package_name
-            ));
-        }
-    };
+    let (cmd, args) = system_install_args(package_name, version, pm).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unsupported package manager {} for package {}",
+            pm.display_name(),
+            package_name
+        )
+    })?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
 
     run_command(cmd, &args).with_context(|| {
         format!(
@@ -99,27 +93,94 @@ pub fn install_with_system_package_manager(
     Ok(())
 }
 
-/// Install a package using Cargo
-pub fn install_with_cargo(package_name: &str) -> Result<()> {
-    run_command("cargo", &["install", package_name])
+/// Build the install command for a system package manager
+///
+/// Brew and Pacman only install their current version, so `version` is
+/// checked against them before this runs rather than passed along.
+fn system_install_args(
+    package_name: &str,
+    version: Option<&str>,
+    pm: PackageManager,
+) -> Option<(&'static str, Vec<String>)> {
+    let (cmd, args): (_, &[&str]) = match pm {
+        PackageManager::Brew => ("brew", &["install"]),
+        PackageManager::Apt if version.is_some() => ("sudo", &["apt-get", "install", "-y", "--allow-downgrades"]),
+        PackageManager::Apt => ("sudo", &["apt-get", "install", "-y"]),
+        PackageManager::Pacman => ("sudo", &["pacman", "-S", "--noconfirm", "--needed"]),
+        PackageManager::Dnf => ("sudo", &["dnf", "install", "-y"]),
+        PackageManager::Yum => ("sudo", &["yum", "install", "-y"]),
+        _ => return None,
+    };
+    let target = match (pm, version) {
+        (PackageManager::Apt, Some(version)) => format!("{package_name}={version}"),
+        (PackageManager::Dnf | PackageManager::Yum, Some(version)) => format!("{package_name}-{version}"),
+        _ => package_name.to_string(),
+    };
+
+    let mut args: Vec<String> = args.iter().map(|arg| (*arg).to_string()).collect();
+    args.push(target);
+    Some((cmd, args))
+}
+
+/// Install a package using Cargo, optionally an exact version
+pub fn install_with_cargo(package_name: &str, version: Option<&str>) -> Result<()> {
+    let mut args = vec!["install", package_name];
+    if let Some(version) = version {
+        args.extend(["--version", version]);
+    }
+    run_command("cargo", &args)
         .with_context(|| format!("Failed to install {package_name} via Cargo"))?;
     Ok(())
 }
 
-/// Install a package using npm
-pub fn install_with_npm(package_name: &str) -> Result<()> {
-    run_command("npm", &["install", "-g", package_name])
+/// Install a package using npm, optionally an exact version
+pub fn install_with_npm(package_name: &str, version: Option<&str>) -> Result<()> {
+    let target = version.map_or_else(|| package_name.to_string(), |v| format!("{package_name}@{v}"));
+    run_command("npm", &["install", "-g", &target])
         .with_context(|| format!("Failed to install {package_name} via npm"))?;
     Ok(())
 }
 
-/// Install a package using pipx
-pub fn install_with_pipx(package_name: &str) -> Result<()> {
-    run_command("pipx", &["install", package_name])
-        .with_context(|| format!("Failed to install {package_name} via pipx"))?;
+/// Install a package using pipx, replacing any other installed version
+pub fn install_with_pipx(package_name: &str, version: Option<&str>) -> Result<()> {
+    let result = match version {
+        Some(version) => run_command("pipx", &["install", "--force", &format!("{package_name}=={version}")]),
+        None => run_command("pipx", &["install", package_name]),
+    };
+    result.with_context(|| format!("Failed to install {package_name} via pipx"))?;
+    Ok(())
+}
+
+/// Install a Python application using `uv tool`
+pub fn install_with_uv(package_name: &str, version: Option<&str>, package: &Package) -> Result<()> {
+    let args = uv_install_args(package_name, version, &package.config);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    run_command("uv", &args)
+        .with_context(|| format!("Failed to install {package_name} via uv"))?;
     Ok(())
 }
 
+/// Build `uv tool install` arguments, including the pinned Python and extras
+///
+/// A pinned version replaces any other installed version, like pipx does.
+fn uv_install_args(package_name: &str, version: Option<&str>, config: &PackageConfig) -> Vec<String> {
+    let mut args = vec!["tool".to_string(), "install".to_string()];
+    if version.is_some() {
+        args.push("--force".to_string());
+    }
+
+    if let Some(python) = &config.python {
+        args.extend(["--python".to_string(), python.clone()]);
+    }
+    for extra in &config.with {
+        args.extend(["--with".to_string(), extra.clone()]);
+    }
+
+    args.push(version.map_or_else(|| package_name.to_string(), |v| format!("{package_name}=={v}")));
+    args
+}
+
 /// Install a package from GitHub releases
 pub fn install_from_github(package_name: &str, _package: &Package) {
     // For now, just report that GitHub installation would happen
@@ -134,3 +195,39 @@ pub fn install_from_github(package_name: &str, _package: &Package) {
         package_name
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_install_args() {
+        let cases = [
+            (PackageManager::Apt, None, "sudo apt-get install -y ripgrep"),
+            (PackageManager::Apt, Some("14.1.0-1"), "sudo apt-get install -y --allow-downgrades ripgrep=14.1.0-1"),
+            (PackageManager::Dnf, Some("14.1.0-3.fc40"), "sudo dnf install -y ripgrep-14.1.0-3.fc40"),
+            (PackageManager::Pacman, Some("14.1.0-1"), "sudo pacman -S --noconfirm --needed ripgrep"),
+        ];
+
+        for (pm, version, expected) in cases {
+            let (cmd, args) = system_install_args("ripgrep", version, pm).unwrap();
+            assert_eq!(format!("{cmd} {}", args.join(" ")), expected);
+        }
+        assert!(system_install_args("ripgrep", None, PackageManager::Cargo).is_none());
+    }
+
+    #[test]
+    fn test_uv_install_args() {
+        let config = PackageConfig { python: Some("3.12".to_string()), with: vec!["black".to_string()], ..Default::default() };
+        let cases = [
+            (None, &PackageConfig::default(), "tool install ruff"),
+            (Some("0.7.0"), &PackageConfig::default(), "tool install --force ruff==0.7.0"),
+            (None, &config, "tool install --python 3.12 --with black ruff"),
+            (Some("0.7.0"), &config, "tool install --force --python 3.12 --with black ruff==0.7.0"),
+        ];
+
+        for (version, config, expected) in cases {
+            assert_eq!(uv_install_args("ruff", version, config).join(" "), expected);
+        }
+    }
+}
@@ -1,7 +1,11 @@
 //! Package manager service interfaces
 
+pub mod aur;
+pub mod brew;
 pub mod installer;
 pub mod methods;
+pub mod url;
+pub mod versions;
 
 // Re-export update function for use by installation coordinator
 pub use methods::update_package_manager;
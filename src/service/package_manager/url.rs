@@ -0,0 +1,168 @@
+//! Direct downloads for the `url` installation method
+//!
+//! Artifacts are always verified against a sha256 before anything is
+//! extracted or placed on `PATH`, and unpacked in a private temp directory
+//! with an unpredictable name, so they can't be swapped after verification.
+
+use crate::common::download::{download_bytes, download_text};
+use crate::common::error::Result;
+use crate::common::path::{ensure_dir, local_bin_dir, make_executable};
+use crate::common::run_command;
+use crate::domain::{ArchiveType, Arch, Os, Package, UrlSource};
+use crate::service::os_detection::{detect_arch, detect_os};
+use anyhow::{anyhow, bail, Context};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Download, verify and install a binary into `dest_dir`
+///
+/// `version` is the pin from `package_versions`; the source's default
+/// version is used when it is `None`. Returns the installed path.
+pub fn install_from_url(
+    package_id: &str,
+    source: &UrlSource,
+    version: Option<&str>,
+    platform: (Os, Arch),
+    dest_dir: &Path,
+) -> Result<PathBuf> {
+    let version = version.or(source.version.as_deref()).ok_or_else(|| {
+        anyhow!("No version for {package_id}; pin one in [package_versions]")
+    })?;
+    let (os, arch) = platform;
+    let url = source.render(&source.url, version, os, arch);
+
+    let bytes = download_bytes(&url)?;
+    let expected = expected_sha256(source, version, platform, &url)?;
+    verify_sha256(&bytes, &expected, &url)?;
+
+    let work_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    unpack_and_install(package_id, source, &bytes, (version, os, arch), work_dir.path(), dest_dir)
+}
+
+/// Install a package's url source into `local_bin_dir` for this platform
+pub fn install_with_url(package: &Package) -> Result<()> {
+    let source = package
+        .config
+        .url
+        .as_ref()
+        .ok_or_else(|| anyhow!("Package {} has no url source", package.id))?;
+    let platform = (detect_os(), detect_arch());
+
+    install_from_url(&package.id, source, package.version.as_deref(), platform, &local_bin_dir()?)?;
+    Ok(())
+}
+
+/// Extract the artifact if needed and copy the executable into place
+fn unpack_and_install(
+    package_id: &str,
+    source: &UrlSource,
+    bytes: &[u8],
+    (version, os, arch): (&str, Os, Arch),
+    work_dir: &Path,
+    dest_dir: &Path,
+) -> Result<PathBuf> {
+    let executable = if source.archive == ArchiveType::Binary {
+        let path = work_dir.join(package_id);
+        fs::write(&path, bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+        path
+    } else {
+        extract(source.archive, bytes, work_dir)?;
+        let inner = source.binary.as_deref().unwrap_or(package_id);
+        work_dir.join(source.render(inner, version, os, arch))
+    };
+
+    if !executable.is_file() {
+        bail!("Archive for {package_id} does not contain {}", executable.display());
+    }
+
+    ensure_dir(&dest_dir.to_path_buf())?;
+    let target = dest_dir.join(source.installed_binary_name(package_id));
+    fs::copy(&executable, &target)
+        .with_context(|| format!("Failed to install {}", target.display()))?;
+    make_executable(&target)?;
+    Ok(target)
+}
+
+/// Unpack an archive into a directory with the system tar/unzip
+fn extract(archive: ArchiveType, bytes: &[u8], work_dir: &Path) -> Result<()> {
+    let archive_path = work_dir.join("download.archive");
+    fs::write(&archive_path, bytes)
+        .with_context(|| format!("Failed to write {}", archive_path.display()))?;
+
+    let file = archive_path.to_string_lossy();
+    let dir = work_dir.to_string_lossy();
+    let (cmd, args) = match archive {
+        ArchiveType::TarGz => ("tar", vec!["-xzf", &file, "-C", &dir]),
+        ArchiveType::TarXz => ("tar", vec!["-xJf", &file, "-C", &dir]),
+        ArchiveType::Zip => ("unzip", vec!["-q", "-o", &file, "-d", &dir]),
+        ArchiveType::Binary => return Ok(()),
+    };
+
+    run_command(cmd, &args).context("Failed to extract downloaded archive")?;
+    Ok(())
+}
+
+/// Work out the expected digest from the source or its checksum file
+fn expected_sha256(source: &UrlSource, version: &str, (os, arch): (Os, Arch), url: &str) -> Result<String> {
+    if let Some(hash) = source.known_sha256(version, os, arch) {
+        return Ok(hash.to_lowercase());
+    }
+
+    let Some(template) = &source.checksum_url else {
+        bail!(
+            "No sha256 for {}-{} at version {version} and no checksum_url to fetch one",
+            os.as_str(),
+            arch.as_str()
+        );
+    };
+
+    let checksum_url = source.render(template, version, os, arch);
+    let contents = download_text(&checksum_url)?;
+    let file_name = url.rsplit('/').next().unwrap_or(url);
+    parse_checksum(&contents, file_name)
+        .ok_or_else(|| anyhow!("No checksum for {file_name} in {checksum_url}"))
+}
+
+/// Find a digest in a checksum file
+///
+/// Accepts a bare digest or `sha256sum` output (`<digest>  <file>`).
+fn parse_checksum(contents: &str, file_name: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let digest = fields.next()?;
+        let matches = match fields.next() {
+            None => true,
+            Some(name) => name.trim_start_matches('*') == file_name,
+        };
+        (matches && is_sha256(digest)).then(|| digest.to_lowercase())
+    })
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Compare the digest of downloaded bytes against the expected value
+fn verify_sha256(bytes: &[u8], expected: &str, url: &str) -> Result<()> {
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if actual != expected {
+        bail!("Checksum mismatch for {url}: expected {expected}, got {actual}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checksum() {
+        let digest = "a".repeat(64);
+        let sums = format!("{}  helm-v3.16.2-darwin-amd64.tar.gz\n{digest}  helm-v3.16.2-linux-amd64.tar.gz\n", "b".repeat(64));
+
+        assert_eq!(parse_checksum(&sums, "helm-v3.16.2-linux-amd64.tar.gz"), Some(digest.clone()));
+        assert_eq!(parse_checksum(&format!("{digest}\n"), "kubectl"), Some(digest));
+        assert_eq!(parse_checksum(&sums, "helm-v3.16.2-linux-arm64.tar.gz"), None);
+    }
+}
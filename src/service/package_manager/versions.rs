@@ -0,0 +1,276 @@
+//! Installed and installable package versions per method
+//!
+//! Used to record the version each package resolved to in `devstrap.lock`
+//! and to check that a locked version can be installed again. Methods that
+//! take a version on the command line (APT, DNF, YUM, Cargo, npm, pipx, uv)
+//! are pinned directly; Homebrew, Pacman and the AUR only ever offer their
+//! current version, so a lock can only be honoured while that still matches.
+
+use super::aur::detect_helper;
+use super::brew::qualified_name;
+use crate::common::error::Result;
+use crate::common::command::run_command_output;
+use crate::common::download::download_text;
+use crate::domain::{InstallMethod, PackageConfig, PackageManager};
+use anyhow::bail;
+
+/// Parses a version query's output for a package name
+type VersionParser = fn(&str, &str) -> Option<String>;
+
+/// Version of a package currently installed with a method
+#[must_use]
+pub fn installed_version(method: &InstallMethod, package_name: &str, config: &PackageConfig) -> Option<String> {
+    let (program, args, parse) = installed_version_query(method, package_name, config)?;
+    parse(&run_command_output(program, &args).ok()?, package_name)
+}
+
+/// Command that lists a package's installed version, and how to read it
+fn installed_version_query<'a>(
+    method: &InstallMethod,
+    package_name: &'a str,
+    config: &PackageConfig,
+) -> Option<(&'static str, Vec<&'a str>, VersionParser)> {
+    let query: (_, Vec<&str>, VersionParser) = match method {
+        InstallMethod::SystemDefault(PackageManager::Brew) if config.cask => {
+            ("brew", vec!["list", "--versions", "--cask", package_name], parse_name_version)
+        }
+        InstallMethod::SystemDefault(PackageManager::Brew) => {
+            ("brew", vec!["list", "--versions", package_name], parse_name_version)
+        }
+        InstallMethod::SystemDefault(PackageManager::Apt) => {
+            ("dpkg-query", vec!["-W", "-f=${Version}", package_name], parse_plain)
+        }
+        InstallMethod::SystemDefault(PackageManager::Pacman) | InstallMethod::Aur => {
+            ("pacman", vec!["-Q", package_name], parse_name_version)
+        }
+        InstallMethod::SystemDefault(PackageManager::Dnf | PackageManager::Yum) => {
+            ("rpm", vec!["-q", "--qf", "%{VERSION}-%{RELEASE}", package_name], parse_plain)
+        }
+        InstallMethod::Cargo => ("cargo", vec!["install", "--list"], parse_cargo_list),
+        InstallMethod::Npm => ("npm", vec!["ls", "-g", "--depth=0", "--json", package_name], parse_npm_ls),
+        InstallMethod::Pipx => ("pipx", vec!["list", "--short"], parse_tool_list),
+        InstallMethod::Uv => ("uv", vec!["tool", "list"], parse_tool_list),
+        _ => return None,
+    };
+    Some(query)
+}
+
+/// Newest version a method would install right now
+#[must_use]
+pub fn available_version(method: &InstallMethod, package_name: &str, config: &PackageConfig) -> Option<String> {
+    if matches!(method, InstallMethod::Pipx | InstallMethod::Uv) {
+        return pypi_version(package_name);
+    }
+    let (program, args, parse) = available_version_query(method, package_name, config)?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    parse(&run_command_output(program, &args).ok()?, package_name)
+}
+
+/// Command that shows a package's newest installable version, and how to read it
+fn available_version_query(
+    method: &InstallMethod,
+    package_name: &str,
+    config: &PackageConfig,
+) -> Option<(&'static str, Vec<String>, VersionParser)> {
+    let (program, args, parse): (_, Vec<&str>, VersionParser) = match method {
+        InstallMethod::SystemDefault(PackageManager::Brew) => {
+            let kind = if config.cask { "--cask" } else { "--formula" };
+            let qualified = qualified_name(package_name, config.tap.as_deref());
+            let args = ["info", "--json=v2", kind, &qualified].map(String::from).to_vec();
+            return Some(("brew", args, parse_brew_info));
+        }
+        InstallMethod::SystemDefault(PackageManager::Apt) => ("apt-cache", vec!["policy", package_name], parse_apt_policy),
+        InstallMethod::SystemDefault(PackageManager::Pacman) => ("pacman", vec!["-Si", package_name], parse_info_version),
+        InstallMethod::SystemDefault(PackageManager::Dnf) => ("dnf", vec!["repoquery", "--latest-limit=1", "--qf", "%{version}-%{release}", package_name], parse_plain),
+        InstallMethod::SystemDefault(PackageManager::Yum) => ("repoquery", vec!["--latest-limit=1", "--qf", "%{version}-%{release}", package_name], parse_plain),
+        InstallMethod::Aur => (detect_helper()?, vec!["-Si", package_name], parse_info_version),
+        InstallMethod::Cargo => ("cargo", vec!["search", "--limit", "1", package_name], parse_cargo_search),
+        InstallMethod::Npm => ("npm", vec!["view", package_name, "version"], parse_plain),
+        _ => return None,
+    };
+    Some((program, args.into_iter().map(String::from).collect(), parse))
+}
+
+/// Latest release of a Python project on `PyPI`
+fn pypi_version(package_name: &str) -> Option<String> {
+    let output = download_text(&format!("https://pypi.org/pypi/{package_name}/json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&output).ok()?;
+    json["info"]["version"].as_str().map(String::from)
+}
+
+/// Newest version in a method's public registry, for locking another platform
+///
+/// Only methods whose registry can be read from any machine are supported.
+#[must_use]
+pub fn registry_version(method: &InstallMethod, package_name: &str, config: &PackageConfig) -> Option<String> {
+    let (url, pointer) = match method {
+        InstallMethod::SystemDefault(PackageManager::Brew) if config.cask => {
+            (format!("https://formulae.brew.sh/api/cask/{package_name}.json"), "/version")
+        }
+        InstallMethod::SystemDefault(PackageManager::Brew) => {
+            (format!("https://formulae.brew.sh/api/formula/{package_name}.json"), "/versions/stable")
+        }
+        InstallMethod::Cargo => (format!("https://crates.io/api/v1/crates/{package_name}"), "/crate/max_stable_version"),
+        InstallMethod::Npm => (format!("https://registry.npmjs.org/{package_name}/latest"), "/version"),
+        InstallMethod::Pipx | InstallMethod::Uv => return pypi_version(package_name),
+        _ => return None,
+    };
+    let json: serde_json::Value = serde_json::from_str(&download_text(&url).ok()?).ok()?;
+    json.pointer(pointer)?.as_str().map(String::from)
+}
+
+/// Whether a method installs whatever version it is given
+#[must_use]
+pub fn can_pin(method: &InstallMethod) -> bool {
+    !matches!(
+        method,
+        InstallMethod::SystemDefault(PackageManager::Brew | PackageManager::Pacman)
+            | InstallMethod::Aur
+            | InstallMethod::System
+            | InstallMethod::GitHub
+    )
+}
+
+/// Fail unless a method that can't be pinned still offers the locked version
+pub fn ensure_available(method: &InstallMethod, package_name: &str, config: &PackageConfig, version: &str) -> Result<()> {
+    if can_pin(method) {
+        return Ok(());
+    }
+    match available_version(method, package_name, config) {
+        Some(available) if available == version => Ok(()),
+        Some(available) => bail!(
+            "Locked version {version} of {package_name} is unavailable; {} offers {available}",
+            method.display_name()
+        ),
+        None => bail!("Could not find {package_name} via {}", method.display_name()),
+    }
+}
+
+/// Output whose last line is the version (`dpkg-query`, `rpm -q --qf`, `npm view`)
+fn parse_plain(output: &str, _package_name: &str) -> Option<String> {
+    output.lines().map(str::trim).rfind(|line| !line.is_empty()).map(String::from)
+}
+
+/// Last version of a `name version...` line (`brew list --versions`, `pacman -Q`)
+fn parse_name_version(output: &str, _package_name: &str) -> Option<String> {
+    output.lines().next()?.split_whitespace().skip(1).last().map(String::from)
+}
+
+/// Version of a crate in `cargo install --list` (`ripgrep v14.1.0:`)
+fn parse_cargo_list(output: &str, package_name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        (parts.next()? == package_name)
+            .then(|| parts.next())?
+            .map(|version| version.trim_start_matches('v').trim_end_matches(':').to_string())
+    })
+}
+
+/// Version of a crate in `cargo search` (`ripgrep = "14.1.1"    # ...`)
+fn parse_cargo_search(output: &str, package_name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (name, rest) = line.split_once(" = \"")?;
+        (name == package_name).then(|| rest.split('"').next().map(String::from))?
+    })
+}
+
+/// `Candidate:` line of `apt-cache policy`
+fn parse_apt_policy(output: &str, _package_name: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Candidate:"))
+        .map(str::trim)
+        .filter(|candidate| *candidate != "(none)")
+        .map(String::from)
+}
+
+/// Version of a package in `npm ls -g --json`
+fn parse_npm_ls(output: &str, package_name: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(output).ok()?;
+    json["dependencies"][package_name]["version"].as_str().map(String::from)
+}
+
+/// Version of a tool in `pipx list --short` or `uv tool list` (`black v24.10.0`)
+fn parse_tool_list(output: &str, package_name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        (parts.next()? == package_name)
+            .then(|| parts.next())?
+            .map(|version| version.trim_start_matches('v').to_string())
+    })
+}
+
+/// Stable version of the formula or cask in `brew info --json=v2`
+fn parse_brew_info(output: &str, _package_name: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(output).ok()?;
+    json["formulae"][0]["versions"]["stable"]
+        .as_str()
+        .or_else(|| json["casks"][0]["version"].as_str())
+        .map(String::from)
+}
+
+/// `Version : 1.2-1` line of `pacman -Si` or `yay -Si`
+fn parse_info_version(output: &str, _package_name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "Version").then(|| value.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_installed_versions() {
+        let cargo = "bat v0.24.0:\n    bat\nripgrep v14.1.1:\n    rg\n";
+        let npm = r#"{"dependencies":{"prettier":{"version":"3.3.3"}}}"#;
+        let uv = "black v24.10.0\n- black\n- blackd\nruff v0.7.1\n- ruff\n";
+        let cases: [(VersionParser, &str, &str, Option<&str>); 10] = [
+            (parse_name_version, "ripgrep 14.0.3 14.1.1\n", "ripgrep", Some("14.1.1")),
+            (parse_name_version, "ripgrep 14.1.1-1", "ripgrep", Some("14.1.1-1")),
+            (parse_name_version, "", "ripgrep", None),
+            (parse_plain, "14.1.0-1\n", "ripgrep", Some("14.1.0-1")),
+            (parse_cargo_list, cargo, "ripgrep", Some("14.1.1")),
+            (parse_cargo_list, cargo, "fd-find", None),
+            (parse_npm_ls, npm, "prettier", Some("3.3.3")),
+            (parse_npm_ls, "{}", "prettier", None),
+            (parse_tool_list, uv, "ruff", Some("0.7.1")),
+            (parse_tool_list, "black 24.10.0\n", "black", Some("24.10.0")),
+        ];
+
+        for (parse, output, name, expected) in cases {
+            assert_eq!(parse(output, name).as_deref(), expected, "{output:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_available_versions() {
+        let formula = r#"{"formulae":[{"name":"jq","versions":{"stable":"1.7.1"}}],"casks":[]}"#;
+        let cask = r#"{"formulae":[],"casks":[{"token":"iterm2","version":"3.5.4"}]}"#;
+        let pacman = "Repository      : extra\nName            : ripgrep\nVersion         : 14.1.1-1\n";
+        let apt = "ripgrep:\n  Installed: (none)\n  Candidate: 14.1.0-1\n  Version table:\n";
+        let cargo = "ripgrep = \"14.1.1\"    # ripgrep is a line-oriented search tool\n";
+        let cases: [(VersionParser, &str, &str, Option<&str>); 7] = [
+            (parse_brew_info, formula, "jq", Some("1.7.1")),
+            (parse_brew_info, cask, "iterm2", Some("3.5.4")),
+            (parse_info_version, pacman, "ripgrep", Some("14.1.1-1")),
+            (parse_apt_policy, apt, "ripgrep", Some("14.1.0-1")),
+            (parse_apt_policy, "ripgrep:\n  Candidate: (none)\n", "ripgrep", None),
+            (parse_cargo_search, cargo, "ripgrep", Some("14.1.1")),
+            (parse_cargo_search, cargo, "ripgrep-all", None),
+        ];
+
+        for (parse, output, name, expected) in cases {
+            assert_eq!(parse(output, name).as_deref(), expected, "{output:?}");
+        }
+    }
+
+    #[test]
+    fn test_can_pin() {
+        assert!(can_pin(&InstallMethod::SystemDefault(PackageManager::Apt)));
+        assert!(can_pin(&InstallMethod::Cargo));
+        assert!(!can_pin(&InstallMethod::SystemDefault(PackageManager::Brew)));
+        assert!(!can_pin(&InstallMethod::Aur));
+    }
+}
@@ -3,34 +3,31 @@
 use crate::domain::{Distro, Os, PackageManager};
 
 /// Detect the default package manager for the OS
+#[must_use]
 pub fn detect_default_package_manager(os: Os, distro: Distro) -> Option<PackageManager> {
     match os {
-        Os::MacOs => {
-            if is_command_available("brew") {
-                Some(PackageManager::Brew)
-            } else {
-                None
-            }
-        }
-        Os::Linux => match distro {
-            Distro::Ubuntu | Distro::Debian => Some(PackageManager::Apt),
-            Distro::Fedora | Distro::Rhel | Distro::CentOs | Distro::Rocky | Distro::Alma => {
-                if is_command_available("dnf") {
-                    Some(PackageManager::Dnf)
-                } else if is_command_available("yum") {
-                    Some(PackageManager::Yum)
-                } else {
-                    None
-                }
-            }
-            Distro::Arch | Distro::Manjaro => Some(PackageManager::Pacman),
-            Distro::Unknown => None,
-        },
+        Os::MacOs => is_command_available("brew").then_some(PackageManager::Brew),
+        Os::Linux => detect_linux_package_manager(distro),
         Os::Unknown => None,
     }
 }
 
+/// Detect the default package manager for a Linux distribution
+fn detect_linux_package_manager(distro: Distro) -> Option<PackageManager> {
+    match distro {
+        Distro::Ubuntu | Distro::Debian => Some(PackageManager::Apt),
+        Distro::Fedora | Distro::Rhel | Distro::CentOs | Distro::Rocky | Distro::Alma => {
+            [PackageManager::Dnf, PackageManager::Yum]
+                .into_iter()
+                .find(|pm| is_command_available(pm.command()))
+        }
+        Distro::Arch | Distro::Manjaro => Some(PackageManager::Pacman),
+        Distro::Unknown => None,
+    }
+}
+
 /// Detect all available package managers on the system
+#[must_use]
 pub fn detect_available_package_managers() -> Vec<PackageManager> {
     let mut managers = Vec::new();
 
@@ -43,6 +40,7 @@ pub fn detect_available_package_managers() -> Vec<PackageManager> {
         PackageManager::Cargo,
         PackageManager::Npm,
         PackageManager::Pipx,
+        PackageManager::Uv,
     ] {
         if is_command_available(pm.command()) {
             managers.push(pm);
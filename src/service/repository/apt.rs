@@ -0,0 +1,153 @@
+//! APT sources, signing keys and PPAs
+
+use super::print_dry_run;
+use crate::common::download::download_bytes;
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::common::system_file::{read_system_file, remove_system_files, write_system_file};
+use crate::service::os_detection::detect_codename;
+use anyhow::Context;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const KEYRING_DIR: &str = "/etc/apt/keyrings";
+const SOURCES_DIR: &str = "/etc/apt/sources.list.d";
+
+/// Borrowed view of an APT repository definition
+pub(super) struct AptSource<'a> {
+    pub url: &'a str,
+    pub key: &'a str,
+    pub suite: &'a str,
+    pub components: &'a [String],
+}
+
+/// Install the signing key and `signed-by` sources entry
+pub(super) fn ensure_source(name: &str, source: &AptSource<'_>, dry_run: bool) -> Result<bool> {
+    let suite = expand_suite(source.suite)?;
+    let sources_path = Path::new(SOURCES_DIR).join(format!("{name}.list"));
+
+    let existing = read_system_file(&sources_path);
+    let up_to_date = existing_keyring(name)
+        .is_some_and(|keyring| existing == source_line(&keyring, source, &suite));
+    if up_to_date {
+        return Ok(false);
+    }
+
+    if dry_run {
+        print_dry_run(&format!("add APT source {name} ({})", source.url));
+        return Ok(true);
+    }
+
+    let key = download_bytes(source.key)?;
+    let keyring = keyring_path(name, &key);
+    run_command("sudo", &["install", "-d", "-m", "0755", KEYRING_DIR])?;
+    write_system_file(&keyring, &key)?;
+    write_system_file(&sources_path, source_line(&keyring, source, &suite).as_bytes())?;
+    Ok(true)
+}
+
+/// Remove the sources entry and its keyring
+pub(super) fn remove_source(name: &str) -> Result<()> {
+    remove_system_files(&[
+        Path::new(SOURCES_DIR).join(format!("{name}.list")),
+        Path::new(KEYRING_DIR).join(format!("{name}.gpg")),
+        Path::new(KEYRING_DIR).join(format!("{name}.asc")),
+    ])
+}
+
+/// Add a PPA without refreshing the index (the caller refreshes once)
+pub(super) fn ensure_ppa(ppa: &str, dry_run: bool) -> Result<bool> {
+    if ppa_configured(ppa) {
+        return Ok(false);
+    }
+
+    if dry_run {
+        print_dry_run(&format!("add ppa:{ppa}"));
+        return Ok(true);
+    }
+
+    run_command("sudo", &["add-apt-repository", "-y", "-n", &format!("ppa:{ppa}")])
+        .with_context(|| format!("Failed to add ppa:{ppa}"))?;
+    Ok(true)
+}
+
+/// Remove a PPA
+pub(super) fn remove_ppa(ppa: &str) -> Result<()> {
+    run_command("sudo", &["add-apt-repository", "-y", "-r", &format!("ppa:{ppa}")])
+        .with_context(|| format!("Failed to remove ppa:{ppa}"))?;
+    Ok(())
+}
+
+/// Check whether any sources file already references the PPA
+fn ppa_configured(ppa: &str) -> bool {
+    let Ok(entries) = fs::read_dir(SOURCES_DIR) else {
+        return false;
+    };
+
+    let needles = [
+        format!("ppa.launchpadcontent.net/{ppa}/"),
+        format!("ppa.launchpad.net/{ppa}/"),
+    ];
+    entries.flatten().any(|entry| {
+        let contents = read_system_file(&entry.path());
+        needles.iter().any(|needle| contents.contains(needle.as_str()))
+    })
+}
+
+/// Keyring devstrap previously installed for this source, if any
+fn existing_keyring(name: &str) -> Option<PathBuf> {
+    ["asc", "gpg"]
+        .iter()
+        .map(|ext| Path::new(KEYRING_DIR).join(format!("{name}.{ext}")))
+        .find(|path| path.exists())
+}
+
+/// ASCII-armored keys must use `.asc` for apt to read them
+fn keyring_path(name: &str, key: &[u8]) -> PathBuf {
+    let ext = if key.starts_with(b"-----BEGIN PGP") { "asc" } else { "gpg" };
+    Path::new(KEYRING_DIR).join(format!("{name}.{ext}"))
+}
+
+/// Replace `{codename}` with the distribution codename
+fn expand_suite(suite: &str) -> Result<String> {
+    if !suite.contains("{codename}") {
+        return Ok(suite.to_string());
+    }
+    let codename = detect_codename().context("Unable to determine distribution codename")?;
+    Ok(suite.replace("{codename}", &codename))
+}
+
+/// One-line sources entry pinned to its keyring
+fn source_line(keyring: &Path, source: &AptSource<'_>, suite: &str) -> String {
+    format!(
+        "deb [signed-by={}] {} {} {}\n",
+        keyring.display(),
+        source.url,
+        suite,
+        source.components.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_line_and_keyring() {
+        let components = vec!["main".to_string()];
+        let source = AptSource {
+            url: "https://cli.github.com/packages",
+            key: "https://cli.github.com/packages/githubcli-archive-keyring.gpg",
+            suite: "stable",
+            components: &components,
+        };
+        let keyring = keyring_path("github-cli", b"-----BEGIN PGP PUBLIC KEY BLOCK-----");
+
+        assert_eq!(keyring, Path::new("/etc/apt/keyrings/github-cli.asc"));
+        assert_eq!(
+            source_line(&keyring, &source, "stable"),
+            "deb [signed-by=/etc/apt/keyrings/github-cli.asc] https://cli.github.com/packages stable main\n"
+        );
+        assert_eq!(keyring_path("gh", &[0x99, 0x01]), Path::new("/etc/apt/keyrings/gh.gpg"));
+    }
+}
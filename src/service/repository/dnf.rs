@@ -0,0 +1,67 @@
+//! DNF/YUM `.repo` files and COPR projects
+
+use super::print_dry_run;
+use crate::common::download::download_bytes;
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::common::system_file::{remove_system_files, write_system_file};
+use anyhow::Context;
+use std::fs;
+use std::path::Path;
+
+const REPOS_DIR: &str = "/etc/yum.repos.d";
+
+/// Download a `.repo` file into `/etc/yum.repos.d`
+pub(super) fn ensure_repo_file(name: &str, url: &str, dry_run: bool) -> Result<bool> {
+    let path = Path::new(REPOS_DIR).join(format!("{name}.repo"));
+    if path.exists() {
+        return Ok(false);
+    }
+
+    if dry_run {
+        print_dry_run(&format!("add repository file {} from {url}", path.display()));
+        return Ok(true);
+    }
+
+    let contents = download_bytes(url)?;
+    write_system_file(&path, &contents)?;
+    Ok(true)
+}
+
+/// Remove a `.repo` file added by devstrap
+pub(super) fn remove_repo_file(name: &str) -> Result<()> {
+    remove_system_files(&[Path::new(REPOS_DIR).join(format!("{name}.repo"))])
+}
+
+/// Enable a COPR project
+pub(super) fn ensure_copr(copr: &str, dry_run: bool) -> Result<bool> {
+    if copr_enabled(copr) {
+        return Ok(false);
+    }
+
+    if dry_run {
+        print_dry_run(&format!("enable copr:{copr}"));
+        return Ok(true);
+    }
+
+    run_command("sudo", &["dnf", "copr", "enable", "-y", copr])
+        .with_context(|| format!("Failed to enable copr:{copr}"))?;
+    Ok(true)
+}
+
+/// Remove a COPR project
+pub(super) fn remove_copr(copr: &str) -> Result<()> {
+    run_command("sudo", &["dnf", "copr", "remove", "-y", copr])
+        .with_context(|| format!("Failed to remove copr:{copr}"))?;
+    Ok(())
+}
+
+/// COPR repo files are named `_copr:<host>:<owner>:<project>.repo`
+fn copr_enabled(copr: &str) -> bool {
+    let suffix = format!(":{}.repo", copr.replace('/', ":"));
+    fs::read_dir(REPOS_DIR).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|entry| entry.file_name().to_string_lossy().ends_with(&suffix))
+    })
+}
@@ -0,0 +1,53 @@
+//! Third-party repository configuration
+//!
+//! Each backend reports whether it changed anything so callers can refresh
+//! the package index once after all repositories are in place.
+
+mod apt;
+mod dnf;
+mod pacman;
+
+use crate::common::error::Result;
+use crate::domain::repository::Repository;
+use colored::Colorize;
+
+/// Configure a repository if it isn't already present
+///
+/// Returns true if the system was (or in dry-run mode would be) changed.
+pub fn ensure_repository(name: &str, repository: &Repository, dry_run: bool) -> Result<bool> {
+    let changed = match repository {
+        Repository::Apt {
+            url,
+            key,
+            suite,
+            components,
+        } => apt::ensure_source(name, &apt::AptSource { url, key, suite, components }, dry_run)?,
+        Repository::Ppa { ppa } => apt::ensure_ppa(ppa, dry_run)?,
+        Repository::Dnf { url } => dnf::ensure_repo_file(name, url, dry_run)?,
+        Repository::Copr { copr } => dnf::ensure_copr(copr, dry_run)?,
+        Repository::Pacman { server, sig_level } => {
+            pacman::ensure_section(name, server.as_deref(), sig_level.as_deref(), dry_run)?
+        }
+    };
+
+    if changed && !dry_run {
+        println!("  {} Added repository {} ({})", "✓".green(), name, repository.describe());
+    }
+    Ok(changed)
+}
+
+/// Remove a repository previously added by devstrap
+pub fn remove_repository(name: &str, repository: &Repository) -> Result<()> {
+    match repository {
+        Repository::Apt { .. } => apt::remove_source(name),
+        Repository::Ppa { ppa } => apt::remove_ppa(ppa),
+        Repository::Dnf { .. } => dnf::remove_repo_file(name),
+        Repository::Copr { copr } => dnf::remove_copr(copr),
+        Repository::Pacman { .. } => pacman::remove_section(name),
+    }
+}
+
+/// Print the dry-run notice for a repository change
+fn print_dry_run(action: &str) {
+    println!("  {} Would {}", "[DRY-RUN]".yellow(), action);
+}
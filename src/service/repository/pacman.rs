@@ -0,0 +1,92 @@
+//! Pacman repository sections in `/etc/pacman.conf`
+
+use super::print_dry_run;
+use crate::common::error::Result;
+use crate::common::system_file::{read_system_file, write_system_file};
+use std::path::Path;
+
+const PACMAN_CONF: &str = "/etc/pacman.conf";
+const DEFAULT_INCLUDE: &str = "Include = /etc/pacman.d/mirrorlist";
+
+/// Append a `[name]` section unless pacman.conf already enables it
+pub(super) fn ensure_section(
+    name: &str,
+    server: Option<&str>,
+    sig_level: Option<&str>,
+    dry_run: bool,
+) -> Result<bool> {
+    let conf = read_system_file(Path::new(PACMAN_CONF));
+    if has_section(&conf, name) {
+        return Ok(false);
+    }
+
+    if dry_run {
+        print_dry_run(&format!("enable pacman repository [{name}]"));
+        return Ok(true);
+    }
+
+    let updated = format!("{}\n\n{}", conf.trim_end(), section(name, server, sig_level));
+    write_system_file(Path::new(PACMAN_CONF), updated.as_bytes())?;
+    Ok(true)
+}
+
+/// Remove the `[name]` section from pacman.conf
+pub(super) fn remove_section(name: &str) -> Result<()> {
+    let conf = read_system_file(Path::new(PACMAN_CONF));
+    if !has_section(&conf, name) {
+        return Ok(());
+    }
+    write_system_file(Path::new(PACMAN_CONF), without_section(&conf, name).as_bytes())
+}
+
+/// Whether an uncommented `[name]` header exists
+fn has_section(conf: &str, name: &str) -> bool {
+    let header = format!("[{name}]");
+    conf.lines().any(|line| line.trim() == header)
+}
+
+/// Render a repository section
+fn section(name: &str, server: Option<&str>, sig_level: Option<&str>) -> String {
+    let mut lines = vec![format!("[{name}]")];
+    if let Some(level) = sig_level {
+        lines.push(format!("SigLevel = {level}"));
+    }
+    lines.push(server.map_or_else(|| DEFAULT_INCLUDE.to_string(), |s| format!("Server = {s}")));
+    lines.join("\n") + "\n"
+}
+
+/// Drop the `[name]` section, keeping every other line intact
+fn without_section(conf: &str, name: &str) -> String {
+    let header = format!("[{name}]");
+    let mut in_section = false;
+    let mut kept = Vec::new();
+
+    for line in conf.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_section = trimmed == header;
+        }
+        if !in_section {
+            kept.push(line);
+        }
+    }
+
+    kept.join("\n").trim_end().to_string() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacman_sections() {
+        let conf = "[options]\nArchitecture = auto\n\n#[multilib]\n#Include = /etc/pacman.d/mirrorlist\n";
+        assert!(!has_section(conf, "multilib"));
+
+        let updated = format!("{}\n\n{}", conf.trim_end(), section("multilib", None, None));
+        assert!(has_section(&updated, "multilib"));
+        assert!(updated.ends_with("[multilib]\nInclude = /etc/pacman.d/mirrorlist\n"));
+
+        assert_eq!(without_section(&updated, "multilib"), conf);
+    }
+}
@@ -0,0 +1,188 @@
+//! Version manager bootstrapping
+//!
+//! Managers are installed by their official installer scripts. The script is
+//! downloaded, its sha256 is checked against a pin, and it is run from a
+//! private temporary file without prompts. The manager's binary must exist
+//! afterwards.
+
+use super::manager::RuntimeManager;
+use crate::common::download::download_bytes;
+use crate::common::error::Result;
+use crate::common::path::make_executable;
+use anyhow::{bail, Context};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// How to install a version manager
+#[derive(Debug, Clone)]
+pub struct InstallerScript {
+    /// Where the script is downloaded from
+    pub url: String,
+    /// Pinned sha256 of the script, for installers published at stable URLs
+    pub sha256: Option<String>,
+    /// Shell used to run the script
+    pub interpreter: String,
+    /// Arguments that make the script run without prompts
+    pub args: Vec<String>,
+    /// Environment variables for the script
+    pub env: Vec<(String, String)>,
+    /// File that exists once the manager is installed
+    pub binary: PathBuf,
+    /// Keep the verified script here instead of running it
+    pub keep_as: Option<PathBuf>,
+}
+
+impl InstallerScript {
+    /// Installer at `url`, run with `interpreter`, providing `binary`
+    #[must_use]
+    pub fn new(url: impl Into<String>, interpreter: &str, binary: PathBuf) -> Self {
+        Self {
+            url: url.into(),
+            sha256: None,
+            interpreter: interpreter.to_string(),
+            args: Vec::new(),
+            env: Vec::new(),
+            binary,
+            keep_as: None,
+        }
+    }
+
+    /// Add arguments passed to the script
+    #[must_use]
+    pub fn args(mut self, args: &[&str]) -> Self {
+        self.args.extend(args.iter().map(ToString::to_string));
+        self
+    }
+
+    /// Set an environment variable for the script
+    #[must_use]
+    pub fn env(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.env.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Keep the script as the manager itself rather than running it
+    #[must_use]
+    pub fn keep_as(mut self, path: PathBuf) -> Self {
+        self.binary.clone_from(&path);
+        self.keep_as = Some(path);
+        self
+    }
+}
+
+/// A completed bootstrap, for recording in the lockfile and state file
+#[derive(Debug, Clone)]
+pub struct Bootstrapped {
+    /// Manager name
+    pub manager: String,
+    /// URL the installer was downloaded from
+    pub url: String,
+    /// sha256 of the installer that was run
+    pub sha256: String,
+}
+
+/// Install a manager with its installer script
+///
+/// `url` overrides the catalog URL (mirrors, tests) and `pinned` the
+/// catalog's checksum. A script with no pin at all is refused unless
+/// `allow_unpinned` is set, in which case it is trusted on first use and its
+/// hash returned for locking.
+pub fn bootstrap_manager(
+    manager: &dyn RuntimeManager,
+    url: Option<&str>,
+    pinned: Option<&str>,
+    allow_unpinned: bool,
+) -> Result<Bootstrapped> {
+    let mut script = manager.installer()?;
+    if let Some(url) = url {
+        script.url = url.to_string();
+    }
+
+    let name = manager.name();
+    let expected = pinned.or(script.sha256.as_deref());
+    if expected.is_none() && !allow_unpinned {
+        bail!(
+            "{name} installer {} has no pinned sha256; set sha256 under [settings.installers.{name}], \
+             or allow_unpinned = true to trust it on first use",
+            script.url
+        );
+    }
+    let sha256 = run_installer(name, &script, expected)?;
+    add_to_path(&script.binary);
+
+    Ok(Bootstrapped {
+        manager: name.to_string(),
+        url: script.url,
+        sha256,
+    })
+}
+
+/// Download, verify and run an installer script, returning its sha256
+pub fn run_installer(name: &str, script: &InstallerScript, expected: Option<&str>) -> Result<String> {
+    let bytes = download_bytes(&script.url)?;
+    let actual = format!("{:x}", Sha256::digest(&bytes));
+    if let Some(expected) = expected.filter(|expected| !expected.eq_ignore_ascii_case(&actual)) {
+        bail!("Checksum mismatch for {name} installer {}: expected {expected}, got {actual}", script.url);
+    }
+
+    if let Some(target) = &script.keep_as {
+        write_script(target, &bytes)?;
+    } else {
+        let mut temp = tempfile::Builder::new()
+            .prefix(&format!("devstrap-{name}-"))
+            .suffix(".sh")
+            .tempfile()
+            .context("Failed to create temporary file")?;
+        temp.write_all(&bytes)
+            .and_then(|()| temp.flush())
+            .with_context(|| format!("Failed to write {}", temp.path().display()))?;
+        execute(name, script, temp.path())?;
+    }
+
+    if !script.binary.exists() {
+        bail!("{name} installer finished but {} was not created", script.binary.display());
+    }
+    Ok(actual)
+}
+
+fn write_script(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+    make_executable(path)
+}
+
+fn execute(name: &str, script: &InstallerScript, path: &Path) -> Result<()> {
+    let output = Command::new(&script.interpreter)
+        .arg(path)
+        .args(&script.args)
+        .envs(script.env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run {name} installer"))?;
+
+    if !output.status.success() {
+        bail!(
+            "{name} installer failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Make a freshly installed manager usable for the rest of this run
+fn add_to_path(binary: &Path) {
+    let Some(dir) = binary.parent() else { return };
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let mut dirs: Vec<PathBuf> = std::env::split_paths(&path).collect();
+    if !dirs.iter().any(|existing| existing == dir) {
+        dirs.insert(0, dir.to_path_buf());
+        if let Ok(joined) = std::env::join_paths(dirs) {
+            std::env::set_var("PATH", joined);
+        }
+    }
+}
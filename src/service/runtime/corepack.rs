@@ -0,0 +1,110 @@
+//! Corepack shims and package manager pins for Node versions
+//!
+//! Corepack ships with Node and provides `pnpm` and `yarn` shims that
+//! download the pinned package manager on first use. Each command runs
+//! through the Node version manager so it applies to one Node version.
+
+use super::manager::RuntimeManager;
+use crate::common::error::Result;
+use anyhow::Context;
+use std::path::PathBuf;
+
+/// Package managers Corepack can pin
+pub const COREPACK_PACKAGE_MANAGERS: [&str; 3] = ["pnpm", "yarn", "npm"];
+
+/// Shims `corepack enable` installs without arguments
+const DEFAULT_SHIMS: [&str; 2] = ["pnpm", "yarn"];
+
+/// Skip Corepack's interactive "download pnpm?" prompt
+const NO_PROMPT: &str = "COREPACK_ENABLE_DOWNLOAD_PROMPT=0";
+
+/// Directory holding the `node` binary of a Node version
+fn node_bin_dir(manager: &dyn RuntimeManager, runtime: &str, version: &str) -> Result<PathBuf> {
+    let dir = manager.exec(runtime, version, &["node", "-p", "require('path').dirname(process.execPath)"])?;
+    Ok(PathBuf::from(dir.trim()))
+}
+
+/// Whether a Node version has Corepack shims for every named package manager
+#[must_use]
+pub fn is_enabled(manager: &dyn RuntimeManager, runtime: &str, version: &str, names: &[&str]) -> bool {
+    let Ok(bin) = node_bin_dir(manager, runtime, version) else {
+        return false;
+    };
+    DEFAULT_SHIMS.iter().chain(names).all(|name| {
+        std::fs::read_link(bin.join(name)).is_ok_and(|target| target.to_string_lossy().contains("corepack"))
+    })
+}
+
+/// Install Corepack shims, including any named package managers
+pub fn enable(manager: &dyn RuntimeManager, runtime: &str, version: &str, names: &[&str]) -> Result<()> {
+    let mut command = vec!["corepack", "enable"];
+    command.extend(DEFAULT_SHIMS.iter().chain(names));
+    manager
+        .exec(runtime, version, &command)
+        .with_context(|| format!("Failed to enable corepack for {runtime} {version}"))?;
+    Ok(())
+}
+
+/// Remove Corepack shims, for one package manager or all of them
+pub fn disable(manager: &dyn RuntimeManager, runtime: &str, version: &str, name: Option<&str>) -> Result<()> {
+    let mut command = vec!["corepack", "disable"];
+    command.extend(name);
+    manager
+        .exec(runtime, version, &command)
+        .with_context(|| format!("Failed to disable corepack for {runtime} {version}"))?;
+    Ok(())
+}
+
+/// Version a package manager shim currently runs, if any
+#[must_use]
+pub fn package_manager_version(
+    manager: &dyn RuntimeManager,
+    runtime: &str,
+    version: &str,
+    name: &str,
+) -> Option<String> {
+    let output = manager.exec(runtime, version, &["env", NO_PROMPT, name, "--version"]).ok()?;
+    parse_version_output(&output)
+}
+
+/// Make `name@requirement` the global default of a Node version's Corepack
+pub fn install_package_manager(
+    manager: &dyn RuntimeManager,
+    runtime: &str,
+    version: &str,
+    name: &str,
+    requirement: &str,
+) -> Result<()> {
+    let target = format!("{name}@{requirement}");
+    manager
+        .exec(runtime, version, &["env", NO_PROMPT, "corepack", "install", "--global", &target])
+        .with_context(|| format!("Failed to install {target} for {runtime} {version}"))?;
+    Ok(())
+}
+
+/// Version line of `pnpm --version`, skipping any download notices
+fn parse_version_output(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_output() {
+        let cases = [
+            ("9.12.1\n", Some("9.12.1")),
+            ("! Corepack is about to download https://registry.npmjs.org/pnpm/-/pnpm-9.12.1.tgz\n9.12.1\n", Some("9.12.1")),
+            ("", None),
+        ];
+
+        for (output, expected) in cases {
+            assert_eq!(parse_version_output(output).as_deref(), expected);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Commands that install and remove global packages through a version manager
+
+use super::installed::go_binary;
+use super::{Ecosystem, GlobalPackage};
+use crate::common::error::Result;
+use crate::service::runtime::manager::RuntimeManager;
+use anyhow::Context;
+
+impl GlobalPackage {
+    /// Command that installs the package, pinned to `pin` when given
+    #[must_use]
+    pub fn install_command(&self, pin: Option<&str>) -> Vec<String> {
+        let name = &self.name;
+        let requirement = pin.or(self.requirement.as_deref());
+        let command: Vec<String> = match (self.ecosystem, requirement) {
+            (Ecosystem::Npm, Some(v)) => vec!["npm".into(), "install".into(), "-g".into(), format!("{name}@{v}")],
+            (Ecosystem::Npm, None) => vec!["npm".into(), "install".into(), "-g".into(), name.clone()],
+            (Ecosystem::Pip, _) => {
+                let target = match (pin, &self.requirement) {
+                    (Some(v), _) => format!("{name}=={v}"),
+                    (None, Some(requirement)) => format!("{name}{requirement}"),
+                    (None, None) => name.clone(),
+                };
+                vec!["python".into(), "-m".into(), "pip".into(), "install".into(), target]
+            }
+            (Ecosystem::Gem, Some(v)) => vec!["gem".into(), "install".into(), name.clone(), "-v".into(), v.into()],
+            (Ecosystem::Gem, None) => vec!["gem".into(), "install".into(), name.clone()],
+            (Ecosystem::Go, v) => vec!["go".into(), "install".into(), format!("{name}@{}", v.unwrap_or("latest"))],
+        };
+        command
+    }
+
+    /// Install the package into a runtime version
+    pub fn install(&self, manager: &dyn RuntimeManager, runtime: &str, version: &str, pin: Option<&str>) -> Result<()> {
+        let command = self.install_command(pin);
+        let command: Vec<&str> = command.iter().map(String::as_str).collect();
+        manager
+            .exec(runtime, version, &command)
+            .with_context(|| format!("Failed to install {} into {runtime} {version}", self.name))?;
+        Ok(())
+    }
+
+    /// Remove the package from a runtime version
+    pub fn uninstall(&self, manager: &dyn RuntimeManager, runtime: &str, version: &str) -> Result<()> {
+        let name = self.name.as_str();
+        let result = match self.ecosystem {
+            Ecosystem::Npm => manager.exec(runtime, version, &["npm", "uninstall", "-g", name]),
+            Ecosystem::Pip => manager.exec(runtime, version, &["python", "-m", "pip", "uninstall", "-y", name]),
+            Ecosystem::Gem => manager.exec(runtime, version, &["gem", "uninstall", "-x", "-a", name]),
+            Ecosystem::Go => {
+                let binary = go_binary(manager, runtime, version, name)?;
+                std::fs::remove_file(&binary)
+                    .with_context(|| format!("Failed to remove {}", binary.display()))
+                    .map(|()| String::new())
+            }
+        };
+        result.with_context(|| format!("Failed to remove {name} from {runtime} {version}"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_command() {
+        let cases = [
+            (Ecosystem::Npm, "typescript@5", None, "npm install -g typescript@5"),
+            (Ecosystem::Npm, "typescript@5", Some("5.4.5"), "npm install -g typescript@5.4.5"),
+            (Ecosystem::Pip, "black>=24", None, "python -m pip install black>=24"),
+            (Ecosystem::Pip, "black>=24", Some("24.10.0"), "python -m pip install black==24.10.0"),
+            (Ecosystem::Gem, "rails", Some("7.1.3"), "gem install rails -v 7.1.3"),
+            (Ecosystem::Go, "mvdan.cc/gofumpt", None, "go install mvdan.cc/gofumpt@latest"),
+        ];
+
+        for (ecosystem, spec, pin, expected) in cases {
+            let package = GlobalPackage::parse(ecosystem, spec).unwrap();
+            assert_eq!(package.install_command(pin).join(" "), expected);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Versions of global packages installed into a runtime version
+
+use super::{Ecosystem, GlobalPackage};
+use crate::common::error::Result;
+use crate::service::runtime::manager::RuntimeManager;
+use std::path::Path;
+
+impl GlobalPackage {
+    /// Version of the package installed in a runtime version, if any
+    #[must_use]
+    pub fn installed_version(&self, manager: &dyn RuntimeManager, runtime: &str, version: &str) -> Option<String> {
+        let name = self.name.as_str();
+        match self.ecosystem {
+            Ecosystem::Npm => {
+                let output = manager.exec(runtime, version, &["npm", "ls", "-g", "--depth=0", "--json", name]).ok()?;
+                parse_npm_ls(&output, name)
+            }
+            Ecosystem::Pip => {
+                let output = manager.exec(runtime, version, &["python", "-m", "pip", "show", name]).ok()?;
+                parse_pip_show(&output)
+            }
+            Ecosystem::Gem => {
+                let output = manager.exec(runtime, version, &["gem", "list", "--local", "--exact", name]).ok()?;
+                parse_gem_list(&output, name)
+            }
+            Ecosystem::Go => {
+                let binary = go_binary(manager, runtime, version, name).ok()?;
+                let binary = binary.to_str()?;
+                let output = manager.exec(runtime, version, &["go", "version", "-m", binary]).ok()?;
+                parse_go_version(&output)
+            }
+        }
+    }
+}
+
+/// Binary `go install` builds for a package path, in the version's `GOPATH`
+pub(super) fn go_binary(manager: &dyn RuntimeManager, runtime: &str, version: &str, package: &str) -> Result<std::path::PathBuf> {
+    let gopath = manager.exec(runtime, version, &["go", "env", "GOPATH"])?;
+    let gopath = gopath.trim().split(':').next().unwrap_or_default();
+    Ok(Path::new(gopath).join("bin").join(go_binary_name(package)))
+}
+
+/// Last path element of a Go package, skipping a major version suffix
+fn go_binary_name(package: &str) -> &str {
+    let mut elements = package.rsplit('/');
+    let last = elements.next().unwrap_or(package);
+    let is_major = last.strip_prefix('v').is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    if is_major {
+        elements.next().unwrap_or(last)
+    } else {
+        last
+    }
+}
+
+/// Version from `npm ls -g --depth=0 --json <name>`
+fn parse_npm_ls(output: &str, name: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(output).ok()?;
+    json["dependencies"][name]["version"].as_str().map(String::from)
+}
+
+/// Version from `pip show <name>`
+fn parse_pip_show(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("Version:"))
+        .map(|version| version.trim().to_string())
+}
+
+/// Newest version from `gem list --local --exact <name>` (`rails (7.1.3, 7.0.8)`)
+fn parse_gem_list(output: &str, name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let versions = line.strip_prefix(name)?.trim().strip_prefix('(')?.strip_suffix(')')?;
+        let newest = versions.split(',').next()?.trim();
+        Some(newest.strip_prefix("default: ").unwrap_or(newest).to_string())
+    })
+}
+
+/// Main module version from `go version -m <binary>`
+fn parse_go_version(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        (fields.next()? == "mod").then(|| fields.nth(1).map(String::from))?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_installed_versions() {
+        let npm = r#"{"dependencies": {"typescript": {"version": "5.4.5", "overridden": false}}}"#;
+        let pip = "Name: black\nVersion: 24.10.0\nSummary: The uncompromising code formatter.\n";
+        let gem = "\n*** LOCAL GEMS ***\n\nrails (7.1.3, 7.0.8)\n";
+        let go = "/home/dev/go/bin/gopls: go1.23.2\n\tpath\tgolang.org/x/tools/gopls\n\tmod\tgolang.org/x/tools/gopls\tv0.16.2\th1:abc=\n";
+
+        let parsed = [
+            parse_npm_ls(npm, "typescript"),
+            parse_npm_ls("{}", "typescript"),
+            parse_pip_show(pip),
+            parse_gem_list(gem, "rails"),
+            parse_gem_list("bundler (default: 2.5.6)", "bundler"),
+            parse_go_version(go),
+        ];
+        let expected = [Some("5.4.5"), None, Some("24.10.0"), Some("7.1.3"), Some("2.5.6"), Some("v0.16.2")];
+        assert_eq!(parsed.iter().map(Option::as_deref).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_go_binary_name() {
+        assert_eq!(go_binary_name("golang.org/x/tools/gopls"), "gopls");
+        assert_eq!(go_binary_name("github.com/go-delve/delve/cmd/dlv"), "dlv");
+        assert_eq!(go_binary_name("github.com/golangci/golangci-lint/v2"), "golangci-lint");
+    }
+}
@@ -0,0 +1,147 @@
+//! Global packages installed into a runtime version
+//!
+//! Each supported runtime installs packages with its own tooling (npm, pip,
+//! gem, `go install`), run through the version manager so they land in the
+//! version they were configured for.
+
+mod commands;
+mod installed;
+
+use crate::common::error::Result;
+use crate::domain::{Version, VersionConstraint};
+use anyhow::anyhow;
+
+/// Runtime names that support `global_packages`
+pub const GLOBAL_PACKAGE_RUNTIMES: [&str; 5] = ["node", "nodejs", "python", "ruby", "go"];
+
+/// Package tooling of a runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    /// `npm install -g`
+    Npm,
+    /// `python -m pip install`
+    Pip,
+    /// `gem install`
+    Gem,
+    /// `go install`
+    Go,
+}
+
+impl Ecosystem {
+    /// Ecosystem used for a runtime's global packages
+    #[must_use]
+    pub fn for_runtime(runtime: &str) -> Option<Self> {
+        match runtime {
+            "node" | "nodejs" => Some(Self::Npm),
+            "python" => Some(Self::Pip),
+            "ruby" => Some(Self::Gem),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+}
+
+/// A global package spec split into name and version requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalPackage {
+    /// Tooling the package is installed with
+    pub ecosystem: Ecosystem,
+    /// Package name (npm package, Python project, gem or Go package path)
+    pub name: String,
+    /// Version requirement as written, without the separator (`5`, `>=23`)
+    pub requirement: Option<String>,
+}
+
+impl GlobalPackage {
+    /// Parse a spec such as `typescript@5`, `@angular/cli`, `black>=24` or
+    /// `golang.org/x/tools/gopls@latest`
+    pub fn parse(ecosystem: Ecosystem, spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let split = match ecosystem {
+            Ecosystem::Npm => spec.rfind('@').filter(|&i| i > 0).map(|i| (i, 1)),
+            Ecosystem::Pip => spec.find(['=', '<', '>', '!', '~', ' ', ';']).map(|i| (i, 0)),
+            Ecosystem::Gem | Ecosystem::Go => spec.find('@').map(|i| (i, 1)),
+        };
+        let (name, requirement) = match split {
+            Some((i, skip)) => (&spec[..i], Some(spec[i + skip..].trim())),
+            None => (spec, None),
+        };
+
+        if name.is_empty() || requirement == Some("") {
+            return Err(anyhow!("Invalid global package '{spec}'"));
+        }
+        Ok(Self {
+            ecosystem,
+            name: name.to_string(),
+            requirement: requirement.map(String::from),
+        })
+    }
+
+    /// Whether an installed version satisfies the requirement
+    ///
+    /// Requirements that aren't version constraints (npm tags, pip
+    /// extras) are never considered satisfied, so the package is reinstalled
+    /// until its resolved version is locked.
+    #[must_use]
+    pub fn accepts(&self, version: &str) -> bool {
+        self.requirement
+            .as_deref()
+            .map_or(true, |requirement| requirement_accepts(requirement.strip_prefix("==").unwrap_or(requirement), version))
+    }
+}
+
+/// Whether a version satisfies a requirement that is a version constraint
+///
+/// Keywords such as `latest` or `stable` accept any version.
+#[must_use]
+pub fn requirement_accepts(requirement: &str, version: &str) -> bool {
+    match (VersionConstraint::parse(requirement), Version::parse(version)) {
+        (Ok(constraint), Some(version)) => constraint.matches(&version),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_specs() {
+        let cases = [
+            (Ecosystem::Npm, "typescript@5", "typescript", Some("5")),
+            (Ecosystem::Npm, "@angular/cli", "@angular/cli", None),
+            (Ecosystem::Npm, "@angular/cli@17", "@angular/cli", Some("17")),
+            (Ecosystem::Pip, "black>=24", "black", Some(">=24")),
+            (Ecosystem::Pip, "ruff==0.7.0", "ruff", Some("==0.7.0")),
+            (Ecosystem::Pip, "httpie", "httpie", None),
+            (Ecosystem::Gem, "rails@7.1", "rails", Some("7.1")),
+            (Ecosystem::Go, "golang.org/x/tools/gopls@latest", "golang.org/x/tools/gopls", Some("latest")),
+        ];
+
+        for (ecosystem, spec, name, requirement) in cases {
+            let package = GlobalPackage::parse(ecosystem, spec).unwrap();
+            assert_eq!((package.name.as_str(), package.requirement.as_deref()), (name, requirement), "{spec}");
+        }
+
+        assert!(GlobalPackage::parse(Ecosystem::Npm, "typescript@").is_err());
+        assert!(GlobalPackage::parse(Ecosystem::Pip, ">=1").is_err());
+    }
+
+    #[test]
+    fn test_accepts() {
+        let cases = [
+            (Ecosystem::Npm, "typescript@5", "5.4.5", true),
+            (Ecosystem::Npm, "typescript@5", "4.9.5", false),
+            (Ecosystem::Npm, "typescript@next", "5.4.5", false),
+            (Ecosystem::Pip, "ruff==0.7.0", "0.7.0", true),
+            (Ecosystem::Pip, "black>=24", "24.10.0", true),
+            (Ecosystem::Gem, "rails", "7.1.3", true),
+            (Ecosystem::Go, "golang.org/x/tools/gopls@latest", "v0.16.2", true),
+        ];
+
+        for (ecosystem, spec, version, accepted) in cases {
+            let package = GlobalPackage::parse(ecosystem, spec).unwrap();
+            assert_eq!(package.accepts(version), accepted, "{spec} {version}");
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Microsoft's `dotnet-install.sh` for .NET SDKs
+//!
+//! SDKs are installed side by side under `~/.dotnet`. Versions are listed
+//! from the official release index: each channel (`8.0`, `9.0`) offers its
+//! newest SDK, and `lts`/`sts` pick the newest channel of that release type.
+
+use super::{select_remote, RuntimeManager};
+use crate::common::download::download_text;
+use crate::common::error::Result;
+use crate::common::path::home_dir;
+use crate::common::run_command;
+use crate::common::command::run_command_output;
+use crate::domain::VersionConstraint;
+use crate::service::runtime::bootstrap::InstallerScript;
+use anyhow::{anyhow, Context};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Official installer script
+const INSTALL_SCRIPT_URL: &str = "https://dot.net/v1/dotnet-install.sh";
+
+/// Index of every .NET release channel
+const RELEASES_INDEX_URL: &str =
+    "https://dotnetcli.blob.core.windows.net/dotnet/release-metadata/releases-index.json";
+
+pub(super) struct DotnetInstall;
+
+#[derive(Debug, Deserialize)]
+struct ReleasesIndex {
+    #[serde(rename = "releases-index")]
+    channels: Vec<Channel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Channel {
+    #[serde(rename = "latest-sdk")]
+    latest_sdk: String,
+    #[serde(rename = "release-type")]
+    release_type: String,
+}
+
+impl RuntimeManager for DotnetInstall {
+    fn name(&self) -> &'static str {
+        "dotnet-install"
+    }
+
+    /// SDK builds are picked for this machine's os and architecture
+    fn lists_host_builds(&self) -> bool {
+        true
+    }
+
+    /// The script is the manager, so it is kept rather than run
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new(INSTALL_SCRIPT_URL, "bash", install_script()?).keep_as(install_script()?))
+    }
+
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(releases_index()?.into_iter().map(|channel| channel.latest_sdk).collect())
+    }
+
+    /// `dotnet --list-sdks` prints `8.0.403 [/home/me/.dotnet/sdk]`
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        let dotnet = dotnet_root()?.join("dotnet");
+        if !dotnet.is_file() {
+            return Ok(Vec::new());
+        }
+
+        Ok(run_command_output(&dotnet.to_string_lossy(), &["--list-sdks"])?
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(String::from)
+            .collect())
+    }
+
+    fn resolve(&self, runtime: &str, constraint: &VersionConstraint) -> Result<String> {
+        match constraint {
+            VersionConstraint::Keyword(keyword) => newest_of_type(&releases_index()?, keyword),
+            _ => select_remote(self, runtime, constraint),
+        }
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        let root = dotnet_root()?;
+        run_command(
+            &install_script()?.to_string_lossy(),
+            &["--version", version, "--install-dir", &root.to_string_lossy(), "--no-path"],
+        )?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        let sdk = dotnet_root()?.join("sdk").join(version);
+        fs::remove_dir_all(&sdk).with_context(|| format!("Failed to remove {}", sdk.display()))
+    }
+
+    /// The SDK pinned by `~/global.json`
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        let global_json = home_dir()?.join("global.json");
+        let Ok(contents) = fs::read_to_string(&global_json) else {
+            return Ok(None);
+        };
+        let json: serde_json::Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", global_json.display()))?;
+        Ok(json["sdk"]["version"].as_str().map(String::from))
+    }
+
+    /// .NET has no global default; `~/global.json` pins the SDK for
+    /// everything under the home directory
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        let global_json = home_dir()?.join("global.json");
+        let contents = format!(
+            "{{\n  \"sdk\": {{\n    \"version\": \"{version}\",\n    \"rollForward\": \"latestPatch\"\n  }}\n}}\n"
+        );
+        fs::write(&global_json, contents)
+            .with_context(|| format!("Failed to write {}", global_json.display()))
+    }
+
+    fn env_activation(&self, _shell: &str) -> String {
+        "export DOTNET_ROOT=\"$HOME/.dotnet\" PATH=\"$HOME/.dotnet:$PATH\"".to_string()
+    }
+}
+
+fn dotnet_root() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".dotnet"))
+}
+
+fn install_script() -> Result<PathBuf> {
+    Ok(dotnet_root()?.join("dotnet-install.sh"))
+}
+
+/// Release channels, oldest first
+fn releases_index() -> Result<Vec<Channel>> {
+    parse_releases_index(&download_text(RELEASES_INDEX_URL)?)
+}
+
+fn parse_releases_index(json: &str) -> Result<Vec<Channel>> {
+    let index: ReleasesIndex =
+        serde_json::from_str(json).context("Failed to parse .NET releases index")?;
+    Ok(index.channels.into_iter().rev().collect())
+}
+
+/// Newest SDK from a channel of the given release type (`lts`, `sts`)
+fn newest_of_type(channels: &[Channel], release_type: &str) -> Result<String> {
+    channels
+        .iter()
+        .rfind(|channel| channel.release_type == release_type)
+        .map(|channel| channel.latest_sdk.clone())
+        .ok_or_else(|| anyhow!("No .NET channel has release type '{release_type}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_releases_index() {
+        let json = r#"{"releases-index": [
+            {"channel-version": "9.0", "latest-sdk": "9.0.100", "release-type": "sts"},
+            {"channel-version": "8.0", "latest-sdk": "8.0.403", "release-type": "lts"},
+            {"channel-version": "6.0", "latest-sdk": "6.0.427", "release-type": "lts"}
+        ]}"#;
+        let channels = parse_releases_index(json).unwrap();
+
+        assert_eq!(newest_of_type(&channels, "lts").unwrap(), "8.0.403");
+        assert_eq!(newest_of_type(&channels, "sts").unwrap(), "9.0.100");
+        assert!(newest_of_type(&channels, "preview").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! FNM (Fast Node Manager)
+
+use super::{exec_with, home_path, output_lines, select_remote, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::domain::VersionConstraint;
+use anyhow::anyhow;
+
+pub(super) struct Fnm;
+
+impl RuntimeManager for Fnm {
+    fn name(&self) -> &'static str {
+        "fnm"
+    }
+
+    fn installer(&self) -> Result<InstallerScript> {
+        let dir = home_path(".local/share/fnm")?;
+        Ok(InstallerScript::new("https://fnm.vercel.app/install", "bash", dir.join("fnm"))
+            .args(&["--skip-shell", "--install-dir", &dir.to_string_lossy()]))
+    }
+
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(output_lines("fnm", &["ls-remote"])?
+            .iter()
+            .filter_map(|l| l.split_whitespace().next())
+            .map(String::from)
+            .collect())
+    }
+
+    /// `fnm list` prints `* v20.10.0 default`, plus `* system` when present
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(output_lines("fnm", &["list"])?
+            .iter()
+            .filter_map(|l| installed_version(l))
+            .map(String::from)
+            .collect())
+    }
+
+    /// The version `fnm list` labels `default`
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        Ok(output_lines("fnm", &["list"])?
+            .iter()
+            .filter(|l| l.split_whitespace().any(|token| token == "default"))
+            .find_map(|l| installed_version(l))
+            .map(String::from))
+    }
+
+    /// fnm marks LTS releases in `ls-remote`, so "lts" is resolved here
+    fn resolve(&self, runtime: &str, constraint: &VersionConstraint) -> Result<String> {
+        match constraint {
+            VersionConstraint::Keyword(keyword) if keyword == "lts" => resolve_lts(),
+            VersionConstraint::Keyword(keyword) => Ok(keyword.clone()),
+            _ => select_remote(self, runtime, constraint),
+        }
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("fnm", &["install", version])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("fnm", &["uninstall", version])?;
+        Ok(())
+    }
+
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("fnm", &["default", version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, shell: &str) -> String {
+        format!("eval \"$(fnm env --use-on-cd --shell {shell})\"")
+    }
+
+    fn exec(&self, _runtime: &str, version: &str, command: &[&str]) -> Result<String> {
+        exec_with(&["fnm", "exec", &format!("--using={version}")], command)
+    }
+}
+
+fn installed_version(line: &str) -> Option<&str> {
+    line.split_whitespace().find(|token| token.starts_with('v'))
+}
+
+/// Newest LTS release
+fn resolve_lts() -> Result<String> {
+    output_lines("fnm", &["ls-remote"])?
+        .iter()
+        .rfind(|l| l.contains("LTS"))
+        .and_then(|s| s.split_whitespace().next())
+        .map(String::from)
+        .ok_or_else(|| anyhow!("No LTS version found"))
+}
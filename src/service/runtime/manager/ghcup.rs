@@ -0,0 +1,145 @@
+//! ghcup, the manager for GHC, cabal, HLS and stack
+
+use super::{home_path, output_lines, select_remote, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::domain::VersionConstraint;
+use anyhow::anyhow;
+
+pub(super) struct Ghcup;
+
+/// A row of `ghcup list --raw-format`
+struct ListEntry {
+    version: String,
+    installed: bool,
+    set: bool,
+    tags: Vec<String>,
+}
+
+impl RuntimeManager for Ghcup {
+    fn name(&self) -> &'static str {
+        "ghcup"
+    }
+
+    /// `ghcup list` shows the builds available for this machine's platform
+    fn lists_host_builds(&self) -> bool {
+        true
+    }
+
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new("https://get-ghcup.haskell.org", "sh", home_path(".ghcup/bin/ghcup")?)
+            .env("BOOTSTRAP_HASKELL_NONINTERACTIVE", "1")
+            .env("BOOTSTRAP_HASKELL_MINIMAL", "1"))
+    }
+
+    fn list_remote(&self, runtime: &str) -> Result<Vec<String>> {
+        Ok(list(runtime)?.into_iter().map(|entry| entry.version).collect())
+    }
+
+    fn list_installed(&self, runtime: &str) -> Result<Vec<String>> {
+        Ok(list(runtime)?
+            .into_iter()
+            .filter(|entry| entry.installed)
+            .map(|entry| entry.version)
+            .collect())
+    }
+
+    /// "recommended" is a ghcup tag, resolved to the version carrying it
+    fn resolve(&self, runtime: &str, constraint: &VersionConstraint) -> Result<String> {
+        match constraint {
+            VersionConstraint::Keyword(keyword) => list(runtime)?
+                .into_iter()
+                .rfind(|entry| entry.tags.contains(keyword))
+                .map(|entry| entry.version)
+                .ok_or_else(|| anyhow!("No {runtime} version is tagged '{keyword}'")),
+            _ => select_remote(self, runtime, constraint),
+        }
+    }
+
+    fn install(&self, runtime: &str, version: &str) -> Result<()> {
+        run_command("ghcup", &["install", runtime, version])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, runtime: &str, version: &str) -> Result<()> {
+        run_command("ghcup", &["rm", runtime, version])?;
+        Ok(())
+    }
+
+    fn current_default(&self, runtime: &str) -> Result<Option<String>> {
+        Ok(list(runtime)?.into_iter().find(|entry| entry.set).map(|entry| entry.version))
+    }
+
+    fn set_default(&self, runtime: &str, version: &str) -> Result<()> {
+        run_command("ghcup", &["set", runtime, version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, _shell: &str) -> String {
+        ". \"$HOME/.ghcup/env\"".to_string()
+    }
+}
+
+fn list(tool: &str) -> Result<Vec<ListEntry>> {
+    let lines = output_lines("ghcup", &["list", "--raw-format", "--tool", tool])?;
+    Ok(parse_list(&lines, tool))
+}
+
+/// Parse `ghcup list --raw-format` rows (oldest first)
+///
+/// Rows look like `✔ ghc 9.4.8 base-4.17.2.1 recommended,hls-powered`; the
+/// first column is `✗` when not installed, `✓` when installed and `✔` when
+/// installed and set as the default.
+fn parse_list(lines: &[String], tool: &str) -> Vec<ListEntry> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            let position = columns.iter().position(|column| *column == tool)?;
+            let version = columns.get(position + 1)?;
+            Some(ListEntry {
+                version: (*version).to_string(),
+                installed: columns[0] != "✗",
+                set: columns[0] == "✔",
+                tags: columns[position + 2..]
+                    .iter()
+                    .flat_map(|column| column.split(','))
+                    .map(String::from)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let lines = [
+            "✗ ghc 9.2.8 base-4.16.4.0 hls-powered",
+            "✔ ghc 9.4.8 base-4.17.2.1 recommended,hls-powered",
+            "✓ ghc 9.6.6 base-4.18.2.1 hls-powered",
+            "✗ ghc 9.10.1 base-4.20.0.0 latest",
+        ]
+        .map(String::from);
+
+        let entries = parse_list(&lines, "ghc");
+        let summary: Vec<(&str, bool, bool, bool)> = entries
+            .iter()
+            .map(|e| (e.version.as_str(), e.installed, e.set, e.tags.iter().any(|t| t == "recommended")))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("9.2.8", false, false, false),
+                ("9.4.8", true, true, true),
+                ("9.6.6", true, false, false),
+                ("9.10.1", false, false, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Mise, the default manager for most runtimes
+
+use super::{exec_with, home_path, output_lines, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::command::run_command_output;
+use crate::common::run_command;
+use anyhow::Context;
+use serde::Deserialize;
+
+pub(super) struct Mise;
+
+impl RuntimeManager for Mise {
+    fn name(&self) -> &'static str {
+        "mise"
+    }
+
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new("https://mise.run", "sh", home_path(".local/bin/mise")?))
+    }
+
+    fn list_remote(&self, runtime: &str) -> Result<Vec<String>> {
+        output_lines("mise", &["ls-remote", runtime])
+    }
+
+    fn list_installed(&self, runtime: &str) -> Result<Vec<String>> {
+        Ok(ls_json(runtime, "--installed")?.into_iter().map(|entry| entry.version).collect())
+    }
+
+    /// The version requested by the global config
+    fn current_default(&self, runtime: &str) -> Result<Option<String>> {
+        Ok(ls_json(runtime, "--global")?.into_iter().next().map(|entry| entry.version))
+    }
+
+    fn install(&self, runtime: &str, version: &str) -> Result<()> {
+        run_command("mise", &["install", &format!("{runtime}@{version}")])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, runtime: &str, version: &str) -> Result<()> {
+        run_command("mise", &["uninstall", &format!("{runtime}@{version}")])?;
+        Ok(())
+    }
+
+    fn set_default(&self, runtime: &str, version: &str) -> Result<()> {
+        run_command("mise", &["use", "--global", &format!("{runtime}@{version}")])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, shell: &str) -> String {
+        format!("eval \"$(mise activate {shell})\"")
+    }
+
+    fn exec(&self, runtime: &str, version: &str, command: &[&str]) -> Result<String> {
+        exec_with(&["mise", "exec", &format!("{runtime}@{version}"), "--"], command)
+    }
+}
+
+/// An entry of `mise ls --json <runtime>`
+#[derive(Debug, Deserialize)]
+struct LsEntry {
+    version: String,
+}
+
+fn ls_json(runtime: &str, filter: &str) -> Result<Vec<LsEntry>> {
+    let json = run_command_output("mise", &["ls", filter, "--json", runtime])?;
+    parse_ls_json(&json)
+}
+
+fn parse_ls_json(json: &str) -> Result<Vec<LsEntry>> {
+    if json.is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(json).context("Failed to parse mise ls output")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ls_json() {
+        let json = r#"[
+            {"version": "3.11.9", "install_path": "/home/me/.local/share/mise/installs/python/3.11.9", "installed": true},
+            {"version": "3.12.4", "requested_version": "3.12", "installed": true, "active": true}
+        ]"#;
+        let versions: Vec<String> = parse_ls_json(json).unwrap().into_iter().map(|e| e.version).collect();
+
+        assert_eq!(versions, ["3.11.9", "3.12.4"]);
+        assert!(parse_ls_json("").unwrap().is_empty());
+    }
+}
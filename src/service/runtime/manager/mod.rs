@@ -0,0 +1,190 @@
+//! Runtime version managers
+//!
+//! Everything devstrap knows about a version manager lives behind
+//! [`RuntimeManager`]. Supporting a new manager means implementing the trait
+//! in its own module and adding it to [`MANAGERS`].
+
+mod dotnet;
+mod fnm;
+mod ghcup;
+mod mise;
+mod opam;
+mod pyenv;
+mod rbenv;
+pub mod rustup;
+pub mod sdkman;
+mod zvm;
+
+use super::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::path::home_dir;
+use crate::common::command::run_command_output;
+use crate::domain::VersionConstraint;
+use anyhow::anyhow;
+
+/// A tool that installs and switches between runtime versions
+pub trait RuntimeManager: Sync {
+    /// Name used for `manager = "..."` in the config
+    fn name(&self) -> &'static str;
+
+    /// Official installer used to bootstrap the manager
+    fn installer(&self) -> Result<InstallerScript>;
+
+    /// Whether the manager is available on this machine
+    fn is_installed(&self) -> bool {
+        which::which(self.name()).is_ok()
+            || self.installer().is_ok_and(|installer| installer.binary.exists())
+    }
+
+    /// Versions of a runtime the manager can install
+    fn list_remote(&self, runtime: &str) -> Result<Vec<String>>;
+
+    /// Whether the versions it lists are builds for this machine's platform only
+    ///
+    /// `update --platform` leaves such runtimes to the first sync on the other platform.
+    fn lists_host_builds(&self) -> bool {
+        false
+    }
+
+    /// Versions of a runtime the manager has installed
+    fn list_installed(&self, runtime: &str) -> Result<Vec<String>>;
+
+    /// Resolve a non-exact constraint to a version the manager can install
+    ///
+    /// Keywords are passed through for the manager to interpret; everything
+    /// else is matched against [`RuntimeManager::list_remote`].
+    fn resolve(&self, runtime: &str, constraint: &VersionConstraint) -> Result<String> {
+        match constraint {
+            VersionConstraint::Keyword(keyword) => Ok(keyword.clone()),
+            _ => select_remote(self, runtime, constraint),
+        }
+    }
+
+    /// Resolve a constraint to a build of one distribution (`temurin`, `zulu`)
+    fn resolve_distribution(&self, runtime: &str, _constraint: &VersionConstraint, distribution: &str) -> Result<String> {
+        Err(anyhow!("{} has no '{distribution}' distribution of {runtime}", self.name()))
+    }
+
+    /// Install a resolved version
+    fn install(&self, runtime: &str, version: &str) -> Result<()>;
+
+    /// Remove an installed version
+    fn uninstall(&self, runtime: &str, version: &str) -> Result<()>;
+
+    /// Version currently used by default, if any
+    fn current_default(&self, runtime: &str) -> Result<Option<String>>;
+
+    /// Make a version the default for new shells
+    fn set_default(&self, runtime: &str, version: &str) -> Result<()>;
+
+    /// Shell snippet that puts the manager's runtimes on `PATH`
+    fn env_activation(&self, shell: &str) -> String;
+
+    /// Run a command with a specific version active, returning its stdout
+    fn exec(&self, runtime: &str, _version: &str, _command: &[&str]) -> Result<String> {
+        Err(anyhow!("{} cannot run commands with a specific {runtime} version", self.name()))
+    }
+}
+
+/// Every supported manager
+static MANAGERS: &[&dyn RuntimeManager] = &[
+    &mise::Mise,
+    &rustup::Rustup,
+    &fnm::Fnm,
+    &sdkman::Sdkman,
+    &pyenv::Pyenv,
+    &rbenv::Rbenv,
+    &ghcup::Ghcup,
+    &opam::Opam,
+    &dotnet::DotnetInstall,
+    &zvm::Zvm,
+];
+
+/// Look up a manager by name
+#[must_use]
+pub fn get_manager(name: &str) -> Option<&'static dyn RuntimeManager> {
+    MANAGERS.iter().copied().find(|manager| manager.name() == name)
+}
+
+/// Look up a manager by name, failing for unknown managers
+pub fn require_manager(name: &str) -> Result<&'static dyn RuntimeManager> {
+    get_manager(name).ok_or_else(|| {
+        anyhow!(
+            "Unknown runtime manager '{name}' (expected one of: {})",
+            manager_names().join(", ")
+        )
+    })
+}
+
+/// Names of every supported manager
+#[must_use]
+pub fn manager_names() -> Vec<&'static str> {
+    MANAGERS.iter().map(|manager| manager.name()).collect()
+}
+
+/// Whether two version strings name the same release (`v20.10.0` and `20.10.0`)
+#[must_use]
+pub fn same_version(a: &str, b: &str) -> bool {
+    a.trim_start_matches('v') == b.trim_start_matches('v')
+}
+
+/// Pick the best version from what the manager lists as installable
+fn select_remote<M: RuntimeManager + ?Sized>(
+    manager: &M,
+    runtime: &str,
+    constraint: &VersionConstraint,
+) -> Result<String> {
+    let available = manager.list_remote(runtime)?;
+    constraint
+        .select(available.iter().map(String::as_str), release_line_depth(runtime))
+        .ok_or_else(|| anyhow!("No {runtime} version available via {} matches", manager.name()))
+}
+
+/// Number of version components that make a release line for `latest-N`
+fn release_line_depth(runtime: &str) -> usize {
+    match runtime {
+        "node" | "nodejs" | "java" | "kotlin" | "scala" | "groovy" | "deno" | "bun" => 1,
+        _ => 2,
+    }
+}
+
+/// Run a command and return its non-empty, trimmed output lines
+fn output_lines(command: &str, args: &[&str]) -> Result<Vec<String>> {
+    Ok(run_command_output(command, args)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Run `program` followed by `command`
+fn exec_with(program: &[&str], command: &[&str]) -> Result<String> {
+    let args: Vec<&str> = program[1..].iter().chain(command).copied().collect();
+    run_command_output(program[0], &args)
+}
+
+/// Path under the home directory where an installer puts its files
+fn home_path(relative: &str) -> Result<std::path::PathBuf> {
+    Ok(home_dir()?.join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_lookup() {
+        for name in manager_names() {
+            assert_eq!(get_manager(name).map(RuntimeManager::name), Some(name));
+        }
+        assert!(require_manager("asdf").is_err());
+    }
+
+    #[test]
+    fn test_same_version() {
+        assert!(same_version("v20.10.0", "20.10.0"));
+        assert!(same_version("3.12.4", "3.12.4"));
+        assert!(!same_version("3.12.4", "3.12"));
+    }
+}
@@ -0,0 +1,82 @@
+//! opam, managing OCaml compilers as switches
+//!
+//! Each version gets its own switch named after the compiler version, so
+//! `ocaml = "5.1"` installs switch `5.1.1` with `ocaml-base-compiler.5.1.1`.
+
+use super::{home_path, output_lines, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+use std::path::{Path, PathBuf};
+
+/// Package providing the upstream compiler
+const COMPILER_PACKAGE: &str = "ocaml-base-compiler";
+
+pub(super) struct Opam;
+
+impl RuntimeManager for Opam {
+    fn name(&self) -> &'static str {
+        "opam"
+    }
+
+    /// The script asks for an install directory; empty stdin takes the default
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new(
+            "https://opam.ocaml.org/install.sh",
+            "sh",
+            PathBuf::from("/usr/local/bin/opam"),
+        ))
+    }
+
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        ensure_initialised()?;
+        let lines = output_lines("opam", &["show", COMPILER_PACKAGE, "--field=all-versions"])?;
+        Ok(lines
+            .iter()
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|version| !version.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        output_lines("opam", &["switch", "list", "--short"])
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        ensure_initialised()?;
+        let compiler = format!("{COMPILER_PACKAGE}.{version}");
+        run_command("opam", &["switch", "create", version, &compiler, "--yes"])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("opam", &["switch", "remove", version, "--yes"])?;
+        Ok(())
+    }
+
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        Ok(output_lines("opam", &["switch", "show"])?.into_iter().next())
+    }
+
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("opam", &["switch", "set", version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, _shell: &str) -> String {
+        "eval \"$(opam env)\"".to_string()
+    }
+}
+
+/// Create the opam root on first use
+///
+/// The installer only provides the binary; `opam init --bare` sets up the
+/// root without building a compiler, since every version gets its own switch.
+fn ensure_initialised() -> Result<()> {
+    let root = std::env::var_os("OPAMROOT").map_or_else(|| home_path(".opam"), |root| Ok(PathBuf::from(root)))?;
+    if !Path::new(&root).join("config").is_file() {
+        run_command("opam", &["init", "--yes", "--bare", "--disable-sandboxing"])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Pyenv, building Python versions from source
+
+use super::{exec_with, home_path, output_lines, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+
+pub(super) struct Pyenv;
+
+impl RuntimeManager for Pyenv {
+    fn name(&self) -> &'static str {
+        "pyenv"
+    }
+
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new("https://pyenv.run", "bash", home_path(".pyenv/bin/pyenv")?))
+    }
+
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        output_lines("pyenv", &["install", "--list"])
+    }
+
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        output_lines("pyenv", &["versions", "--bare"])
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("pyenv", &["install", version])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("pyenv", &["uninstall", "-f", version])?;
+        Ok(())
+    }
+
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        Ok(output_lines("pyenv", &["global"])?.into_iter().next().filter(|v| v != "system"))
+    }
+
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("pyenv", &["global", version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, shell: &str) -> String {
+        format!("eval \"$(pyenv init - {shell})\"")
+    }
+
+    fn exec(&self, _runtime: &str, version: &str, command: &[&str]) -> Result<String> {
+        exec_with(&["env", &format!("PYENV_VERSION={version}"), "pyenv", "exec"], command)
+    }
+}
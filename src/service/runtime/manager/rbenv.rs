@@ -0,0 +1,57 @@
+//! Rbenv, building Ruby versions with ruby-build
+
+use super::{exec_with, home_path, output_lines, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+
+pub(super) struct Rbenv;
+
+impl RuntimeManager for Rbenv {
+    fn name(&self) -> &'static str {
+        "rbenv"
+    }
+
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new(
+            "https://github.com/rbenv/rbenv-installer/raw/HEAD/bin/rbenv-installer",
+            "bash",
+            home_path(".rbenv/bin/rbenv")?,
+        ))
+    }
+
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        output_lines("rbenv", &["install", "--list-all"])
+    }
+
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        output_lines("rbenv", &["versions", "--bare"])
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("rbenv", &["install", version])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("rbenv", &["uninstall", "-f", version])?;
+        Ok(())
+    }
+
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        Ok(output_lines("rbenv", &["global"])?.into_iter().next().filter(|v| v != "system"))
+    }
+
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("rbenv", &["global", version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, shell: &str) -> String {
+        format!("eval \"$(rbenv init - {shell})\"")
+    }
+
+    fn exec(&self, _runtime: &str, version: &str, command: &[&str]) -> Result<String> {
+        exec_with(&["env", &format!("RBENV_VERSION={version}"), "rbenv", "exec"], command)
+    }
+}
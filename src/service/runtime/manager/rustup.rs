@@ -0,0 +1,260 @@
+//! Rustup, the manager for Rust toolchains
+//!
+//! Channels move, so "stable" alone isn't reproducible. The channel
+//! manifests on the dist server say which release each channel currently
+//! points to; that concrete toolchain is what gets installed and locked.
+
+use super::{home_path, output_lines, RuntimeManager};
+use crate::common::download::download_text;
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+use crate::domain::{Version, VersionConstraint};
+use anyhow::{anyhow, bail, Context};
+use serde::Deserialize;
+
+/// Dist server used when `RUSTUP_DIST_SERVER` is not set
+pub const DEFAULT_DIST_SERVER: &str = "https://static.rust-lang.org";
+
+/// The parts of a channel manifest needed to name a toolchain
+#[derive(Debug, Deserialize)]
+struct ChannelManifest {
+    date: String,
+    pkg: ManifestPackages,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackages {
+    rust: ManifestPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    version: String,
+}
+
+/// Architectures that start the host triple in a toolchain name
+const HOST_ARCHES: &[&str] = &[
+    "x86_64", "aarch64", "i686", "armv7", "arm", "riscv64gc", "powerpc64le", "s390x", "loongarch64",
+];
+
+pub(super) struct Rustup;
+
+impl RuntimeManager for Rustup {
+    fn name(&self) -> &'static str {
+        "rustup"
+    }
+
+    /// Installs rustup only; toolchains come from the lockfile
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new("https://sh.rustup.rs", "sh", home_path(".cargo/bin/rustup")?)
+            .args(&["-y", "--no-modify-path", "--default-toolchain", "none"]))
+    }
+
+    /// Rustup has no release listing; channels are resolved from manifests
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(["stable", "beta", "nightly"].map(String::from).to_vec())
+    }
+
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(output_lines("rustup", &["toolchain", "list"])?
+            .iter()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(|toolchain| strip_host(toolchain).to_string())
+            .collect())
+    }
+
+    /// Resolve using the same dist server rustup itself uses
+    fn resolve(&self, _runtime: &str, constraint: &VersionConstraint) -> Result<String> {
+        let server =
+            std::env::var("RUSTUP_DIST_SERVER").unwrap_or_else(|_| DEFAULT_DIST_SERVER.to_string());
+        resolve_with_server(constraint, &server)
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("rustup", &["toolchain", "install", version])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("rustup", &["toolchain", "uninstall", version])?;
+        Ok(())
+    }
+
+    /// `rustup default` prints `1.82.0-x86_64-unknown-linux-gnu (default)`
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        Ok(output_lines("rustup", &["default"])?
+            .first()
+            .and_then(|line| line.split_whitespace().next())
+            .map(|toolchain| strip_host(toolchain).to_string()))
+    }
+
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("rustup", &["default", version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, _shell: &str) -> String {
+        ". \"$HOME/.cargo/env\"".to_string()
+    }
+}
+
+/// Profiles accepted by `rustup set profile`
+pub const PROFILES: &[&str] = &["minimal", "default", "complete"];
+
+/// Something added to a toolchain after it is installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ToolchainItem {
+    /// `rustup component`, such as clippy or rust-src
+    Component,
+    /// `rustup target`, such as wasm32-unknown-unknown
+    Target,
+}
+
+impl ToolchainItem {
+    /// Rustup subcommand and display label
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Component => "component",
+            Self::Target => "target",
+        }
+    }
+
+    /// Components or targets installed in a toolchain
+    ///
+    /// Host components are listed as `clippy-x86_64-unknown-linux-gnu`, so
+    /// the host triple is dropped to match the names used in config.
+    pub fn list_installed(self, toolchain: &str) -> Result<Vec<String>> {
+        let lines = output_lines("rustup", &[self.as_str(), "list", "--installed", "--toolchain", toolchain])?;
+        Ok(lines
+            .iter()
+            .map(|line| match self {
+                Self::Component => strip_host(line).to_string(),
+                Self::Target => line.clone(),
+            })
+            .collect())
+    }
+
+    /// Add a component or target to a toolchain
+    pub fn add(self, toolchain: &str, name: &str) -> Result<()> {
+        run_command("rustup", &[self.as_str(), "add", "--toolchain", toolchain, name])?;
+        Ok(())
+    }
+
+    /// Remove a component or target from a toolchain
+    pub fn remove(self, toolchain: &str, name: &str) -> Result<()> {
+        run_command("rustup", &[self.as_str(), "remove", "--toolchain", toolchain, name])?;
+        Ok(())
+    }
+}
+
+/// Profile used for toolchains installed from now on
+pub fn current_profile() -> Result<String> {
+    Ok(output_lines("rustup", &["show", "profile"])?.into_iter().next().unwrap_or_default())
+}
+
+/// Set the profile used for toolchains installed from now on
+pub fn set_profile(profile: &str) -> Result<()> {
+    run_command("rustup", &["set", "profile", profile])?;
+    Ok(())
+}
+
+/// Drop the host triple from an installed toolchain name
+///
+/// `1.82.0-x86_64-unknown-linux-gnu` becomes `1.82.0` and
+/// `nightly-2026-10-01-aarch64-apple-darwin` becomes `nightly-2026-10-01`.
+fn strip_host(toolchain: &str) -> &str {
+    toolchain
+        .match_indices('-')
+        .find(|(i, _)| HOST_ARCHES.iter().any(|arch| toolchain[i + 1..].starts_with(arch)))
+        .map_or(toolchain, |(i, _)| &toolchain[..i])
+}
+
+/// Resolve a constraint to a concrete toolchain (`1.82.0`, `nightly-2026-10-01`)
+pub fn resolve_with_server(constraint: &VersionConstraint, server: &str) -> Result<String> {
+    match constraint {
+        VersionConstraint::Exact(toolchain) => Ok(toolchain.clone()),
+        VersionConstraint::Keyword(channel) if channel == "stable" => release(server, "stable"),
+        VersionConstraint::Keyword(channel) if channel == "beta" || channel == "nightly" => {
+            dated(server, channel)
+        }
+        VersionConstraint::Keyword(other) => bail!("'{other}' is not a Rust channel"),
+        VersionConstraint::LatestMinus(n) => {
+            let stable = stable_version(server)?;
+            let minor = stable.part(1).checked_sub(*n as u64).ok_or_else(|| anyhow!("No Rust release latest-{n}"))?;
+            release(server, &format!("{}.{minor}", stable.part(0)))
+        }
+        VersionConstraint::Range(_) => newest_matching(constraint, server),
+    }
+}
+
+/// Walk minor releases down from stable until one satisfies the range
+fn newest_matching(constraint: &VersionConstraint, server: &str) -> Result<String> {
+    let stable = stable_version(server)?;
+    let major = stable.part(0);
+
+    for minor in (0..=stable.part(1)).rev() {
+        let line_start = Version { raw: String::new(), parts: vec![major, minor, 0], suffix: None };
+        let line_end = Version { raw: String::new(), parts: vec![major, minor, u64::MAX], suffix: None };
+        if !constraint.matches(&line_start) && !constraint.matches(&line_end) {
+            continue;
+        }
+
+        let toolchain = release(server, &format!("{major}.{minor}"))?;
+        if Version::parse(&toolchain).is_some_and(|v| constraint.matches(&v)) {
+            return Ok(toolchain);
+        }
+    }
+
+    bail!("No Rust release matches the requested version")
+}
+
+fn stable_version(server: &str) -> Result<Version> {
+    let stable = release(server, "stable")?;
+    Version::parse(&stable).ok_or_else(|| anyhow!("Unexpected stable version '{stable}'"))
+}
+
+/// Release version a channel points to (`1.82.0`)
+fn release(server: &str, channel: &str) -> Result<String> {
+    let manifest = fetch_manifest(server, channel)?;
+    manifest
+        .pkg
+        .rust
+        .version
+        .split_whitespace()
+        .next()
+        .map(String::from)
+        .ok_or_else(|| anyhow!("Channel manifest for {channel} has no rust version"))
+}
+
+/// Dated toolchain for a moving channel (`nightly-2026-10-01`)
+fn dated(server: &str, channel: &str) -> Result<String> {
+    let manifest = fetch_manifest(server, channel)?;
+    Ok(format!("{channel}-{}", manifest.date))
+}
+
+fn fetch_manifest(server: &str, channel: &str) -> Result<ChannelManifest> {
+    let url = format!("{}/dist/channel-rust-{channel}.toml", server.trim_end_matches('/'));
+    let contents = download_text(&url)?;
+    toml::from_str(&contents).with_context(|| format!("Failed to parse channel manifest {url}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_host() {
+        let cases = [
+            ("1.82.0-x86_64-unknown-linux-gnu", "1.82.0"),
+            ("nightly-2026-10-01-aarch64-apple-darwin", "nightly-2026-10-01"),
+            ("stable-armv7-unknown-linux-gnueabihf", "stable"),
+            ("my-toolchain", "my-toolchain"),
+        ];
+
+        for (toolchain, expected) in cases {
+            assert_eq!(strip_host(toolchain), expected);
+        }
+    }
+}
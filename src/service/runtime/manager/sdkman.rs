@@ -0,0 +1,295 @@
+//! SDKMAN, the default manager for JVM languages
+//!
+//! `sdk` is a shell function rather than a binary, so every command runs
+//! in a bash that sources the SDKMAN init script first.
+//!
+//! Java is published by many vendors under identifiers such as
+//! `21.0.5-tem`, so Java constraints are resolved within one distribution.
+
+use super::{select_remote, RuntimeManager};
+use crate::common::error::Result;
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::path::home_dir;
+use crate::common::command::run_command_output;
+use crate::domain::{Version, VersionConstraint};
+use anyhow::{anyhow, Context};
+use std::fs;
+use std::path::PathBuf;
+
+pub(super) struct Sdkman;
+
+/// Java distributions by config name, with the SDKMAN vendor code that ends
+/// their identifiers
+pub const JAVA_DISTRIBUTIONS: [(&str, &str); 13] = [
+    ("temurin", "tem"),
+    ("zulu", "zulu"),
+    ("graal", "graal"),
+    ("graalce", "graalce"),
+    ("corretto", "amzn"),
+    ("liberica", "librca"),
+    ("microsoft", "ms"),
+    ("oracle", "oracle"),
+    ("semeru", "sem"),
+    ("sapmachine", "sapmchn"),
+    ("dragonwell", "albba"),
+    ("kona", "kona"),
+    ("mandrel", "mandrel"),
+];
+
+/// Distribution used for Java when config doesn't name one
+pub const DEFAULT_JAVA_DISTRIBUTION: &str = "temurin";
+
+/// SDKMAN vendor code of a Java distribution
+#[must_use]
+pub fn java_vendor(distribution: &str) -> Option<&'static str> {
+    JAVA_DISTRIBUTIONS
+        .iter()
+        .find(|(name, _)| *name == distribution)
+        .map(|(_, vendor)| *vendor)
+}
+
+/// A row of the `sdk list java` table
+#[derive(Debug, PartialEq, Eq)]
+struct JavaRelease {
+    version: String,
+    vendor: String,
+    identifier: String,
+}
+
+impl RuntimeManager for Sdkman {
+    fn name(&self) -> &'static str {
+        "sdkman"
+    }
+
+    /// `sdk list` shows the candidates published for this machine's platform
+    fn lists_host_builds(&self) -> bool {
+        true
+    }
+
+    /// `rcupdate=false` keeps the installer away from shell profiles
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new(
+            "https://get.sdkman.io?rcupdate=false",
+            "bash",
+            sdkman_dir()?.join("bin/sdkman-init.sh"),
+        ))
+    }
+
+    /// List versions (or Java identifiers) of a candidate
+    fn list_remote(&self, runtime: &str) -> Result<Vec<String>> {
+        Ok(parse_sdk_list(&sdk(&format!("list {runtime}"))?))
+    }
+
+    /// Java resolves within the default distribution; other candidates
+    /// match their plain version list
+    fn resolve(&self, runtime: &str, constraint: &VersionConstraint) -> Result<String> {
+        match constraint {
+            _ if runtime == "java" => self.resolve_distribution(runtime, constraint, DEFAULT_JAVA_DISTRIBUTION),
+            VersionConstraint::Keyword(keyword) => Ok(keyword.clone()),
+            _ => select_remote(self, runtime, constraint),
+        }
+    }
+
+    fn resolve_distribution(&self, runtime: &str, constraint: &VersionConstraint, distribution: &str) -> Result<String> {
+        let vendor = java_vendor(distribution)
+            .filter(|_| runtime == "java")
+            .ok_or_else(|| anyhow!("SDKMAN has no '{distribution}' distribution of {runtime}"))?;
+        let releases = parse_java_list(&sdk("list java")?);
+        select_java(&releases, vendor, constraint)
+            .ok_or_else(|| anyhow!("No {distribution} build of java matches"))
+    }
+
+    /// Installed versions are directories under `candidates/<runtime>`
+    fn list_installed(&self, runtime: &str) -> Result<Vec<String>> {
+        let dir = sdkman_dir()?.join("candidates").join(runtime);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        Ok(entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name != "current")
+            .collect())
+    }
+
+    fn install(&self, runtime: &str, version: &str) -> Result<()> {
+        sdk(&format!("install {runtime} {version}")).map(drop)
+    }
+
+    fn uninstall(&self, runtime: &str, version: &str) -> Result<()> {
+        sdk(&format!("uninstall {runtime} {version}")).map(drop)
+    }
+
+    /// `candidates/<runtime>/current` links to the default version
+    fn current_default(&self, runtime: &str) -> Result<Option<String>> {
+        let current = sdkman_dir()?.join("candidates").join(runtime).join("current");
+        Ok(fs::read_link(current)
+            .ok()
+            .and_then(|target| target.file_name()?.to_str().map(String::from)))
+    }
+
+    fn set_default(&self, runtime: &str, version: &str) -> Result<()> {
+        sdk(&format!("default {runtime} {version}")).map(drop)
+    }
+
+    fn env_activation(&self, _shell: &str) -> String {
+        "source \"$HOME/.sdkman/bin/sdkman-init.sh\"".to_string()
+    }
+}
+
+fn sdkman_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".sdkman"))
+}
+
+/// Run an `sdk` subcommand without interactive prompts
+fn sdk(args: &str) -> Result<String> {
+    run_command_output(
+        "bash",
+        &[
+            "-c",
+            &format!("export sdkman_auto_answer=true; source ~/.sdkman/bin/sdkman-init.sh && sdk {args}"),
+        ],
+    )
+}
+
+/// Parse the `sdk list java` table
+///
+/// Rows are `Vendor | Use | Version | Dist | Status | Identifier`; the
+/// vendor is only printed on its first row, so the Dist column is used.
+/// Headers, separators and local-only installs without a version are skipped.
+fn parse_java_list(output: &str) -> Vec<JavaRelease> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+            let [_, _, version, vendor, _, identifier] = columns.as_slice() else {
+                return None;
+            };
+            Version::parse(version).map(|_| JavaRelease {
+                version: (*version).to_string(),
+                vendor: (*vendor).to_string(),
+                identifier: (*identifier).to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Newest identifier of a vendor matching the constraint
+///
+/// Only plain releases are candidates, so `JavaFX` (`.fx`), `CRaC` (`.crac`)
+/// and early-access builds are never picked unless named exactly.
+fn select_java(releases: &[JavaRelease], vendor: &str, constraint: &VersionConstraint) -> Option<String> {
+    let releases: Vec<&JavaRelease> = releases.iter().filter(|r| r.vendor == vendor).collect();
+    if let VersionConstraint::Exact(raw) = constraint {
+        return Some(exact_identifier(&releases, raw));
+    }
+
+    let plain = releases
+        .iter()
+        .filter(|r| Version::parse(&r.version).is_some_and(|v| v.suffix.is_none() && allowed_major(&v, constraint)))
+        .map(|r| r.version.as_str());
+    let version = match constraint {
+        VersionConstraint::Keyword(_) => VersionConstraint::LatestMinus(0).select(plain, 1)?,
+        _ => constraint.select(plain, 1)?,
+    };
+    releases
+        .iter()
+        .find(|r| r.version == version)
+        .map(|r| r.identifier.clone())
+}
+
+/// Identifier for an exact version, which may already be an identifier
+fn exact_identifier(releases: &[&JavaRelease], raw: &str) -> String {
+    releases
+        .iter()
+        .find(|r| r.version == raw)
+        .map_or_else(|| raw.to_string(), |r| r.identifier.clone())
+}
+
+/// `lts` only considers long-term support releases (8, 11, 17, 21, 25, ...)
+fn allowed_major(version: &Version, constraint: &VersionConstraint) -> bool {
+    let major = version.part(0);
+    !matches!(constraint, VersionConstraint::Keyword(k) if k == "lts")
+        || major == 8
+        || major == 11
+        || (major >= 17 && (major - 17) % 4 == 0)
+}
+
+/// Extract versions from `sdk list` output
+///
+/// Java prints a table whose last column is the identifier; other
+/// candidates print a grid of versions decorated with `>`, `*` and `+`.
+fn parse_sdk_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .flat_map(|line| {
+            if line.contains('|') {
+                line.rsplit('|').next().map(str::trim).into_iter().collect::<Vec<_>>()
+            } else {
+                line.split_whitespace().collect()
+            }
+        })
+        .filter(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sdk_list() {
+        let java = " Temurin       |     | 21.0.2       | tem     |            | 21.0.2-tem\n \
+                                  | >>> | 17.0.10      | tem     | installed  | 17.0.10-tem\n";
+        assert_eq!(parse_sdk_list(java), ["21.0.2-tem", "17.0.10-tem"]);
+
+        let kotlin = "     2.0.20             1.9.24          > * 1.9.22\n     2.0.10\n";
+        assert_eq!(parse_sdk_list(kotlin), ["2.0.20", "1.9.24", "1.9.22", "2.0.10"]);
+    }
+
+    const JAVA_LIST: &str = include_str!("../../../../tests/fixtures/sdkman/list-java.txt");
+
+    #[test]
+    fn test_parse_java_list() {
+        let releases = parse_java_list(JAVA_LIST);
+
+        assert_eq!(releases.len(), 29);
+        assert_eq!(
+            releases[0],
+            JavaRelease {
+                version: "23.0.1".into(),
+                vendor: "amzn".into(),
+                identifier: "23.0.1-amzn".into(),
+            }
+        );
+        assert!(releases.iter().any(|r| r.identifier == "17.0.13-tem"));
+        assert!(!releases.iter().any(|r| r.identifier == "Identifier" || r.identifier == "17.0.9-custom"));
+    }
+
+    #[test]
+    fn test_select_java() {
+        let releases = parse_java_list(JAVA_LIST);
+        let cases = [
+            ("tem", "17", Some("17.0.13-tem")),
+            ("tem", "21", Some("21.0.5-tem")),
+            ("tem", "latest", Some("23.0.1-tem")),
+            ("tem", "lts", Some("21.0.5-tem")),
+            ("tem", ">=17", Some("23.0.1-tem")),
+            ("tem", "^17", Some("17.0.13-tem")),
+            ("tem", "21.0.4", Some("21.0.4-tem")),
+            ("zulu", "23", Some("23.0.1-zulu")),
+            ("amzn", "8", Some("8.0.432-amzn")),
+            ("graal", "17", Some("17.0.12-graal")),
+            ("librca", "11", None),
+            ("tem", "22.0.2-oracle", Some("22.0.2-oracle")),
+        ];
+
+        for (vendor, constraint, expected) in cases {
+            let parsed = VersionConstraint::parse(constraint).unwrap();
+            assert_eq!(select_java(&releases, vendor, &parsed).as_deref(), expected, "{vendor} {constraint}");
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! zvm, the Zig version manager
+
+use super::{home_path, output_lines, RuntimeManager};
+use crate::service::runtime::bootstrap::InstallerScript;
+use crate::common::error::Result;
+use crate::common::run_command;
+
+pub(super) struct Zvm;
+
+impl RuntimeManager for Zvm {
+    fn name(&self) -> &'static str {
+        "zvm"
+    }
+
+    fn installer(&self) -> Result<InstallerScript> {
+        Ok(InstallerScript::new("https://www.zvm.app/install.sh", "bash", home_path(".zvm/self/zvm")?))
+    }
+
+    fn list_remote(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(versions(&output_lines("zvm", &["ls", "--all"])?))
+    }
+
+    fn list_installed(&self, _runtime: &str) -> Result<Vec<String>> {
+        Ok(versions(&output_lines("zvm", &["ls"])?))
+    }
+
+    fn install(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("zvm", &["install", version])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("zvm", &["uninstall", version])?;
+        Ok(())
+    }
+
+    /// `zvm ls` marks the version in use with `[x]`
+    fn current_default(&self, _runtime: &str) -> Result<Option<String>> {
+        let lines: Vec<String> = output_lines("zvm", &["ls"])?
+            .into_iter()
+            .filter(|line| line.contains("[x]"))
+            .collect();
+        Ok(versions(&lines).into_iter().next())
+    }
+
+    fn set_default(&self, _runtime: &str, version: &str) -> Result<()> {
+        run_command("zvm", &["use", version])?;
+        Ok(())
+    }
+
+    fn env_activation(&self, _shell: &str) -> String {
+        "export PATH=\"$HOME/.zvm/bin:$HOME/.zvm/self:$PATH\"".to_string()
+    }
+}
+
+/// Version numbers in `zvm ls` output, ignoring colours, markers and `master`
+fn versions(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| strip_ansi(line))
+        .flat_map(|line| line.split_whitespace().map(String::from).collect::<Vec<_>>())
+        .filter(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+        .collect()
+}
+
+/// Remove terminal colour sequences such as `\x1b[32m`
+fn strip_ansi(line: &str) -> String {
+    let mut plain = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions() {
+        let lines = ["\x1b[32m0.13.0\x1b[0m [x]", "0.12.1", "master"].map(String::from);
+
+        assert_eq!(versions(&lines), ["0.13.0", "0.12.1"]);
+    }
+}
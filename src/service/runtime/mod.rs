@@ -1,15 +1,20 @@
 //! Runtime and version manager service interfaces
 
+pub mod bootstrap;
+pub mod corepack;
 pub mod framework_install;
+pub mod global_packages;
+pub mod manager;
 pub mod resolution;
 pub mod resolver;
-pub mod runtime_install;
 pub mod setup;
 pub mod system_lang;
 
+pub use bootstrap::{bootstrap_manager, Bootstrapped, InstallerScript};
 pub use framework_install::install_framework;
+pub use global_packages::{requirement_accepts, Ecosystem, GlobalPackage, GLOBAL_PACKAGE_RUNTIMES};
+pub use manager::{get_manager, require_manager, same_version, RuntimeManager};
 pub use resolution::resolve_runtime_version;
 pub use resolver::VersionResolver;
-pub use runtime_install::{install_runtime_version, set_default_runtime};
-pub use setup::{get_required_managers, install_manager, is_manager_installed};
+pub use setup::{configured_manager, get_required_managers, manager_name_for};
 pub use system_lang::install_system_languages;
@@ -4,26 +4,21 @@ use crate::domain::Lockfile;
 use crate::common::error::Result;
 use super::resolver::VersionResolver;
 
-/// Resolve a runtime version, using lockfile if available
+/// Resolve a runtime version constraint, using the lockfile if available
+///
+/// Newly resolved constraints are recorded alongside their resolved version.
 pub fn resolve_runtime_version(
     name: &str,
     requested: &str,
     manager: &str,
+    distribution: Option<&str>,
     lockfile: &mut Lockfile,
 ) -> Result<String> {
-    if lockfile.needs_resolution(name, requested) {
-        let resolved = VersionResolver::resolve(name, requested, Some(manager))?;
-        lockfile.set_runtime(
-            name.to_string(),
-            requested.to_string(),
-            resolved.clone(),
-            manager.to_string(),
-        );
-        Ok(resolved)
-    } else {
-        Ok(lockfile
-            .get_runtime_version(name)
-            .unwrap_or(requested)
-            .to_string())
+    if let Some(locked) = lockfile.locked_version(name, requested) {
+        return Ok(locked.to_string());
     }
+
+    let resolved = VersionResolver::resolve(name, requested, Some(manager), distribution)?;
+    lockfile.lock_version(name, requested, &resolved, manager);
+    Ok(resolved)
 }
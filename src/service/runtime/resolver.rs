@@ -0,0 +1,53 @@
+//! Version resolution
+//!
+//! Resolves version constraints like "latest", "3.11", "^20" or "latest-1"
+//! to actual versions. Each manager only lists what it can install; the
+//! shared [`VersionConstraint`] picks the best match.
+
+use super::manager::require_manager;
+use crate::common::error::Result;
+use crate::domain::VersionConstraint;
+use anyhow::Context;
+
+/// Version resolver for different runtime managers
+pub struct VersionResolver;
+
+impl VersionResolver {
+    /// Resolve a version constraint to an actual version
+    ///
+    /// With a distribution, even exact versions go through the manager so
+    /// they can be mapped to that distribution's identifier.
+    pub fn resolve(
+        runtime: &str,
+        version: &str,
+        manager: Option<&str>,
+        distribution: Option<&str>,
+    ) -> Result<String> {
+        let constraint = VersionConstraint::parse(version)?;
+        if constraint.is_exact() && distribution.is_none() {
+            return Ok(version.to_string());
+        }
+
+        let manager = require_manager(manager.unwrap_or_else(|| Self::default_manager(runtime)))?;
+        let resolved = match distribution {
+            Some(distribution) => manager.resolve_distribution(runtime, &constraint, distribution),
+            None => manager.resolve(runtime, &constraint),
+        };
+        resolved.with_context(|| format!("Failed to resolve {runtime} '{version}'"))
+    }
+
+    /// Get default manager for a runtime
+    #[must_use]
+    pub fn default_manager(runtime: &str) -> &str {
+        match runtime {
+            "node" | "nodejs" => "fnm",
+            "java" | "kotlin" | "scala" | "groovy" => "sdkman",
+            "rust" => "rustup",
+            "ghc" | "cabal" | "hls" | "stack" => "ghcup",
+            "ocaml" => "opam",
+            "dotnet" => "dotnet-install",
+            "zig" => "zvm",
+            _ => "mise",
+        }
+    }
+}
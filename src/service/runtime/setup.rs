@@ -1,73 +1,43 @@
 //! Version manager installation helpers
 
-use crate::domain::runtime::RuntimeSpec;
-use crate::common::error::Result;
+use super::manager::{require_manager, RuntimeManager};
 use super::resolver::VersionResolver;
-use crate::common::run_command;
-use anyhow::anyhow;
-use colored::Colorize;
+use crate::common::error::Result;
+use crate::domain::runtime::RuntimeSpec;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 
-/// Check if a version manager is installed
-pub fn is_manager_installed(manager: &str) -> bool {
-    which::which(manager).is_ok()
+/// Manager configured for a runtime, falling back to its default
+#[must_use]
+pub fn manager_name_for(runtime: &str, spec: &RuntimeSpec) -> String {
+    spec.get_manager()
+        .unwrap_or_else(|| VersionResolver::default_manager(runtime).to_string())
 }
 
-/// Install a version manager
-pub fn install_manager(manager: &str, dry_run: bool) -> Result<()> {
-    if dry_run {
-        println!("    {} Would install {}", "[DRY-RUN]".yellow(), manager);
-        return Ok(());
-    }
-
-    match manager {
-        "mise" => {
-            run_command("curl", &["https://mise.run", "|", "sh"])?;
-        }
-        "rustup" => {
-            run_command(
-                "curl",
-                &[
-                    "--proto",
-                    "=https",
-                    "--tlsv1.2",
-                    "-sSf",
-                    "https://sh.rustup.rs",
-                    "|",
-                    "sh",
-                    "-s",
-                    "--",
-                    "-y",
-                ],
-            )?;
-        }
-        "fnm" => {
-            run_command(
-                "curl",
-                &["-fsSL", "https://fnm.vercel.app/install", "|", "bash"],
-            )?;
-        }
-        _ => return Err(anyhow!("Don't know how to install manager: {manager}")),
-    }
-
-    Ok(())
+/// Manager for a runtime, as configured or by default when config dropped it
+pub fn configured_manager<S: BuildHasher>(
+    runtimes: &HashMap<String, RuntimeSpec, S>,
+    runtime: &str,
+) -> Result<&'static dyn RuntimeManager> {
+    let name = runtimes.get(runtime).map_or_else(
+        || VersionResolver::default_manager(runtime).to_string(),
+        |spec| manager_name_for(runtime, spec),
+    );
+    require_manager(&name)
 }
 
-/// Get list of required version managers from runtime configuration
-pub fn get_required_managers(
-    runtimes: &HashMap<String, RuntimeSpec>,
-) -> Vec<String> {
-    let mut managers = Vec::new();
+/// Get the version managers required by the runtime configuration
+pub fn get_required_managers<S: BuildHasher>(
+    runtimes: &HashMap<String, RuntimeSpec, S>,
+) -> Result<Vec<&'static dyn RuntimeManager>> {
+    let mut managers: Vec<&'static dyn RuntimeManager> = Vec::new();
 
     for (runtime, spec) in runtimes {
-        let mgr = spec
-            .get_manager()
-            .unwrap_or_else(|| VersionResolver::default_manager(runtime).to_string());
-
-        if !managers.contains(&mgr) {
-            managers.push(mgr);
+        let manager = require_manager(&manager_name_for(runtime, spec))?;
+        if !managers.iter().any(|m| m.name() == manager.name()) {
+            managers.push(manager);
         }
     }
 
-    managers
+    Ok(managers)
 }
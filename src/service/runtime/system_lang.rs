@@ -3,10 +3,12 @@
 use crate::common::error::Result;
 use crate::common::run_command;
 use colored::Colorize;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
 
 /// Install system-provided languages
-pub fn install_system_languages(
-    system_languages: &std::collections::HashMap<String, bool>,
+pub fn install_system_languages<S: BuildHasher>(
+    system_languages: &HashMap<String, bool, S>,
     dry_run: bool,
 ) -> Result<()> {
     if system_languages.is_empty() {
@@ -48,18 +50,25 @@ fn install_system_language(lang: &str, dry_run: bool) -> Result<()> {
     for pkg in packages {
         println!("  {} {}", "↓".cyan(), pkg);
         if !dry_run {
-            // Use system package manager
-            if which::which("apt").is_ok() {
-                run_command("sudo", &["apt", "install", "-y", pkg])?;
-            } else if which::which("brew").is_ok() {
-                run_command("brew", &["install", pkg])?;
-            } else if which::which("dnf").is_ok() {
-                run_command("sudo", &["dnf", "install", "-y", pkg])?;
-            } else if which::which("pacman").is_ok() {
-                run_command("sudo", &["pacman", "-S", "--noconfirm", pkg])?;
-            }
+            install_with_available_manager(pkg)?;
         }
     }
 
     Ok(())
 }
+
+/// Install a package with the first available system package manager
+fn install_with_available_manager(pkg: &str) -> Result<()> {
+    // Use system package manager
+    if which::which("apt").is_ok() {
+        run_command("sudo", &["apt", "install", "-y", pkg])?;
+    } else if which::which("brew").is_ok() {
+        run_command("brew", &["install", pkg])?;
+    } else if which::which("dnf").is_ok() {
+        run_command("sudo", &["dnf", "install", "-y", pkg])?;
+    } else if which::which("pacman").is_ok() {
+        run_command("sudo", &["pacman", "-S", "--noconfirm", pkg])?;
+    }
+
+    Ok(())
+}
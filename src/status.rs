@@ -0,0 +1,41 @@
+//! Status command - how the machine differs from the config
+
+use crate::cli::Cli;
+use crate::init::{initialize_app, load_system_and_config};
+use crate::installation::{load_state, state_path};
+use crate::plan::SyncPlan;
+use colored::Colorize;
+use devstrap::config::{ActiveProfile, StateFile};
+
+/// Run the status command
+///
+/// Explains profile changes since the last sync, then shows the sync plan
+/// without changing anything.
+pub fn run_status(cli: &Cli) {
+    initialize_app(cli);
+
+    let (system_info, config, layers) = load_system_and_config(cli);
+    let state = load_state(&state_path(cli));
+
+    show_profile_changes(&layers.profiles, &state);
+    let plan = SyncPlan::calculate(&config, &state, &system_info);
+    plan.show(false, true);
+}
+
+/// Compare the active profiles with those applied at the last sync
+fn show_profile_changes(active: &[ActiveProfile], state: &StateFile) {
+    let dropped: Vec<&String> =
+        state.profiles.iter().filter(|name| !active.iter().any(|profile| profile.name == **name)).collect();
+    if active.is_empty() && dropped.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "Profiles:".bold().cyan());
+    for profile in active {
+        let note = if state.profiles.contains(&profile.name) { "" } else { " - not applied at the last sync" };
+        println!("  {} {} ({}){}", "✓".green(), profile.name.bold(), profile.reason, note.yellow());
+    }
+    for name in dropped {
+        println!("  {} {} - applied at the last sync, no longer active", "⚠".yellow(), name.bold());
+    }
+}
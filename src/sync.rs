@@ -2,134 +2,202 @@
 
 use crate::cli::Cli;
 use crate::init::{initialize_app, load_system_and_config};
-use crate::installation::{run_installation, run_runtime_installation};
+use crate::installation::{
+    acquire_sync_lock, load_lockfile, load_state, lockfile_path, record_package_versions, run_installation,
+    run_runtime_installation, state_path,
+};
+use crate::plan::SyncPlan;
 use devstrap::common::confirm;
 use devstrap::config::StateFile;
-use devstrap::domain::{Config, SystemInfo};
-use devstrap::usecase::Installer;
+use devstrap::domain::{Config, Lockfile, SystemInfo};
+use devstrap::usecase::{
+    package_lock_mismatches, preferred_method, refresh_expired_versions, prune_corepack, prune_global_packages, prune_repositories, prune_taps,
+    prune_toolchain_extras, required_corepack, required_global_packages, required_repositories,
+    required_taps, required_toolchain_extras, Installer, VersionRefresh,
+};
 use colored::Colorize;
 use std::path::Path;
 use std::process;
 
+/// How sync treats the package versions in `devstrap.lock`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Install the newest versions and lock packages that aren't locked yet
+    Update,
+    /// Install the locked versions (`--locked`)
+    Locked,
+    /// Install the locked versions and leave the lockfile untouched (`--frozen`)
+    Frozen,
+}
+
+impl LockMode {
+    /// Mode selected by the `--locked` and `--frozen` flags
+    pub fn from_flags(locked: bool, frozen: bool) -> Self {
+        if frozen {
+            Self::Frozen
+        } else if locked {
+            Self::Locked
+        } else {
+            Self::Update
+        }
+    }
+}
+
+/// Options of the sync command
+pub struct SyncOptions {
+    /// Remove what config no longer asks for
+    pub prune: bool,
+    /// Re-resolve every locked version
+    pub refresh: bool,
+    /// How locked package versions are used
+    pub lock_mode: LockMode,
+}
+
 /// Run the sync command
-pub fn run_sync(cli: &Cli, prune: bool, refresh: bool) {
+pub fn run_sync(cli: &Cli, options: &SyncOptions) {
     initialize_app(cli);
 
-    let (system_info, config, _project_root) = load_system_and_config(cli);
-    let state_path = get_state_path(cli);
-    let mut state = StateFile::from_file(&state_path).unwrap_or_default();
+    let (system_info, config, layers) = load_system_and_config(cli);
+    let _sync_lock = acquire_sync_lock(cli);
+    let state_path = state_path(cli);
+    let mut state = load_state(&state_path);
+    let mut lockfile = load_lockfile(&lockfile_path(cli), options.refresh);
+    let config_hash = layers.config_hash();
 
     show_dry_run_warning(cli.dry_run);
+    check_lockfile_freshness(options.lock_mode, &lockfile, &config_hash);
+    ensure_lockfile_matches(options.lock_mode, &config, &system_info, &lockfile);
+    let refreshes = refresh_expired_locks(options.lock_mode, &config, &mut lockfile);
 
-    let (to_install, to_remove) = calculate_diff(&config, &state);
+    let plan = SyncPlan::calculate(&config, &state, &system_info).with_version_refreshes(&refreshes);
 
-    if !show_sync_plan(&to_install, &to_remove, prune, cli.dry_run) {
+    let prune = options.prune;
+    if !plan.show(prune, cli.dry_run) {
         return;
     }
 
-    if !confirm_sync(cli, prune, &to_install, &to_remove) {
+    if !confirm_sync(cli, prune, &plan) {
         println!("{}", "Sync cancelled".yellow());
         process::exit(0);
     }
 
-    handle_package_removal(prune, &to_remove, &mut state, cli, &system_info);
+    handle_package_removal(prune, &plan.to_remove, &mut state, cli, &system_info);
+    handle_tap_removal(prune, &config, &mut state, cli, &system_info);
+    handle_repository_removal(prune, &config, &mut state, cli, &system_info);
+    handle_toolchain_removal(prune, &config, &mut state, cli);
+    handle_global_package_removal(prune, &config, &mut state, cli);
+    handle_corepack_removal(prune, &config, &mut state, cli);
 
-    let installer = Installer::new(config.clone(), system_info.clone(), cli.dry_run);
+    let installer = create_installer(&config, &system_info, &lockfile, options.lock_mode, cli.dry_run);
     run_installation(&installer, &config);
+    ensure_locked_installs(options.lock_mode, &installer);
 
-    update_state_for_installed(&to_install, &mut state, &system_info, cli.dry_run);
-    run_runtime_installation(&config, cli, refresh);
+    update_state_for_installed(&plan.to_install, &mut state, &system_info, &config, cli.dry_run);
+    record_added_taps(&installer, &mut state);
+    record_added_repositories(&installer, &mut state);
+
+    let frozen = options.lock_mode == LockMode::Frozen;
+    if !frozen {
+        lockfile.config_hash = Some(config_hash);
+        record_package_versions(&config, &system_info, &mut lockfile, cli.dry_run);
+    }
+    run_runtime_installation(&config, cli, lockfile, &[], !frozen && !cli.dry_run, &mut state);
 
+    state.profiles = layers.profiles.iter().map(|profile| profile.name.clone()).collect();
     save_state(&state, &state_path, cli.dry_run);
     show_completion();
 }
 
-fn get_state_path(cli: &Cli) -> std::path::PathBuf {
-    cli.config
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .join("devstrap.state")
+/// Re-resolve floating runtime versions past `refresh_after`, unless versions are pinned by the lock flags
+fn refresh_expired_locks(lock_mode: LockMode, config: &Config, lockfile: &mut Lockfile) -> Vec<VersionRefresh> {
+    if lock_mode != LockMode::Update {
+        return Vec::new();
+    }
+    refresh_expired_versions(config, lockfile, chrono::Utc::now())
 }
 
-fn show_dry_run_warning(dry_run: bool) {
-    if dry_run {
-        println!(
-            "\n{} {}",
-            "⚠".yellow().bold(),
-            "DRY RUN MODE - No changes will be made".yellow().bold()
-        );
+/// Warn about a stale lockfile, or exit under `--frozen`
+fn check_lockfile_freshness(lock_mode: LockMode, lockfile: &Lockfile, config_hash: &str) {
+    let Some(reason) = lockfile.staleness(config_hash) else {
+        return;
+    };
+
+    if lock_mode == LockMode::Frozen {
+        eprintln!("\n{} devstrap.lock is stale: {}", "✗".red(), reason);
+        eprintln!("Run {} to update the lockfile", "devstrap sync".bold());
+        process::exit(1);
     }
+    println!(
+        "\n{} devstrap.lock is stale: {}; this sync will update it",
+        "⚠".yellow(),
+        reason
+    );
 }
 
-fn calculate_diff(config: &Config, state: &StateFile) -> (Vec<String>, Vec<String>) {
-    let desired_packages = config.get_all_packages();
-    let current_packages = state.get_package_ids();
-
-    let to_install: Vec<String> = desired_packages
-        .iter()
-        .filter(|pkg| !state.has_package(pkg))
-        .cloned()
-        .collect();
+/// Exit when `--frozen` finds the lockfile out of step with the config
+fn ensure_lockfile_matches(lock_mode: LockMode, config: &Config, system_info: &SystemInfo, lockfile: &Lockfile) {
+    if lock_mode != LockMode::Frozen {
+        return;
+    }
 
-    let to_remove: Vec<String> = current_packages
-        .iter()
-        .filter(|pkg| !desired_packages.contains(pkg))
-        .cloned()
-        .collect();
+    let mismatches = package_lock_mismatches(config, system_info, lockfile);
+    if mismatches.is_empty() {
+        return;
+    }
 
-    (to_install, to_remove)
+    eprintln!("\n{} devstrap.lock does not match the config:", "✗".red());
+    for mismatch in &mismatches {
+        eprintln!("  {} {}", "✗".red(), mismatch);
+    }
+    eprintln!("Run {} to update the lockfile", "devstrap sync".bold());
+    process::exit(1);
 }
 
-/// Returns true if sync should continue, false if everything is in sync
-fn show_sync_plan(
-    to_install: &[String],
-    to_remove: &[String],
-    prune: bool,
+/// Installer that honours locked package versions under `--locked` and `--frozen`
+fn create_installer(
+    config: &Config,
+    system_info: &SystemInfo,
+    lockfile: &Lockfile,
+    lock_mode: LockMode,
     dry_run: bool,
-) -> bool {
-    if !to_install.is_empty() || (prune && !to_remove.is_empty()) {
-        println!("\n{}", "Sync Plan:".bold().cyan());
-
-        if !to_install.is_empty() {
-            println!("  {} To install:", "✓".green());
-            for pkg in to_install {
-                println!("    • {}", pkg.green());
-            }
-        }
-
-        if prune && !to_remove.is_empty() {
-            println!("  {} To remove:", "✗".red());
-            for pkg in to_remove {
-                println!("    • {}", pkg.red());
-            }
-        } else if !to_remove.is_empty() {
-            println!(
-                "  {} Packages not in config (use --prune to remove):",
-                "⚠".yellow()
-            );
-            for pkg in to_remove {
-                println!("    • {}", pkg.yellow());
-            }
-        }
-        println!();
-        true
+) -> Installer {
+    let installer = Installer::new(config.clone(), system_info.clone(), dry_run);
+    if lock_mode == LockMode::Update {
+        installer
     } else {
-        println!("\n{} Everything in sync!", "✓".green().bold());
-        !dry_run
+        installer.with_lockfile(lockfile.clone())
     }
 }
 
-fn confirm_sync(
-    cli: &Cli,
-    prune: bool,
-    to_install: &[String],
-    to_remove: &[String],
-) -> bool {
+/// Exit when `--frozen` couldn't install every locked version
+fn ensure_locked_installs(lock_mode: LockMode, installer: &Installer) {
+    let failed = installer.failed_packages();
+    if lock_mode == LockMode::Frozen && !failed.is_empty() {
+        eprintln!(
+            "{} Could not install the locked versions of: {}",
+            "✗".red(),
+            failed.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+fn show_dry_run_warning(dry_run: bool) {
+    if dry_run {
+        println!(
+            "\n{} {}",
+            "⚠".yellow().bold(),
+            "DRY RUN MODE - No changes will be made".yellow().bold()
+        );
+    }
+}
+
+fn confirm_sync(cli: &Cli, prune: bool, plan: &SyncPlan) -> bool {
     if cli.dry_run || cli.yes {
         return true;
     }
 
-    if !to_install.is_empty() || (prune && !to_remove.is_empty()) {
+    if plan.has_changes(prune) {
         confirm("Proceed with sync?")
     } else {
         true
@@ -161,19 +229,86 @@ fn handle_package_removal(
                 method_str
             );
 
-            if !cli.dry_run {
-                attempt_uninstall(pkg_id, &method_str, system_info, state);
-            } else {
+            if cli.dry_run {
                 println!("    {} Would remove {}", "[DRY-RUN]".yellow(), pkg_id);
+            } else {
+                attempt_uninstall(pkg_id, &method_str, system_info, state);
             }
         }
     }
 }
 
+fn handle_tap_removal(
+    prune: bool,
+    config: &Config,
+    state: &mut StateFile,
+    cli: &Cli,
+    system_info: &SystemInfo,
+) {
+    if !prune {
+        return;
+    }
+
+    let required = required_taps(config, system_info);
+    if let Err(e) = prune_taps(state, &required, cli.dry_run) {
+        eprintln!("    {} Failed to prune taps: {}", "✗".red(), e);
+    }
+}
+
+fn handle_toolchain_removal(prune: bool, config: &Config, state: &mut StateFile, cli: &Cli) {
+    if !prune {
+        return;
+    }
+
+    let required = required_toolchain_extras(config);
+    if let Err(e) = prune_toolchain_extras(state, &required, cli.dry_run) {
+        eprintln!("    {} Failed to prune rust components: {}", "✗".red(), e);
+    }
+}
+
+fn handle_global_package_removal(prune: bool, config: &Config, state: &mut StateFile, cli: &Cli) {
+    if !prune {
+        return;
+    }
+
+    let required = required_global_packages(config);
+    if let Err(e) = prune_global_packages(config, state, &required, cli.dry_run) {
+        eprintln!("    {} Failed to prune global packages: {}", "✗".red(), e);
+    }
+}
+
+fn handle_corepack_removal(prune: bool, config: &Config, state: &mut StateFile, cli: &Cli) {
+    if !prune {
+        return;
+    }
+
+    let required = required_corepack(config);
+    if let Err(e) = prune_corepack(config, state, &required, cli.dry_run) {
+        eprintln!("    {} Failed to prune corepack: {}", "✗".red(), e);
+    }
+}
+
+fn handle_repository_removal(
+    prune: bool,
+    config: &Config,
+    state: &mut StateFile,
+    cli: &Cli,
+    system_info: &SystemInfo,
+) {
+    if !prune {
+        return;
+    }
+
+    let required = required_repositories(config, system_info);
+    if let Err(e) = prune_repositories(state, &required, cli.dry_run) {
+        eprintln!("    {} Failed to prune repositories: {}", "✗".red(), e);
+    }
+}
+
 fn attempt_uninstall(pkg_id: &str, method_str: &str, system_info: &SystemInfo, state: &mut StateFile) {
     let method = devstrap::domain::InstallMethod::from_string(&method_str.to_lowercase(), system_info);
     if let Some(method) = method {
-        if let Err(e) = devstrap::common::uninstall_package(pkg_id, &method) {
+        if let Err(e) = devstrap::common::uninstall_builtin_package(pkg_id, &method) {
             eprintln!("    {} Failed to uninstall {}: {}", "✗".red(), pkg_id, e);
         } else {
             state.remove_package(pkg_id);
@@ -185,6 +320,7 @@ fn update_state_for_installed(
     to_install: &[String],
     state: &mut StateFile,
     system_info: &SystemInfo,
+    config: &Config,
     dry_run: bool,
 ) {
     if dry_run {
@@ -192,14 +328,28 @@ fn update_state_for_installed(
     }
 
     for pkg_id in to_install {
-        let method = system_info
-            .default_package_manager
-            .map(devstrap::domain::InstallMethod::SystemDefault)
-            .unwrap_or(devstrap::domain::InstallMethod::System);
+        let method = preferred_method(pkg_id, system_info, &config.settings).unwrap_or_else(|| {
+            system_info.default_package_manager.map_or(
+                devstrap::domain::InstallMethod::System,
+                devstrap::domain::InstallMethod::SystemDefault,
+            )
+        });
         state.add_package(pkg_id.clone(), &method, None);
     }
 }
 
+fn record_added_taps(installer: &Installer, state: &mut StateFile) {
+    for tap in installer.added_taps() {
+        state.add_tap(tap);
+    }
+}
+
+fn record_added_repositories(installer: &Installer, state: &mut StateFile) {
+    for (name, repository) in installer.added_repositories() {
+        state.add_repository(name, repository);
+    }
+}
+
 fn save_state(state: &StateFile, state_path: &Path, dry_run: bool) {
     if dry_run {
         return;
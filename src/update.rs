@@ -0,0 +1,177 @@
+//! Update command - move locked versions forward
+
+use crate::cli::Cli;
+use crate::init::{initialize_app, load_system_and_config};
+use crate::installation::{
+    acquire_sync_lock, load_state, lockfile_path, read_lockfile, run_runtime_installation, state_path,
+};
+use devstrap::common::confirm;
+use devstrap::config::StateFile;
+use devstrap::domain::{parse_platform, Config, Lockfile};
+use devstrap::usecase::{
+    apply_package_updates, lock_package_updates, lock_runtime_updates, plan_platform_updates, plan_updates,
+    report_errors, runtime_update_config, unknown_update_names, updated_runtimes, LockUpdate, VersionChange,
+};
+use colored::{ColoredString, Colorize};
+use std::process;
+
+/// Run the update command
+pub fn run_update(cli: &Cli, names: &[String]) {
+    initialize_app(cli);
+
+    let (system_info, config, _layers) = load_system_and_config(cli);
+    ensure_known_names(&config, names);
+
+    let _sync_lock = acquire_sync_lock(cli);
+    let lockfile_path = lockfile_path(cli);
+    let mut lockfile = read_lockfile(&lockfile_path);
+
+    println!("\n{} Checking for newer versions...", "↻".cyan());
+    let updates = plan_updates(&config, &system_info, &lockfile, names);
+    show_updates(&updates);
+
+    if !updates.iter().any(LockUpdate::is_change) {
+        println!("\n{} Everything is up to date", "✓".green());
+        return;
+    }
+    if cli.dry_run {
+        println!("\n{} No changes made", "[DRY-RUN]".yellow());
+        return;
+    }
+    if !cli.yes && !confirm("Install these versions?") {
+        println!("{}", "Update cancelled".yellow());
+        process::exit(0);
+    }
+
+    let state_path = state_path(cli);
+    let mut state = load_state(&state_path);
+
+    let errors = apply_package_updates(&config, &system_info, &updates, &mut lockfile, &mut state, cli.dry_run);
+    report_errors(errors);
+
+    install_runtime_updates(&config, cli, lockfile, &updates, &mut state);
+
+    if let Err(e) = state.save(&state_path) {
+        eprintln!("{} Failed to save state file: {}", "✗".red(), e);
+    }
+    println!("\n{}", "✓ devstrap update complete!".green().bold());
+}
+
+/// Run the update command for other platforms
+///
+/// Only each platform's lockfile section is written; nothing is installed.
+pub fn run_platform_update(cli: &Cli, names: &[String], platforms: &[String]) {
+    initialize_app(cli);
+
+    let (_system_info, config, _layers) = load_system_and_config(cli);
+    ensure_known_names(&config, names);
+
+    let _sync_lock = acquire_sync_lock(cli);
+    let lockfile_path = lockfile_path(cli);
+    let mut lockfile = read_lockfile(&lockfile_path);
+
+    let planned: Vec<_> = platforms
+        .iter()
+        .map(|key| (key, plan_for_platform(&config, &lockfile.clone().for_platform(key), key, names)))
+        .collect();
+
+    if !planned.iter().flat_map(|(_, updates)| updates).any(LockUpdate::is_change) {
+        println!("\n{} Everything is up to date", "✓".green());
+        return;
+    }
+    if cli.dry_run {
+        println!("\n{} No changes made", "[DRY-RUN]".yellow());
+        return;
+    }
+    if !cli.yes && !confirm("Lock these versions?") {
+        println!("{}", "Update cancelled".yellow());
+        process::exit(0);
+    }
+
+    for (key, updates) in &planned {
+        lockfile = lockfile.for_platform(key);
+        lock_runtime_updates(updates, &mut lockfile);
+        lock_package_updates(updates, &mut lockfile);
+    }
+    if let Err(e) = lockfile.save(&lockfile_path) {
+        eprintln!("{} Failed to save lockfile: {}", "✗".red(), e);
+        process::exit(1);
+    }
+    println!("\n{}", "✓ devstrap update complete!".green().bold());
+}
+
+/// Plan and print one platform's updates, exiting on an unknown platform
+fn plan_for_platform(config: &Config, lockfile: &Lockfile, key: &str, names: &[String]) -> Vec<LockUpdate> {
+    let Some((os, arch)) = parse_platform(key) else {
+        eprintln!("{} Unknown platform {key}; expected OS-ARCH like linux-x86_64", "✗".red());
+        process::exit(1);
+    };
+    println!("\n{} Checking for newer versions for {}...", "↻".cyan(), key.bold());
+    let updates = plan_platform_updates(config, os, arch, lockfile, names);
+    show_updates(&updates);
+    updates
+}
+
+/// Exit when a name matches neither a configured package nor runtime
+fn ensure_known_names(config: &Config, names: &[String]) {
+    let unknown = unknown_update_names(config, names);
+    if !unknown.is_empty() {
+        eprintln!(
+            "{} Not a configured package or runtime: {}",
+            "✗".red(),
+            unknown.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+/// Print the old → new table
+fn show_updates(updates: &[LockUpdate]) {
+    let width = updates.iter().map(|u| u.label().len()).max().unwrap_or(0);
+    let current_width = updates
+        .iter()
+        .map(|u| u.current.as_deref().unwrap_or("-").len())
+        .max()
+        .unwrap_or(0);
+
+    println!();
+    for update in updates {
+        let current = update.current.as_deref().unwrap_or("-");
+        println!(
+            "  {}  {:<current_width$}  →  {}  {}",
+            format!("{:<width$}", update.label()).bold(),
+            current,
+            update.latest,
+            change_marker(update.change()),
+        );
+    }
+}
+
+/// Colored marker for a version change
+fn change_marker(change: VersionChange) -> ColoredString {
+    match change {
+        VersionChange::New => "new".cyan(),
+        VersionChange::Upgrade => "upgrade".green(),
+        VersionChange::Downgrade => "downgrade".red(),
+        VersionChange::Unchanged => "unchanged".dimmed(),
+        VersionChange::Changed => "changed".yellow(),
+    }
+}
+
+/// Install the runtimes whose locks moved and save the lockfile
+///
+/// Only the changed runtimes go through the coordinator, which locks each new
+/// version once it installs, so every other lockfile entry is written back as
+/// it was read.
+fn install_runtime_updates(config: &Config, cli: &Cli, lockfile: Lockfile, updates: &[LockUpdate], state: &mut StateFile) {
+    let runtimes = updated_runtimes(updates);
+    if runtimes.is_empty() {
+        if let Err(e) = lockfile.save(lockfile_path(cli)) {
+            eprintln!("{} Failed to save lockfile: {}", "✗".red(), e);
+        }
+        return;
+    }
+
+    let config = runtime_update_config(config, &runtimes);
+    run_runtime_installation(&config, cli, lockfile, updates, true, state);
+}
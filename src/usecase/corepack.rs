@@ -0,0 +1,168 @@
+//! Corepack reconciliation
+//!
+//! Enables Corepack and pins `package_managers` in every version of a Node
+//! runtime, locks the versions they resolved to, and undoes both under
+//! `--prune` once they are dropped from config.
+
+use super::global_packages::difference;
+use crate::common::error::Result;
+use crate::config::{CorepackExtras, StateFile};
+use crate::domain::runtime::RuntimeSpec;
+use crate::domain::{Config, Lockfile};
+use crate::service::runtime::corepack::{
+    disable, enable, install_package_manager, is_enabled, package_manager_version,
+};
+use crate::service::runtime::{configured_manager, requirement_accepts, same_version, RuntimeManager};
+use anyhow::anyhow;
+use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Item standing for the shims themselves among a runtime's Corepack items
+const SHIMS: &str = "corepack";
+
+/// Corepack settings a runtime's config asks for
+#[must_use]
+pub fn corepack_extras(spec: &RuntimeSpec) -> CorepackExtras {
+    CorepackExtras {
+        enabled: spec.uses_corepack(),
+        package_managers: spec
+            .get_package_managers()
+            .map(|pins| pins.keys().cloned().collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Corepack settings requested per runtime in config
+#[must_use]
+pub fn required_corepack(config: &Config) -> BTreeMap<String, CorepackExtras> {
+    config
+        .runtimes
+        .iter()
+        .map(|(name, spec)| (name.clone(), corepack_extras(spec)))
+        .filter(|(_, extras)| extras.enabled)
+        .collect()
+}
+
+/// Corepack shims and package managers in config that devstrap hasn't applied yet
+#[must_use]
+pub fn pending_corepack_items(
+    state: &StateFile,
+    required: &BTreeMap<String, CorepackExtras>,
+) -> Vec<(String, String)> {
+    difference(&items(required), &items(&state.corepack))
+}
+
+/// Corepack shims and package managers devstrap applied that config dropped
+#[must_use]
+pub fn stale_corepack_items(
+    state: &StateFile,
+    required: &BTreeMap<String, CorepackExtras>,
+) -> Vec<(String, String)> {
+    difference(&items(&state.corepack), &items(required))
+}
+
+fn items(extras: &BTreeMap<String, CorepackExtras>) -> BTreeMap<String, BTreeSet<String>> {
+    extras
+        .iter()
+        .map(|(runtime, extras)| {
+            let shims = extras.enabled.then(|| SHIMS.to_string());
+            (runtime.clone(), shims.into_iter().chain(extras.package_managers.iter().cloned()).collect())
+        })
+        .collect()
+}
+
+/// Enable Corepack in a Node version and pin its package managers
+///
+/// Returns each `name@requirement` pin with the version it resolved to,
+/// for the lockfile.
+pub fn ensure_corepack(
+    manager: &dyn RuntimeManager,
+    runtime: &str,
+    version: &str,
+    pins: &BTreeMap<String, String>,
+    lockfile: &Lockfile,
+    dry_run: bool,
+) -> Result<BTreeMap<String, String>> {
+    let names: Vec<&str> = pins.keys().map(String::as_str).collect();
+    ensure_enabled(manager, runtime, version, &names, dry_run)?;
+
+    let mut resolved = BTreeMap::new();
+    for (name, requirement) in pins {
+        let pin = format!("{name}@{requirement}");
+        let locked = lockfile.locked_package_manager(runtime, version, &pin);
+        if let Some(current) = ensure_package_manager(manager, runtime, version, (name, requirement), locked, dry_run)? {
+            resolved.insert(pin, current);
+        }
+    }
+    Ok(resolved)
+}
+
+fn ensure_enabled(manager: &dyn RuntimeManager, runtime: &str, version: &str, names: &[&str], dry_run: bool) -> Result<()> {
+    if is_enabled(manager, runtime, version, names) {
+        return Ok(());
+    }
+    if dry_run {
+        println!("    {} Would run: corepack enable ({runtime} {version})", "[DRY-RUN]".yellow());
+        return Ok(());
+    }
+    println!("  {} corepack enabled ({runtime} {version})", "↻".cyan());
+    enable(manager, runtime, version, names)
+}
+
+/// Pin one package manager unless the shim already runs a matching version
+fn ensure_package_manager(
+    manager: &dyn RuntimeManager,
+    runtime: &str,
+    version: &str,
+    (name, requirement): (&str, &str),
+    locked: Option<&str>,
+    dry_run: bool,
+) -> Result<Option<String>> {
+    let current = package_manager_version(manager, runtime, version, name)
+        .filter(|current| locked.map_or_else(|| requirement_accepts(requirement, current), |l| same_version(l, current)));
+    if let Some(current) = current {
+        println!("  {} {} {} up to date ({runtime} {version})", "✓".green(), name.dimmed(), current.dimmed());
+        return Ok(Some(current));
+    }
+
+    if dry_run {
+        println!("    {} Would pin {name}@{requirement} ({runtime} {version})", "[DRY-RUN]".yellow());
+        return Ok(locked.map(String::from));
+    }
+
+    println!("  {} {}@{} ({runtime} {version})", "↓".cyan(), name.green(), locked.unwrap_or(requirement));
+    install_package_manager(manager, runtime, version, name, locked.unwrap_or(requirement))?;
+    package_manager_version(manager, runtime, version, name)
+        .map(Some)
+        .ok_or_else(|| anyhow!("Pinned {name} for {runtime} {version} but could not read its version"))
+}
+
+/// Remove one package manager's shim, or all shims, from every installed version of a runtime
+fn undo_item(config: &Config, runtime: &str, package_manager: Option<&str>) -> Result<()> {
+    let manager = configured_manager(&config.runtimes, runtime)?;
+    for version in manager.list_installed(runtime)? {
+        disable(manager, runtime, &version, package_manager)?;
+    }
+    Ok(())
+}
+
+/// Disable Corepack shims and package managers that config no longer asks for
+pub fn prune_corepack(
+    config: &Config,
+    state: &mut StateFile,
+    required: &BTreeMap<String, CorepackExtras>,
+    dry_run: bool,
+) -> Result<()> {
+    for (runtime, item) in stale_corepack_items(state, required) {
+        if dry_run {
+            println!("    {} Would disable {} {}", "[DRY-RUN]".yellow(), runtime, item);
+            continue;
+        }
+
+        println!("  {} Disabling {} {}...", "✗".red(), runtime, item);
+        let package_manager = (item != SHIMS).then_some(item.as_str());
+        undo_item(config, &runtime, package_manager)?;
+        state.remove_corepack(&runtime, package_manager);
+    }
+    Ok(())
+}
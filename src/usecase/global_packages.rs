@@ -0,0 +1,166 @@
+//! Global package reconciliation
+//!
+//! Installs the `global_packages` of node, python, ruby and go runtimes into
+//! every configured version, pins the versions they resolved to in the
+//! lockfile, and removes the ones dropped from config under `--prune`.
+
+use crate::common::error::Result;
+use crate::config::StateFile;
+use crate::domain::runtime::RuntimeSpec;
+use crate::domain::{Config, Lockfile};
+use crate::service::runtime::{
+    configured_manager, same_version, Ecosystem, GlobalPackage, RuntimeManager,
+};
+use anyhow::anyhow;
+use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Names of the global packages a runtime's config asks for
+#[must_use]
+pub fn global_package_names(runtime: &str, spec: &RuntimeSpec) -> BTreeSet<String> {
+    let Some(ecosystem) = Ecosystem::for_runtime(runtime) else {
+        return BTreeSet::new();
+    };
+    spec.get_global_packages()
+        .iter()
+        .filter_map(|package| GlobalPackage::parse(ecosystem, package).ok())
+        .map(|package| package.name)
+        .collect()
+}
+
+/// Global package names requested per runtime in config
+#[must_use]
+pub fn required_global_packages(config: &Config) -> BTreeMap<String, BTreeSet<String>> {
+    config
+        .runtimes
+        .iter()
+        .map(|(name, spec)| (name.clone(), global_package_names(name, spec)))
+        .filter(|(_, packages)| !packages.is_empty())
+        .collect()
+}
+
+/// Global packages in config that devstrap hasn't installed yet
+#[must_use]
+pub fn pending_global_packages(
+    state: &StateFile,
+    required: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<(String, String)> {
+    difference(required, &state.global_packages)
+}
+
+/// Global packages devstrap installed that config no longer asks for
+#[must_use]
+pub fn stale_global_packages(
+    state: &StateFile,
+    required: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<(String, String)> {
+    difference(&state.global_packages, required)
+}
+
+/// `(runtime, package)` pairs in `left` that aren't in `right`
+pub(super) fn difference(
+    left: &BTreeMap<String, BTreeSet<String>>,
+    right: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<(String, String)> {
+    left.iter()
+        .flat_map(|(runtime, packages)| packages.iter().map(move |package| (runtime, package)))
+        .filter(|(runtime, package)| !right.get(*runtime).is_some_and(|r| r.contains(*package)))
+        .map(|(runtime, package)| (runtime.clone(), package.clone()))
+        .collect()
+}
+
+/// Install a runtime version's global packages
+///
+/// Returns each spec with the version it resolved to, for the lockfile.
+pub fn ensure_global_packages(
+    manager: &dyn RuntimeManager,
+    runtime: &str,
+    version: &str,
+    specs: &[String],
+    lockfile: &Lockfile,
+    dry_run: bool,
+) -> Result<BTreeMap<String, String>> {
+    let mut resolved = BTreeMap::new();
+    let Some(ecosystem) = Ecosystem::for_runtime(runtime) else {
+        return Ok(resolved);
+    };
+
+    for spec in specs {
+        let package = GlobalPackage::parse(ecosystem, spec)?;
+        let locked = lockfile.locked_global_package(runtime, version, spec);
+        if let Some(installed) = ensure_global_package(manager, runtime, version, &package, locked, dry_run)? {
+            resolved.insert(spec.clone(), installed);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Install one package unless a matching version is already there
+fn ensure_global_package(
+    manager: &dyn RuntimeManager,
+    runtime: &str,
+    version: &str,
+    package: &GlobalPackage,
+    locked: Option<&str>,
+    dry_run: bool,
+) -> Result<Option<String>> {
+    let current = package
+        .installed_version(manager, runtime, version)
+        .filter(|installed| locked.map_or_else(|| package.accepts(installed), |l| same_version(l, installed)));
+    if let Some(current) = current {
+        println!("  {} {} {} up to date ({runtime} {version})", "✓".green(), package.name.dimmed(), current.dimmed());
+        return Ok(Some(current));
+    }
+
+    if dry_run {
+        println!("    {} Would install {} into {runtime} {version}", "[DRY-RUN]".yellow(), package.name);
+        return Ok(locked.map(String::from));
+    }
+
+    println!("  {} {} ({runtime} {version})", "↓".cyan(), package.name.green());
+    package.install(manager, runtime, version, locked)?;
+    package
+        .installed_version(manager, runtime, version)
+        .map(Some)
+        .ok_or_else(|| anyhow!("Installed {} into {runtime} {version} but could not read its version", package.name))
+}
+
+/// Remove a package from every installed version of a runtime
+fn remove_global_package(config: &Config, runtime: &str, name: &str) -> Result<()> {
+    let Some(ecosystem) = Ecosystem::for_runtime(runtime) else {
+        return Ok(());
+    };
+    let manager = configured_manager(&config.runtimes, runtime)?;
+    let package = GlobalPackage {
+        ecosystem,
+        name: name.to_string(),
+        requirement: None,
+    };
+
+    for version in manager.list_installed(runtime)? {
+        if package.installed_version(manager, runtime, &version).is_some() {
+            package.uninstall(manager, runtime, &version)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove stale global packages from every installed runtime version
+pub fn prune_global_packages(
+    config: &Config,
+    state: &mut StateFile,
+    required: &BTreeMap<String, BTreeSet<String>>,
+    dry_run: bool,
+) -> Result<()> {
+    for (runtime, name) in stale_global_packages(state, required) {
+        if dry_run {
+            println!("    {} Would remove {} global {}", "[DRY-RUN]".yellow(), runtime, name);
+            continue;
+        }
+
+        println!("  {} Removing {} global {}...", "✗".red(), runtime, name);
+        remove_global_package(config, &runtime, &name)?;
+        state.remove_global_package(&runtime, &name);
+    }
+    Ok(())
+}
@@ -2,13 +2,18 @@
 //!
 //! Provides the Installer struct and group-level installation coordination.
 
-use super::orchestration::{prepare_packages, report_errors};
-use crate::service::package_manager::{installer, update_package_manager};
+use super::orchestration::{preferred_method, prepare_packages, report_errors};
+use super::package_locks::package_lock_key;
+use super::repositories::{ensure_repositories, required_repositories};
+use super::taps::required_taps;
+use crate::service::package_manager::{aur, brew, installer, update_package_manager};
 use crate::domain::Config;
 use crate::domain::SystemInfo;
 use crate::common::error::Result;
-use crate::domain::Package;
+use crate::domain::{InstallMethod, Lockfile, Package};
+use crate::domain::Repository;
 use colored::Colorize;
+use std::cell::RefCell;
 use std::sync::Arc;
 
 /// Main installer coordinator
@@ -16,6 +21,10 @@ pub struct Installer {
     config: Arc<Config>,
     system_info: Arc<SystemInfo>,
     dry_run: bool,
+    added_taps: RefCell<Vec<String>>,
+    added_repositories: RefCell<Vec<(String, Repository)>>,
+    lockfile: Option<Arc<Lockfile>>,
+    failed: RefCell<Vec<String>>,
 }
 
 impl Installer {
@@ -26,16 +35,62 @@ impl Installer {
             config: Arc::new(config),
             system_info: Arc::new(system_info),
             dry_run,
+            added_taps: RefCell::new(Vec::new()),
+            added_repositories: RefCell::new(Vec::new()),
+            lockfile: None,
+            failed: RefCell::new(Vec::new()),
         }
     }
 
+    /// Install the package versions recorded in a lockfile
+    #[must_use]
+    pub fn with_lockfile(mut self, lockfile: Lockfile) -> Self {
+        self.lockfile = Some(Arc::new(lockfile));
+        self
+    }
+
+    /// Homebrew taps newly added during this run
+    #[must_use]
+    pub fn added_taps(&self) -> Vec<String> {
+        self.added_taps.borrow().clone()
+    }
+
+    /// Third-party repositories newly configured during this run
+    #[must_use]
+    pub fn added_repositories(&self) -> Vec<(String, Repository)> {
+        self.added_repositories.borrow().clone()
+    }
+
+    /// Packages that failed to install during this run
+    #[must_use]
+    pub fn failed_packages(&self) -> Vec<String> {
+        self.failed.borrow().clone()
+    }
+
     /// Install all packages sequentially by group
     ///
     /// Groups are processed sequentially, and packages within each group
     /// are also installed sequentially to avoid lock file conflicts.
+    /// Repositories are configured first so the index is refreshed once.
     pub fn install_all(&self) -> Result<()> {
-        // Update package manager cache before installing packages
-        // Only update if we have a system package manager (not Cargo/npm/pipx)
+        self.ensure_repositories();
+        self.update_system_cache()?;
+        self.ensure_taps()?;
+        self.ensure_aur_helper()?;
+
+        let groups = self.config.get_package_groups();
+
+        for (idx, _group) in groups.iter().enumerate() {
+            self.install_group_by_index(idx);
+        }
+
+        Ok(())
+    }
+
+    /// Update package manager cache before installing packages
+    ///
+    /// Only update if we have a system package manager (not Cargo/npm/pipx)
+    fn update_system_cache(&self) -> Result<()> {
         use crate::domain::PackageManager;
         if let Some(pm) = self.system_info.default_package_manager {
             if matches!(
@@ -49,13 +104,38 @@ impl Installer {
                 update_package_manager(pm, self.dry_run)?;
             }
         }
+        Ok(())
+    }
 
-        let groups = self.config.get_package_groups();
-
-        for (idx, _group) in groups.iter().enumerate() {
-            self.install_group_by_index(idx);
+    /// Configure the third-party repositories that apply to this system
+    ///
+    /// Failures are reported without stopping the install; only the packages
+    /// that need a failed repository will fail.
+    fn ensure_repositories(&self) {
+        let required = required_repositories(&self.config, &self.system_info);
+        let (added, errors) = ensure_repositories(&required, self.dry_run);
+        if !self.dry_run {
+            self.added_repositories.borrow_mut().extend(added);
         }
+        report_errors(errors);
+    }
 
+    /// Add every Homebrew tap the configured packages need, once per run
+    fn ensure_taps(&self) -> Result<()> {
+        let taps = required_taps(&self.config, &self.system_info);
+        let added = brew::ensure_taps(&taps, self.dry_run)?;
+        self.added_taps.borrow_mut().extend(added);
+        Ok(())
+    }
+
+    /// Make sure an AUR helper exists when any package is coming from the AUR
+    fn ensure_aur_helper(&self) -> Result<()> {
+        let needs_aur = self.config.get_all_packages().iter().any(|id| {
+            preferred_method(id, &self.system_info, &self.config.settings) == Some(InstallMethod::Aur)
+        });
+        if needs_aur {
+            aur::ensure_helper(self.config.settings.aur_bootstrap.as_deref(), self.dry_run)?;
+        }
         Ok(())
     }
 
@@ -83,14 +163,38 @@ impl Installer {
             format!("#{}", group_idx + 1).bold()
         );
 
-        let packages = prepare_packages(package_ids, &self.system_info);
+        let mut packages = prepare_packages(package_ids, &self.system_info, &self.config.settings);
+        self.apply_version_pins(&mut packages);
         let results = self.install_packages(&packages);
         let errors = Self::collect_errors(results, &packages);
+        self.failed.borrow_mut().extend(errors.iter().map(|(id, _)| id.clone()));
 
         report_errors(errors);
     }
 
+    /// Attach `package_versions` pins and locked versions to prepared packages
+    ///
+    /// A locked version wins over the pin, which `sync --frozen` has already
+    /// checked the two agree on.
+    fn apply_version_pins(&self, packages: &mut [Package]) {
+        for package in packages {
+            package.locked_version = self.locked_version(package);
+            package.version = package
+                .locked_version
+                .clone()
+                .filter(|_| package.preferred_method == InstallMethod::Url)
+                .or_else(|| self.config.get_package_version(&package.id).map(String::from));
+        }
+    }
 
+    /// Version locked for a package under its preferred method
+    fn locked_version(&self, package: &Package) -> Option<String> {
+        let (method, name) = package_lock_key(package)?;
+        self.lockfile
+            .as_ref()?
+            .locked_package_version(&package.id, &method, &name)
+            .map(String::from)
+    }
 
     /// Install multiple packages sequentially
     fn install_packages(&self, packages: &[Package]) -> Vec<Result<()>> {
@@ -106,8 +210,6 @@ impl Installer {
             .collect()
     }
 
-
-
     /// Collect installation errors from results
     fn collect_errors(
         results: Vec<Result<()>>,
@@ -144,6 +246,7 @@ mod tests {
             special_installs: std::collections::HashMap::new(),
             runtimes: std::collections::HashMap::new(),
             system_languages: std::collections::HashMap::new(),
+            ..crate::domain::Config::default()
         };
 
         let _installer = Installer::new(config, system_info, false);
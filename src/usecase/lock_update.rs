@@ -0,0 +1,122 @@
+//! Lockfile entries `devstrap update` compares and moves
+//!
+//! An update pairs a locked version with the version the entry resolves to
+//! now, and classifies the move for the update table.
+
+use crate::domain::Version;
+use crate::service::runtime::same_version;
+use std::cmp::Ordering;
+
+/// A lockfile entry `update` can move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockEntry {
+    /// A runtime version constraint
+    Runtime {
+        /// Runtime name
+        name: String,
+        /// Constraint as written in config (`lts`, `3.12`)
+        constraint: String,
+        /// Manager the runtime is installed with
+        manager: String,
+    },
+    /// A package under its installation method
+    Package {
+        /// Package id
+        id: String,
+        /// Installation method
+        method: String,
+        /// Package name used by the method
+        name: String,
+    },
+}
+
+/// How an entry's version moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionChange {
+    /// Not locked yet
+    New,
+    /// Newer than the locked version
+    Upgrade,
+    /// Older than the locked version
+    Downgrade,
+    /// Same as the locked version
+    Unchanged,
+    /// Different, but the versions can't be ordered
+    Changed,
+}
+
+/// A locked version next to the version it resolves to now
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockUpdate {
+    /// Entry being updated
+    pub entry: LockEntry,
+    /// Version in the lockfile, if any
+    pub current: Option<String>,
+    /// Version it resolves to now
+    pub latest: String,
+}
+
+impl LockUpdate {
+    /// Name shown in the update table (`ripgrep`, `node lts`)
+    #[must_use]
+    pub fn label(&self) -> String {
+        match &self.entry {
+            LockEntry::Runtime { name, constraint, .. } => format!("{name} {constraint}"),
+            LockEntry::Package { id, .. } => id.clone(),
+        }
+    }
+
+    /// How the version moves
+    #[must_use]
+    pub fn change(&self) -> VersionChange {
+        let Some(current) = &self.current else {
+            return VersionChange::New;
+        };
+        if same_version(current, &self.latest) {
+            return VersionChange::Unchanged;
+        }
+        match (Version::parse(current), Version::parse(&self.latest)) {
+            (Some(current), Some(latest)) => match latest.cmp(&current) {
+                Ordering::Greater => VersionChange::Upgrade,
+                Ordering::Less => VersionChange::Downgrade,
+                Ordering::Equal => VersionChange::Changed,
+            },
+            _ => VersionChange::Changed,
+        }
+    }
+
+    /// Whether applying this update changes anything
+    #[must_use]
+    pub fn is_change(&self) -> bool {
+        self.change() != VersionChange::Unchanged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_change() {
+        let cases = [
+            (None, "14.1.1", VersionChange::New),
+            (Some("14.1.0"), "14.1.1", VersionChange::Upgrade),
+            (Some("20.11.0"), "20.9.0", VersionChange::Downgrade),
+            (Some("v1.82.0"), "1.82.0", VersionChange::Unchanged),
+            (Some("nightly-2026-10-01"), "nightly-2026-10-15", VersionChange::Changed),
+        ];
+
+        for (current, latest, expected) in cases {
+            let update = LockUpdate {
+                entry: LockEntry::Package {
+                    id: "ripgrep".to_string(),
+                    method: "cargo".to_string(),
+                    name: "ripgrep".to_string(),
+                },
+                current: current.map(String::from),
+                latest: latest.to_string(),
+            };
+            assert_eq!(update.change(), expected, "{current:?} -> {latest}");
+        }
+    }
+}
@@ -1,11 +1,49 @@
 //! Business logic and use cases
 
+pub mod corepack;
+pub mod global_packages;
 pub mod install;
 pub mod list;
+pub mod lock_update;
 pub mod orchestration;
+pub mod package_locks;
+pub mod refresh;
+pub mod repositories;
 pub mod runtime_coordinator;
+pub mod taps;
+pub mod toolchains;
+pub mod update;
+pub mod update_apply;
+pub mod update_platform;
 
+pub use corepack::{
+    corepack_extras, ensure_corepack, pending_corepack_items, prune_corepack, required_corepack,
+    stale_corepack_items,
+};
+pub use global_packages::{
+    ensure_global_packages, global_package_names, pending_global_packages, prune_global_packages,
+    required_global_packages, stale_global_packages,
+};
 pub use install::Installer;
 pub use list::list_packages;
-pub use orchestration::{dispatch_installation, prepare_packages, print_package_status, report_errors};
+pub use orchestration::{
+    dispatch_installation, plan_packages, preferred_method, prepare_packages, print_package_status,
+    report_errors,
+};
+pub use package_locks::{lock_packages, package_lock_key, package_lock_mismatches};
+pub use refresh::{refresh_expired_versions, VersionRefresh};
+pub use repositories::{
+    ensure_repositories, prune_repositories, required_repositories, stale_repositories,
+};
 pub use runtime_coordinator::RuntimeCoordinator;
+pub use taps::{prune_taps, required_taps, stale_taps};
+pub use toolchains::{
+    apply_toolchain_extras, pending_toolchain_items, prune_toolchain_extras,
+    required_toolchain_extras, stale_toolchain_items,
+};
+pub use lock_update::{LockEntry, LockUpdate, VersionChange};
+pub use update::{plan_updates, unknown_update_names};
+pub use update_apply::{
+    apply_package_updates, lock_package_updates, lock_runtime_updates, runtime_update_config, updated_runtimes,
+};
+pub use update_platform::{plan_platform_updates, platform_system_info};
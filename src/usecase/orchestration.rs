@@ -4,33 +4,96 @@
 
 use crate::service::package_manager::methods::{
     install_from_github, install_with_cargo, install_with_npm, install_with_pipx,
-    install_with_system_package_manager,
+    install_with_system_package_manager, install_with_uv,
 };
 use crate::builtin;
-use crate::domain::SystemInfo;
+use crate::domain::{Settings, SystemInfo};
 use crate::common::error::Result;
-use crate::domain::{determine_best_method, InstallMethod, Package};
-use crate::common::detect_installation_method;
+use crate::domain::{
+    determine_best_method, InstallMethod, Package, PackageConfig, PackageManager,
+};
+use crate::common::{
+    detect_aur_installation, detect_cask_installation, detect_installation_method, local_bin_dir,
+};
+use crate::service::package_manager::aur::install_with_aur;
+use crate::service::package_manager::brew::install_with_brew;
+use crate::service::package_manager::url::install_with_url;
+use crate::service::package_manager::versions::ensure_available;
 use colored::Colorize;
 
 /// Prepare packages for installation
 #[must_use]
-pub fn prepare_packages(package_ids: &[String], system_info: &SystemInfo) -> Vec<Package> {
-    let mut packages = Vec::new();
-
-    for package_id in package_ids {
-        if let Some(package_config) = builtin::get_package(package_id) {
-            if let Some(method) = determine_best_method(package_config, system_info) {
-                let mut package = Package::new(package_id.clone(), package_config.clone(), method);
+pub fn prepare_packages(
+    package_ids: &[String],
+    system_info: &SystemInfo,
+    settings: &Settings,
+) -> Vec<Package> {
+    let mut packages = plan_packages(package_ids, system_info, settings);
+    for package in &mut packages {
+        package.current_method = detect_current_method(package, system_info);
+    }
+    packages
+}
 
-                package.current_method = detect_installation_method(package_id, system_info);
+/// Resolve packages to their preferred method without checking what is installed
+#[must_use]
+pub fn plan_packages(
+    package_ids: &[String],
+    system_info: &SystemInfo,
+    settings: &Settings,
+) -> Vec<Package> {
+    package_ids
+        .iter()
+        .filter_map(|package_id| {
+            let package_config = effective_package_config(builtin::get_package(package_id)?, settings);
+            let method = determine_best_method(&package_config, system_info)?;
+            Some(Package::new(package_id.clone(), package_config, method))
+        })
+        .collect()
+}
 
-                packages.push(package);
-            }
+/// Detect how a package is currently installed
+fn detect_current_method(package: &Package, system_info: &SystemInfo) -> Option<InstallMethod> {
+    if package.config.cask {
+        let cask = package.config.name_for_method("brew").unwrap_or(&package.id);
+        return detect_cask_installation(cask, system_info);
+    }
+    if let Some(source) = &package.config.url {
+        if local_bin_has(&source.installed_binary_name(&package.id)) {
+            return Some(InstallMethod::Url);
+        }
+    }
+    if let Some(aur) = &package.config.aur {
+        if let Some(method) = detect_aur_installation(aur, system_info) {
+            return Some(method);
         }
     }
+    detect_installation_method(&package.id, system_info)
+}
 
-    packages
+/// Whether devstrap's bin directory contains an executable
+fn local_bin_has(binary: &str) -> bool {
+    local_bin_dir().is_ok_and(|dir| dir.join(binary).is_file())
+}
+
+/// Determine the preferred installation method for a builtin package
+#[must_use]
+pub fn preferred_method(
+    package_id: &str,
+    system_info: &SystemInfo,
+    settings: &Settings,
+) -> Option<InstallMethod> {
+    let package_config = effective_package_config(builtin::get_package(package_id)?, settings);
+    determine_best_method(&package_config, system_info)
+}
+
+/// Apply global settings that change how a builtin package is installed
+fn effective_package_config(package_config: &PackageConfig, settings: &Settings) -> PackageConfig {
+    if settings.pipx_via_uv {
+        package_config.with_pipx_via_uv()
+    } else {
+        package_config.clone()
+    }
 }
 
 /// Report installation errors
@@ -44,18 +107,32 @@ pub fn report_errors(errors: Vec<(String, anyhow::Error)>) {
 }
 
 /// Dispatch installation to appropriate method
+///
+/// A package with a locked version gets exactly that version, or an error
+/// when the method can no longer provide it.
 pub fn dispatch_installation(
     package_name: &str,
     method: &InstallMethod,
     package: &Package,
 ) -> Result<()> {
+    let locked = package.locked_version.as_deref();
+    if let Some(version) = locked {
+        ensure_available(method, package_name, &package.config, version)?;
+    }
+
     match method {
+        InstallMethod::SystemDefault(PackageManager::Brew) => {
+            install_with_brew(package_name, &package.config)?;
+        }
         InstallMethod::SystemDefault(pm) => {
-            install_with_system_package_manager(package_name, *pm)?;
+            install_with_system_package_manager(package_name, locked, *pm)?;
         }
-        InstallMethod::Cargo => install_with_cargo(package_name)?,
-        InstallMethod::Npm => install_with_npm(package_name)?,
-        InstallMethod::Pipx => install_with_pipx(package_name)?,
+        InstallMethod::Cargo => install_with_cargo(package_name, locked)?,
+        InstallMethod::Npm => install_with_npm(package_name, locked)?,
+        InstallMethod::Pipx => install_with_pipx(package_name, locked)?,
+        InstallMethod::Uv => install_with_uv(package_name, locked, package)?,
+        InstallMethod::Aur => install_with_aur(package_name)?,
+        InstallMethod::Url => install_with_url(package)?,
         InstallMethod::GitHub => {
             install_from_github(package_name, package);
         }
@@ -68,38 +145,46 @@ pub fn dispatch_installation(
 
 /// Print package installation status
 pub fn print_package_status(package: &Package, needs_install: bool) {
-    if let Some(current_method) = &package.current_method {
-        if needs_install {
-            if matches!(current_method, InstallMethod::System) {
-                println!(
-                    "  {} {} (installing via {} alongside system version)",
-                    "↻".yellow(),
-                    package.id.bold(),
-                    package.preferred_method.display_name().green()
-                );
-            } else {
-                println!(
-                    "  {} {} (currently via {}, preferring {})",
-                    "↻".yellow(),
-                    package.id.bold(),
-                    current_method.display_name().yellow(),
-                    package.preferred_method.display_name().green()
-                );
-            }
-        } else {
-            println!(
-                "  {} {} (via {})",
-                "✓".green(),
-                package.id.dimmed(),
-                current_method.display_name().dimmed()
-            );
-        }
-    } else {
-        println!(
+    match &package.current_method {
+        Some(current_method) if needs_install => print_reinstall_status(package, current_method),
+        Some(current_method) => println!(
+            "  {} {} (via {})",
+            "✓".green(),
+            package.id.dimmed(),
+            current_method.display_name().dimmed()
+        ),
+        None => println!(
             "  {} {} (via {})",
             "↓".cyan(),
             package.id.bold(),
             package.preferred_method.display_name().cyan()
+        ),
+    }
+}
+
+/// Explain why an installed package is being installed again
+fn print_reinstall_status(package: &Package, current_method: &InstallMethod) {
+    if *current_method == package.preferred_method {
+        println!(
+            "  {} {} (installing locked version {})",
+            "↻".yellow(),
+            package.id.bold(),
+            package.locked_version.as_deref().unwrap_or_default().green()
+        );
+    } else if matches!(current_method, InstallMethod::System) {
+        println!(
+            "  {} {} (installing via {} alongside system version)",
+            "↻".yellow(),
+            package.id.bold(),
+            package.preferred_method.display_name().green()
+        );
+    } else {
+        println!(
+            "  {} {} (currently via {}, preferring {})",
+            "↻".yellow(),
+            package.id.bold(),
+            current_method.display_name().yellow(),
+            package.preferred_method.display_name().green()
         );
     }
 }
@@ -0,0 +1,98 @@
+//! Package version locking
+//!
+//! Records the version each configured package is installed at in the
+//! lockfile's `packages` section, and checks that section against the config
+//! for `sync --frozen`.
+
+use super::orchestration::{plan_packages, prepare_packages};
+use crate::domain::{Config, InstallMethod, Lockfile, Package, SystemInfo};
+use crate::service::package_manager::versions::installed_version;
+use std::collections::BTreeSet;
+
+/// Method and method-specific name a package is locked under
+///
+/// `None` for packages devstrap doesn't install itself.
+#[must_use]
+pub fn package_lock_key(package: &Package) -> Option<(String, String)> {
+    if matches!(package.preferred_method, InstallMethod::System | InstallMethod::GitHub) {
+        return None;
+    }
+    Some((package.preferred_method.command().to_string(), package.package_name()?))
+}
+
+/// Version a package is installed at with its preferred method
+fn resolved_version(package: &Package, name: &str, config: &Config) -> Option<String> {
+    if package.current_method.as_ref() != Some(&package.preferred_method) {
+        return None;
+    }
+    if package.preferred_method == InstallMethod::Url {
+        return config
+            .get_package_version(&package.id)
+            .map(String::from)
+            .or_else(|| package.config.url.as_ref()?.version.clone());
+    }
+    installed_version(&package.preferred_method, name, &package.config)
+}
+
+/// Record the installed version of configured packages that aren't locked yet
+///
+/// Existing entries are kept until `--refresh` starts from an empty
+/// lockfile, unless the package's method or name changed. Url packages follow
+/// their `package_versions` pin. Packages that are no longer configured are
+/// dropped.
+pub fn lock_packages(config: &Config, system_info: &SystemInfo, lockfile: &mut Lockfile) {
+    let ids = config.get_all_packages();
+    let configured: BTreeSet<&String> = ids.iter().collect();
+    lockfile.platform_lock_mut().packages.retain(|id, _| configured.contains(id));
+
+    for package in prepare_packages(&ids, system_info, &config.settings) {
+        let Some((method, name)) = package_lock_key(&package) else {
+            continue;
+        };
+        let is_locked = lockfile.locked_package_version(&package.id, &method, &name).is_some();
+        if is_locked && package.preferred_method != InstallMethod::Url {
+            continue;
+        }
+        if let Some(version) = resolved_version(&package, &name, config) {
+            lockfile.lock_package(&package.id, &method, &name, &version);
+        }
+    }
+}
+
+/// Ways the lockfile's packages disagree with the config
+#[must_use]
+pub fn package_lock_mismatches(config: &Config, system_info: &SystemInfo, lockfile: &Lockfile) -> Vec<String> {
+    let ids = config.get_all_packages();
+    let mut mismatches: Vec<String> = plan_packages(&ids, system_info, &config.settings)
+        .iter()
+        .filter_map(|package| package_mismatch(package, config, lockfile))
+        .collect();
+
+    mismatches.extend(
+        lockfile
+            .platform_lock()
+            .into_iter()
+            .flat_map(|section| section.packages.keys())
+            .filter(|id| !ids.contains(id))
+            .map(|id| format!("{id} is locked but no longer in config")),
+    );
+    mismatches
+}
+
+/// How one configured package disagrees with its lock entry, if it does
+fn package_mismatch(package: &Package, config: &Config, lockfile: &Lockfile) -> Option<String> {
+    let (method, name) = package_lock_key(package)?;
+    let Some(locked) = lockfile.platform_lock().and_then(|section| section.packages.get(&package.id)) else {
+        return Some(format!("{} is not locked", package.id));
+    };
+    if locked.method != method || locked.name != name {
+        return Some(format!(
+            "{} is locked as {} {} but config installs {method} {name}",
+            package.id, locked.method, locked.name
+        ));
+    }
+    config
+        .get_package_version(&package.id)
+        .filter(|pin| package.preferred_method == InstallMethod::Url && *pin != locked.version)
+        .map(|pin| format!("{} is pinned to {pin} but locked at {}", package.id, locked.version))
+}
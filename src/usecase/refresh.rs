@@ -0,0 +1,115 @@
+//! Re-resolving floating runtime versions whose locks expired
+//!
+//! With `refresh_after` set, a sync re-resolves each floating constraint
+//! ("latest", "lts", "3.12") of a runtime whose lock is older than the
+//! interval, and relocks it, so the coordinator installs the new version.
+
+use crate::domain::{Config, Lockfile, VersionConstraint};
+use crate::service::runtime::{manager_name_for, same_version, VersionResolver};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use std::collections::BTreeSet;
+
+/// A floating constraint re-resolved because its lock expired
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRefresh {
+    /// Runtime name
+    pub runtime: String,
+    /// Constraint from config (`lts`)
+    pub constraint: String,
+    /// Version it was locked to
+    pub previous: String,
+    /// Version it resolves to now
+    pub resolved: String,
+}
+
+impl VersionRefresh {
+    /// Whether re-resolving moved the constraint to another version
+    #[must_use]
+    pub fn is_bump(&self) -> bool {
+        !same_version(&self.previous, &self.resolved)
+    }
+
+    /// Display label (`node lts 20.11.0 → 20.18.0`)
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!("{} {} {} → {}", self.runtime, self.constraint, self.previous, self.resolved)
+    }
+}
+
+/// Re-resolve and relock the floating versions whose `refresh_after` has passed
+///
+/// Constraints that fail to resolve are reported and keep their lock.
+pub fn refresh_expired_versions(config: &Config, lockfile: &mut Lockfile, now: DateTime<Utc>) -> Vec<VersionRefresh> {
+    let mut expired: Vec<&String> =
+        config.runtimes.keys().filter(|name| is_expired(config, lockfile, name, now)).collect();
+    expired.sort();
+
+    expired.into_iter().flat_map(|name| refresh_runtime(config, lockfile, name)).collect()
+}
+
+/// Whether a runtime has a policy and its locks are older than it
+fn is_expired(config: &Config, lockfile: &Lockfile, name: &str, now: DateTime<Utc>) -> bool {
+    let Ok(Some(policy)) = config.refresh_after_for(name) else {
+        return false;
+    };
+    policy.is_due(lockfile.resolved_at(name), now)
+}
+
+/// Re-resolve one runtime's locked floating constraints
+fn refresh_runtime(config: &Config, lockfile: &mut Lockfile, name: &str) -> Vec<VersionRefresh> {
+    let spec = &config.runtimes[name];
+    let manager = manager_name_for(name, spec);
+    let mut constraints: BTreeSet<String> = spec.get_versions().into_iter().collect();
+    constraints.insert(spec.get_default_version());
+
+    let mut refreshes = Vec::new();
+    for constraint in constraints.into_iter().filter(|c| is_floating(c)) {
+        let Some(previous) = lockfile.locked_version(name, &constraint).map(String::from) else {
+            continue;
+        };
+        match VersionResolver::resolve(name, &constraint, Some(&manager), spec.get_distribution()) {
+            Ok(resolved) => {
+                lockfile.lock_version(name, &constraint, &resolved, &manager);
+                refreshes.push(VersionRefresh { runtime: name.to_string(), constraint, previous, resolved });
+            }
+            Err(e) => eprintln!("  {} Could not re-resolve {} {}: {}", "⚠".yellow(), name, constraint, e),
+        }
+    }
+    refreshes
+}
+
+/// Whether a constraint can resolve to a different version over time
+fn is_floating(constraint: &str) -> bool {
+    VersionConstraint::parse(constraint).is_ok_and(|c| !c.is_exact())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_skips_fresh_and_exact_locks() {
+        let config: Config = toml::from_str(
+            "[settings]\nrefresh_after = \"7d\"\n\n[runtimes]\nnode = \"20.11.0\"\npython = { version = \"3.12\", refresh_after = \"30d\" }\n",
+        )
+        .unwrap();
+        let later = Utc::now() + chrono::Duration::days(8);
+        let mut lockfile = Lockfile::default();
+        lockfile.set_runtime("node", "20.11.0", "20.11.0", "fnm");
+        lockfile.set_runtime("python", "3.12", "3.12.4", "mise");
+
+        assert!(is_expired(&config, &lockfile, "node", later));
+        assert!(!is_expired(&config, &lockfile, "python", later));
+        assert!(refresh_expired_versions(&config, &mut lockfile, later).is_empty());
+    }
+
+    #[test]
+    fn test_is_floating() {
+        let cases = [("20.11.0", false), ("lts", true), ("latest", true), ("3.12", true), ("^1.2", true)];
+
+        for (constraint, floating) in cases {
+            assert_eq!(is_floating(constraint), floating, "{constraint}");
+        }
+    }
+}
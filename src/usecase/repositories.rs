@@ -0,0 +1,110 @@
+//! Third-party repository coordination
+//!
+//! Works out which repositories apply to this system, configures the
+//! missing ones and removes repositories devstrap added once nothing
+//! references them anymore.
+
+use super::orchestration::preferred_method;
+use crate::builtin;
+use crate::common::error::Result;
+use crate::config::StateFile;
+use crate::domain::{Config, InstallMethod, Repository, SystemInfo};
+use crate::service::repository::{ensure_repository, remove_repository};
+use colored::Colorize;
+use std::collections::BTreeMap;
+
+/// Collect the repositories that apply to this system
+///
+/// Global `[repositories]` entries apply whenever the default package
+/// manager supports them; package-level repositories only when that
+/// package will be installed through the system package manager.
+#[must_use]
+pub fn required_repositories(config: &Config, system_info: &SystemInfo) -> BTreeMap<String, Repository> {
+    let Some(pm) = system_info.default_package_manager else {
+        return BTreeMap::new();
+    };
+    let applies = |repository: &Repository| repository.applies_to(pm, system_info.distro);
+
+    let mut required: BTreeMap<String, Repository> = config
+        .repositories
+        .iter()
+        .filter(|(_, repository)| applies(repository))
+        .map(|(name, repository)| (name.clone(), repository.clone()))
+        .collect();
+
+    for id in config.get_all_packages() {
+        if preferred_method(&id, system_info, &config.settings) != Some(InstallMethod::SystemDefault(pm)) {
+            continue;
+        }
+        let Some(package) = builtin::get_package(&id) else {
+            continue;
+        };
+        for (name, repository) in package.repositories.iter().filter(|(_, r)| applies(r)) {
+            required.entry(name.clone()).or_insert_with(|| repository.clone());
+        }
+    }
+
+    required
+}
+
+/// A repository that could not be configured, and why
+type RepositoryError = (String, anyhow::Error);
+
+/// Configure every required repository
+///
+/// Returns the repositories that changed and the ones that failed. A failure
+/// doesn't stop the others, so everything added before it is still recorded.
+#[must_use]
+pub fn ensure_repositories(
+    required: &BTreeMap<String, Repository>,
+    dry_run: bool,
+) -> (Vec<(String, Repository)>, Vec<RepositoryError>) {
+    let mut changed = Vec::new();
+    let mut errors = Vec::new();
+    for (name, repository) in required {
+        match ensure_repository(name, repository, dry_run) {
+            Ok(true) => changed.push((name.clone(), repository.clone())),
+            Ok(false) => {}
+            Err(e) => errors.push((format!("repository {name}"), e)),
+        }
+    }
+    (changed, errors)
+}
+
+/// Repositories devstrap added that nothing requires anymore
+#[must_use]
+pub fn stale_repositories(
+    state: &StateFile,
+    required: &BTreeMap<String, Repository>,
+) -> Vec<(String, Repository)> {
+    state
+        .repositories
+        .iter()
+        .filter(|(name, _)| !required.contains_key(*name))
+        .map(|(name, repository)| (name.clone(), repository.clone()))
+        .collect()
+}
+
+/// Remove every stale repository and drop it from the state file
+pub fn prune_repositories(
+    state: &mut StateFile,
+    required: &BTreeMap<String, Repository>,
+    dry_run: bool,
+) -> Result<()> {
+    for (name, repository) in stale_repositories(state, required) {
+        if dry_run {
+            println!(
+                "    {} Would remove repository {} ({})",
+                "[DRY-RUN]".yellow(),
+                name,
+                repository.describe()
+            );
+            continue;
+        }
+
+        println!("  {} Removing repository {}...", "✗".red(), name);
+        remove_repository(&name, &repository)?;
+        state.remove_repository(&name);
+    }
+    Ok(())
+}
@@ -7,11 +7,19 @@ use crate::common::error::Result;
 use crate::domain::{Config, Lockfile};
 use crate::domain::runtime::RuntimeSpec;
 use crate::service::runtime::{
-    get_required_managers, install_manager, install_runtime_version,
-    install_system_languages, is_manager_installed, resolve_runtime_version,
-    set_default_runtime, VersionResolver,
+    bootstrap_manager, get_required_managers, install_system_languages, manager_name_for,
+    require_manager, resolve_runtime_version, same_version, Bootstrapped, Ecosystem,
+    RuntimeManager,
 };
+use super::corepack::{corepack_extras, ensure_corepack};
+use super::global_packages::{ensure_global_packages, global_package_names};
+use super::orchestration::report_errors;
+use super::toolchains::apply_toolchain_extras;
+use super::lock_update::{LockEntry, LockUpdate};
+use crate::config::{CorepackExtras, ToolchainExtras};
+use anyhow::anyhow;
 use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::Path;
 
 /// Runtime manager for installing language runtimes
@@ -19,6 +27,11 @@ pub struct RuntimeCoordinator {
     config: Config,
     lockfile: Lockfile,
     dry_run: bool,
+    bootstrapped: Vec<Bootstrapped>,
+    toolchain_extras: ToolchainExtras,
+    global_packages: BTreeMap<String, BTreeSet<String>>,
+    corepack: BTreeMap<String, CorepackExtras>,
+    updates: Vec<LockUpdate>,
 }
 
 impl RuntimeCoordinator {
@@ -29,9 +42,21 @@ impl RuntimeCoordinator {
             config,
             lockfile,
             dry_run,
+            bootstrapped: Vec::new(),
+            toolchain_extras: ToolchainExtras::default(),
+            global_packages: BTreeMap::new(),
+            corepack: BTreeMap::new(),
+            updates: Vec::new(),
         }
     }
 
+    /// Move runtimes to the versions `update` resolved, locking each only once it installs
+    #[must_use]
+    pub fn with_updates(mut self, updates: &[LockUpdate]) -> Self {
+        self.updates = updates.iter().filter(|update| update.is_change()).cloned().collect();
+        self
+    }
+
     /// Install all configured runtimes and frameworks
     pub fn install_all(&mut self) -> Result<()> {
         println!("\n{}", "═".repeat(60).cyan());
@@ -41,46 +66,189 @@ impl RuntimeCoordinator {
         self.ensure_managers()?;
         install_system_languages(&self.config.system_languages, self.dry_run)?;
 
-        for (name, spec) in &self.config.runtimes.clone() {
-            self.install_runtime(name, spec)?;
-        }
+        let order = self.config.runtime_install_order()?;
+        report_errors(self.install_in_order(&order));
 
         Ok(())
     }
 
+    /// Install runtimes in dependency order, skipping dependents of failures
+    fn install_in_order(&mut self, order: &[String]) -> Vec<(String, anyhow::Error)> {
+        let mut errors = Vec::new();
+        let mut failed = HashSet::new();
+
+        for name in order {
+            let spec = self.config.runtimes[name].clone();
+            let result = match spec.get_requires().filter(|required| failed.contains(required)) {
+                Some(required) => {
+                    println!("\n{} Skipping {} (requires {})", "⚠".yellow(), name.bold(), required);
+                    Err(anyhow!("Skipped because required runtime '{required}' failed"))
+                }
+                None => self.install_locking(name, &spec),
+            };
+
+            if let Err(e) = result {
+                failed.insert(name.clone());
+                errors.push((name.clone(), e));
+            }
+        }
+
+        errors
+    }
+
+    /// Install a runtime, keeping its previous locks when the install fails
+    fn install_locking(&mut self, name: &str, spec: &RuntimeSpec) -> Result<()> {
+        let previous = self.lockfile.clone();
+        for update in &self.updates {
+            if let LockEntry::Runtime { name: runtime, constraint, manager } = &update.entry {
+                if runtime == name {
+                    self.lockfile.lock_version(name, constraint, &update.latest, manager);
+                }
+            }
+        }
+
+        let result = self.install_runtime(name, spec);
+        if result.is_err() {
+            self.lockfile.restore_runtime(name, &previous);
+        }
+        result
+    }
+
     /// Ensure required version managers are installed
-    fn ensure_managers(&self) -> Result<()> {
-        let managers_needed = get_required_managers(&self.config.runtimes);
+    fn ensure_managers(&mut self) -> Result<()> {
+        for manager in get_required_managers(&self.config.runtimes)? {
+            if manager.is_installed() {
+                continue;
+            }
 
-        for manager in managers_needed {
-            if !is_manager_installed(&manager) {
-                println!("  {} Installing {} manager...", "↓".cyan(), manager);
-                install_manager(&manager, self.dry_run)?;
+            println!("  {} Installing {} manager...", "↓".cyan(), manager.name());
+            if self.dry_run {
+                println!("    {} Would install {}", "[DRY-RUN]".yellow(), manager.name());
+                continue;
             }
+
+            self.bootstrap(manager)?;
         }
 
         Ok(())
     }
 
+    /// Bootstrap a manager, pinning its installer script in the lockfile
+    fn bootstrap(&mut self, manager: &dyn RuntimeManager) -> Result<()> {
+        let name = manager.name();
+        let installer = self.config.settings.installers.get(name);
+        let url = installer.and_then(|i| i.url.as_deref());
+        let pinned = installer
+            .and_then(|i| i.sha256.as_deref())
+            .or_else(|| self.lockfile.installer_sha256(name));
+        let allow_unpinned = installer.is_some_and(|i| i.allow_unpinned);
+
+        let bootstrapped = bootstrap_manager(manager, url, pinned, allow_unpinned)?;
+        self.lockfile
+            .lock_installer(name, &bootstrapped.url, &bootstrapped.sha256);
+        println!("    {} {} installed", "✓".green(), name);
+        self.bootstrapped.push(bootstrapped);
+
+        let shell = std::env::var("SHELL").unwrap_or_default();
+        let shell = shell.rsplit('/').next().filter(|s| !s.is_empty()).unwrap_or("bash");
+        println!(
+            "    {} Add to your shell profile: {}",
+            "⚠".yellow(),
+            manager.env_activation(shell)
+        );
+        Ok(())
+    }
+
+    /// Managers bootstrapped during this run
+    #[must_use]
+    pub fn bootstrapped_managers(&self) -> &[Bootstrapped] {
+        &self.bootstrapped
+    }
+
+    /// Rustup profile, components and targets applied during this run
+    #[must_use]
+    pub fn applied_toolchain_extras(&self) -> &ToolchainExtras {
+        &self.toolchain_extras
+    }
+
+    /// Global packages installed during this run, by runtime
+    #[must_use]
+    pub fn applied_global_packages(&self) -> &BTreeMap<String, BTreeSet<String>> {
+        &self.global_packages
+    }
+
+    /// Corepack shims and package managers applied during this run, by runtime
+    #[must_use]
+    pub fn applied_corepack(&self) -> &BTreeMap<String, CorepackExtras> {
+        &self.corepack
+    }
+
     /// Install a runtime
     fn install_runtime(&mut self, name: &str, spec: &RuntimeSpec) -> Result<()> {
         println!("\n{} {}", "Installing runtime:".bold().cyan(), name.bold());
 
-        let manager = spec
-            .get_manager()
-            .unwrap_or_else(|| VersionResolver::default_manager(name).to_string());
+        let manager = manager_name_for(name, spec);
+        let runtime_manager = require_manager(&manager)?;
 
-        let versions = spec.get_versions();
+        self.lockfile.lock_distribution(name, spec.get_distribution());
+        let toolchains = self.install_versions(runtime_manager, name, spec)?;
         let default_version = spec.get_default_version();
+        let default_resolved = resolve_runtime_version(
+            name,
+            &default_version,
+            &manager,
+            spec.get_distribution(),
+            &mut self.lockfile,
+        )?;
+        self.lockfile
+            .set_runtime(name, &default_version, &default_resolved, &manager);
+
+        if manager == "rustup" && spec.has_toolchain_options() {
+            self.apply_toolchain_extras(spec, &toolchains)?;
+        }
+
+        if Ecosystem::for_runtime(name).is_some() {
+            self.apply_global_packages(runtime_manager, name, spec, &toolchains)?;
+        }
+
+        if spec.uses_corepack() {
+            self.apply_corepack(runtime_manager, name, spec, &toolchains)?;
+        }
+
+        self.ensure_default(runtime_manager, name, &default_resolved)
+    }
 
-        for version in &versions {
+    /// Resolve and install each configured version, returning the resolved versions
+    fn install_versions(
+        &mut self,
+        runtime_manager: &dyn RuntimeManager,
+        name: &str,
+        spec: &RuntimeSpec,
+    ) -> Result<Vec<String>> {
+        let manager = runtime_manager.name();
+        let installed = runtime_manager.list_installed(name).unwrap_or_default();
+        let mut toolchains = Vec::new();
+
+        for version in &spec.get_versions() {
             let requested = version.clone();
             let resolved = resolve_runtime_version(
                 name,
                 &requested,
-                &manager,
+                manager,
+                spec.get_distribution(),
                 &mut self.lockfile,
             )?;
+            toolchains.push(resolved.clone());
+
+            if installed.iter().any(|v| same_version(v, &resolved)) {
+                println!(
+                    "  {} version {} (resolved: {}) up to date",
+                    "✓".green(),
+                    requested,
+                    resolved.dimmed()
+                );
+                continue;
+            }
 
             println!(
                 "  {} version {} (resolved: {})",
@@ -90,14 +258,81 @@ impl RuntimeCoordinator {
             );
 
             if !self.dry_run {
-                install_runtime_version(name, &resolved, &manager)?;
+                runtime_manager.install(name, &resolved)?;
             }
         }
 
-        if !self.dry_run {
-            set_default_runtime(name, &default_version, &manager)?;
+        Ok(toolchains)
+    }
+
+    /// Apply rustup components, targets and profile to the runtime's toolchains
+    fn apply_toolchain_extras(&mut self, spec: &RuntimeSpec, toolchains: &[String]) -> Result<()> {
+        apply_toolchain_extras(spec, toolchains, self.dry_run)?;
+
+        let extras = &mut self.toolchain_extras;
+        extras.profile = spec.get_profile().map(String::from).or(extras.profile.take());
+        extras.components.extend(spec.get_components().iter().cloned());
+        extras.targets.extend(spec.get_targets().iter().cloned());
+        Ok(())
+    }
+
+    /// Install the runtime's global packages into each version and lock them
+    fn apply_global_packages(
+        &mut self,
+        manager: &dyn RuntimeManager,
+        name: &str,
+        spec: &RuntimeSpec,
+        versions: &[String],
+    ) -> Result<()> {
+        for version in versions {
+            let resolved = ensure_global_packages(
+                manager,
+                name,
+                version,
+                spec.get_global_packages(),
+                &self.lockfile,
+                self.dry_run,
+            )?;
+            self.lockfile.lock_global_packages(name, version, resolved);
         }
 
+        let packages = global_package_names(name, spec);
+        if !packages.is_empty() {
+            self.global_packages.entry(name.to_string()).or_default().extend(packages);
+        }
+        Ok(())
+    }
+
+    /// Enable Corepack in each version, pin its package managers and lock them
+    fn apply_corepack(
+        &mut self,
+        manager: &dyn RuntimeManager,
+        name: &str,
+        spec: &RuntimeSpec,
+        versions: &[String],
+    ) -> Result<()> {
+        let pins = spec.get_package_managers().cloned().unwrap_or_default();
+        for version in versions {
+            let resolved = ensure_corepack(manager, name, version, &pins, &self.lockfile, self.dry_run)?;
+            self.lockfile.lock_package_managers(name, version, resolved);
+        }
+
+        self.corepack.insert(name.to_string(), corepack_extras(spec));
+        Ok(())
+    }
+
+    /// Set the default version unless it already is the default
+    fn ensure_default(&self, manager: &dyn RuntimeManager, name: &str, version: &str) -> Result<()> {
+        let current = manager.current_default(name).ok().flatten();
+        if current.is_some_and(|current| same_version(&current, version)) {
+            println!("  {} default {} already set", "✓".green(), version.dimmed());
+            return Ok(());
+        }
+
+        println!("  {} default → {}", "↻".cyan(), version.green());
+        if !self.dry_run {
+            manager.set_default(name, version)?;
+        }
         Ok(())
     }
 
@@ -0,0 +1,52 @@
+//! Homebrew tap coordination
+//!
+//! Works out which taps the configured packages need and removes taps
+//! devstrap added once nothing depends on them anymore.
+
+use super::orchestration::preferred_method;
+use crate::builtin;
+use crate::common::error::Result;
+use crate::config::StateFile;
+use crate::domain::{Config, InstallMethod, PackageManager, SystemInfo};
+use crate::service::package_manager::brew::remove_tap;
+use colored::Colorize;
+use std::collections::BTreeSet;
+
+/// Collect the taps needed by packages that will be installed via Homebrew
+#[must_use]
+pub fn required_taps(config: &Config, system_info: &SystemInfo) -> BTreeSet<String> {
+    config
+        .get_all_packages()
+        .iter()
+        .filter(|id| {
+            preferred_method(id, system_info, &config.settings)
+                == Some(InstallMethod::SystemDefault(PackageManager::Brew))
+        })
+        .filter_map(|id| builtin::get_package(id)?.tap.clone())
+        .collect()
+}
+
+/// Taps devstrap added that no configured package needs anymore
+#[must_use]
+pub fn stale_taps(state: &StateFile, required: &BTreeSet<String>) -> Vec<String> {
+    state.taps.difference(required).cloned().collect()
+}
+
+/// Untap every stale tap and drop it from the state file
+pub fn prune_taps(
+    state: &mut StateFile,
+    required: &BTreeSet<String>,
+    dry_run: bool,
+) -> Result<()> {
+    for tap in stale_taps(state, required) {
+        if dry_run {
+            println!("    {} Would run: brew untap {}", "[DRY-RUN]".yellow(), tap);
+            continue;
+        }
+
+        println!("  {} Untapping {}...", "✗".red(), tap);
+        remove_tap(&tap)?;
+        state.remove_tap(&tap);
+    }
+    Ok(())
+}
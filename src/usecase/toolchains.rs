@@ -0,0 +1,136 @@
+//! Rustup toolchain reconciliation
+//!
+//! Applies the `components`, `targets` and `profile` keys of rustup-managed
+//! runtimes to every toolchain devstrap installs, and removes the ones that
+//! were dropped from config under `--prune`.
+
+use crate::common::error::Result;
+use crate::config::{StateFile, ToolchainExtras};
+use crate::domain::runtime::RuntimeSpec;
+use crate::domain::Config;
+use crate::service::runtime::manager::rustup::{current_profile, set_profile, ToolchainItem};
+use crate::service::runtime::{get_manager, manager_name_for};
+use colored::Colorize;
+
+/// Extras requested by the rustup-managed runtimes in config
+#[must_use]
+pub fn required_toolchain_extras(config: &Config) -> ToolchainExtras {
+    let mut extras = ToolchainExtras::default();
+    for (name, spec) in &config.runtimes {
+        if manager_name_for(name, spec) != "rustup" {
+            continue;
+        }
+        extras.profile = spec.get_profile().map(String::from).or(extras.profile);
+        extras.components.extend(spec.get_components().iter().cloned());
+        extras.targets.extend(spec.get_targets().iter().cloned());
+    }
+    extras
+}
+
+/// Components and targets in config that devstrap hasn't applied yet
+#[must_use]
+pub fn pending_toolchain_items(state: &StateFile, required: &ToolchainExtras) -> Vec<(ToolchainItem, String)> {
+    items(required)
+        .into_iter()
+        .filter(|item| !items(&state.rustup).contains(item))
+        .collect()
+}
+
+/// Components and targets devstrap added that config no longer asks for
+#[must_use]
+pub fn stale_toolchain_items(state: &StateFile, required: &ToolchainExtras) -> Vec<(ToolchainItem, String)> {
+    let required = items(required);
+    items(&state.rustup)
+        .into_iter()
+        .filter(|item| !required.contains(item))
+        .collect()
+}
+
+fn items(extras: &ToolchainExtras) -> Vec<(ToolchainItem, String)> {
+    let components = extras.components.iter().map(|c| (ToolchainItem::Component, c.clone()));
+    let targets = extras.targets.iter().map(|t| (ToolchainItem::Target, t.clone()));
+    components.chain(targets).collect()
+}
+
+/// Set the profile and add missing components and targets to toolchains
+pub fn apply_toolchain_extras(spec: &RuntimeSpec, toolchains: &[String], dry_run: bool) -> Result<()> {
+    if let Some(profile) = spec.get_profile() {
+        apply_profile(profile, dry_run)?;
+    }
+
+    let wanted: Vec<(ToolchainItem, &String)> = spec
+        .get_components()
+        .iter()
+        .map(|c| (ToolchainItem::Component, c))
+        .chain(spec.get_targets().iter().map(|t| (ToolchainItem::Target, t)))
+        .collect();
+
+    for toolchain in toolchains {
+        for (kind, name) in &wanted {
+            add_item(*kind, toolchain, name, dry_run)?;
+        }
+    }
+    Ok(())
+}
+
+fn apply_profile(profile: &str, dry_run: bool) -> Result<()> {
+    if current_profile().is_ok_and(|current| current == profile) {
+        return Ok(());
+    }
+    if dry_run {
+        println!("    {} Would run: rustup set profile {}", "[DRY-RUN]".yellow(), profile);
+        return Ok(());
+    }
+    println!("  {} profile → {}", "↻".cyan(), profile.green());
+    set_profile(profile)
+}
+
+fn add_item(kind: ToolchainItem, toolchain: &str, name: &str, dry_run: bool) -> Result<()> {
+    if kind.list_installed(toolchain).is_ok_and(|installed| installed.iter().any(|i| i == name)) {
+        return Ok(());
+    }
+    if dry_run {
+        println!("    {} Would add {} {} to {}", "[DRY-RUN]".yellow(), kind.as_str(), name, toolchain);
+        return Ok(());
+    }
+    println!("  {} {} {} ({})", "↓".cyan(), kind.as_str(), name.green(), toolchain);
+    kind.add(toolchain, name)
+}
+
+/// Remove a component or target from each toolchain that has it
+fn remove_item(kind: ToolchainItem, toolchains: &[String], name: &str) -> Result<()> {
+    for toolchain in toolchains {
+        if kind.list_installed(toolchain).is_ok_and(|installed| installed.iter().any(|i| i == name)) {
+            kind.remove(toolchain, name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove stale components and targets from every installed toolchain
+pub fn prune_toolchain_extras(state: &mut StateFile, required: &ToolchainExtras, dry_run: bool) -> Result<()> {
+    let stale = stale_toolchain_items(state, required);
+    if stale.is_empty() {
+        return Ok(());
+    }
+
+    let toolchains = get_manager("rustup")
+        .map(|rustup| rustup.list_installed("rust"))
+        .transpose()?
+        .unwrap_or_default();
+
+    for (kind, name) in stale {
+        if dry_run {
+            println!("    {} Would remove rust {} {}", "[DRY-RUN]".yellow(), kind.as_str(), name);
+            continue;
+        }
+
+        println!("  {} Removing rust {} {}...", "✗".red(), kind.as_str(), name);
+        remove_item(kind, &toolchains, &name)?;
+        match kind {
+            ToolchainItem::Component => state.rustup.components.remove(&name),
+            ToolchainItem::Target => state.rustup.targets.remove(&name),
+        };
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Re-resolving locked versions for `devstrap update`
+//!
+//! Every runtime constraint and package is resolved again as if nothing were
+//! locked and compared with the lockfile. Applying the entries that moved
+//! lives in `update_apply`, locking other platforms in `update_platform`.
+
+use super::lock_update::{LockEntry, LockUpdate};
+use super::orchestration::plan_packages;
+use super::package_locks::package_lock_key;
+use crate::domain::{Config, InstallMethod, Lockfile, Package, SystemInfo};
+use crate::service::package_manager::versions::available_version;
+use crate::service::runtime::{manager_name_for, VersionResolver};
+use colored::Colorize;
+use std::collections::BTreeSet;
+
+/// Names that are neither a configured package nor a configured runtime
+#[must_use]
+pub fn unknown_update_names(config: &Config, names: &[String]) -> Vec<String> {
+    let packages = config.get_all_packages();
+    names
+        .iter()
+        .filter(|name| !packages.contains(name) && !config.runtimes.contains_key(*name))
+        .cloned()
+        .collect()
+}
+
+/// Finds the version a package would install now
+pub(super) type LatestVersion = fn(&Package, &str) -> Option<String>;
+
+/// Re-resolve the named runtimes and packages, or all of them
+///
+/// Entries that can't be resolved are reported and left out.
+#[must_use]
+pub fn plan_updates(config: &Config, system_info: &SystemInfo, lockfile: &Lockfile, names: &[String]) -> Vec<LockUpdate> {
+    let selected = |name: &str| names.is_empty() || names.iter().any(|n| n == name);
+    let packages = selected_packages(config, system_info, &selected);
+    let mut updates = runtime_updates(config, lockfile, &selected);
+    updates.extend(package_updates(config, &packages, lockfile, installable_version));
+    updates
+}
+
+/// Selected packages resolved to their preferred method on a system
+pub(super) fn selected_packages(config: &Config, system_info: &SystemInfo, selected: &dyn Fn(&str) -> bool) -> Vec<Package> {
+    let ids: Vec<String> = config.get_all_packages().into_iter().filter(|id| selected(id)).collect();
+    plan_packages(&ids, system_info, &config.settings)
+}
+
+/// Updates for every constraint of the selected runtimes
+pub(super) fn runtime_updates(config: &Config, lockfile: &Lockfile, selected: &dyn Fn(&str) -> bool) -> Vec<LockUpdate> {
+    let mut names: Vec<&String> = config.runtimes.keys().filter(|name| selected(name)).collect();
+    names.sort();
+
+    let mut updates = Vec::new();
+    for name in names {
+        let spec = &config.runtimes[name];
+        let manager = manager_name_for(name, spec);
+        let mut constraints: BTreeSet<String> = spec.get_versions().into_iter().collect();
+        constraints.insert(spec.get_default_version());
+
+        for constraint in constraints {
+            match VersionResolver::resolve(name, &constraint, Some(&manager), spec.get_distribution()) {
+                Ok(latest) => updates.push(LockUpdate {
+                    current: lockfile.locked_version(name, &constraint).map(String::from),
+                    entry: LockEntry::Runtime {
+                        name: name.clone(),
+                        constraint,
+                        manager: manager.clone(),
+                    },
+                    latest,
+                }),
+                Err(e) => eprintln!("  {} Could not resolve {} {}: {}", "⚠".yellow(), name, constraint, e),
+            }
+        }
+    }
+    updates
+}
+
+/// Updates for the packages devstrap installs itself
+pub(super) fn package_updates(config: &Config, packages: &[Package], lockfile: &Lockfile, latest: LatestVersion) -> Vec<LockUpdate> {
+    packages
+        .iter()
+        .filter_map(|package| {
+            let (method, name) = package_lock_key(package)?;
+            let Some(latest) = url_version(package, config).or_else(|| latest(package, &name)) else {
+                eprintln!("  {} Could not find the latest version of {}", "⚠".yellow(), package.id);
+                return None;
+            };
+            Some(LockUpdate {
+                current: lockfile.locked_package_version(&package.id, &method, &name).map(String::from),
+                entry: LockEntry::Package {
+                    id: package.id.clone(),
+                    method,
+                    name,
+                },
+                latest,
+            })
+        })
+        .collect()
+}
+
+/// Version a url package installs
+///
+/// Url packages have no index to query, so they follow their pin or source version.
+fn url_version(package: &Package, config: &Config) -> Option<String> {
+    if package.preferred_method != InstallMethod::Url {
+        return None;
+    }
+    config
+        .get_package_version(&package.id)
+        .map(String::from)
+        .or_else(|| package.config.url.as_ref()?.version.clone())
+}
+
+/// Version the package's method would install on this machine
+fn installable_version(package: &Package, name: &str) -> Option<String> {
+    available_version(&package.preferred_method, name, &package.config)
+}
@@ -0,0 +1,114 @@
+//! Installing and locking the entries `devstrap update` moved
+
+use super::lock_update::{LockEntry, LockUpdate};
+use super::orchestration::prepare_packages;
+use crate::config::StateFile;
+use crate::domain::runtime::RuntimeSpec;
+use crate::domain::{Config, InstallMethod, Lockfile, SystemInfo};
+use crate::service::package_manager::installer::update_package;
+use std::collections::BTreeSet;
+
+/// Install the new version of each changed package and lock it
+///
+/// Returns the packages that failed; their lock entries are left alone.
+pub fn apply_package_updates(
+    config: &Config,
+    system_info: &SystemInfo,
+    updates: &[LockUpdate],
+    lockfile: &mut Lockfile,
+    state: &mut StateFile,
+    dry_run: bool,
+) -> Vec<(String, anyhow::Error)> {
+    let mut errors = Vec::new();
+    for update in updates.iter().filter(|update| update.is_change()) {
+        let LockEntry::Package { id, method, name } = &update.entry else {
+            continue;
+        };
+        let Some(mut package) = prepare_packages(std::slice::from_ref(id), system_info, &config.settings).pop() else {
+            continue;
+        };
+        package.locked_version = Some(update.latest.clone());
+        if package.preferred_method == InstallMethod::Url {
+            package.version = Some(update.latest.clone());
+        }
+
+        match update_package(&package, system_info.default_package_manager, dry_run) {
+            Ok(()) if !dry_run => {
+                lockfile.lock_package(id, method, name, &update.latest);
+                state.add_package(id.clone(), &package.preferred_method, Some(update.latest.clone()));
+            }
+            Ok(()) => {}
+            Err(e) => errors.push((id.clone(), e)),
+        }
+    }
+    errors
+}
+
+/// Lock the new version of each changed package without installing it
+pub fn lock_package_updates(updates: &[LockUpdate], lockfile: &mut Lockfile) {
+    for update in updates.iter().filter(|update| update.is_change()) {
+        if let LockEntry::Package { id, method, name } = &update.entry {
+            lockfile.lock_package(id, method, name, &update.latest);
+        }
+    }
+}
+
+/// Lock the new version of each changed runtime constraint without installing it
+pub fn lock_runtime_updates(updates: &[LockUpdate], lockfile: &mut Lockfile) {
+    for update in updates.iter().filter(|update| update.is_change()) {
+        if let LockEntry::Runtime { name, constraint, manager } = &update.entry {
+            lockfile.lock_version(name, constraint, &update.latest, manager);
+        }
+    }
+}
+
+/// Runtimes with a changed constraint, for the coordinator to install
+#[must_use]
+pub fn updated_runtimes(updates: &[LockUpdate]) -> BTreeSet<String> {
+    updates
+        .iter()
+        .filter(|update| update.is_change())
+        .filter_map(|update| match &update.entry {
+            LockEntry::Runtime { name, .. } => Some(name.clone()),
+            LockEntry::Package { .. } => None,
+        })
+        .collect()
+}
+
+/// Config narrowed to the given runtimes and the runtimes they require
+///
+/// System languages are left out; `update` only reinstalls what moved.
+#[must_use]
+pub fn runtime_update_config(config: &Config, runtimes: &BTreeSet<String>) -> Config {
+    let mut keep = runtimes.clone();
+    let mut pending: Vec<String> = runtimes.iter().cloned().collect();
+    while let Some(name) = pending.pop() {
+        let required = config.runtimes.get(&name).and_then(RuntimeSpec::get_requires);
+        if let Some(required) = required.filter(|required| keep.insert(required.clone())) {
+            pending.push(required);
+        }
+    }
+
+    let mut narrowed = config.clone();
+    narrowed.runtimes.retain(|name, _| keep.contains(name));
+    narrowed.system_languages.clear();
+    narrowed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::update::unknown_update_names;
+
+    #[test]
+    fn test_runtime_update_config_keeps_requirements() {
+        let config: Config = toml::from_str(
+            "[runtimes]\nnode = \"lts\"\npython = \"3.12\"\ntypescript = { version = \"latest\", requires = \"node\" }\n",
+        )
+        .unwrap();
+
+        let narrowed = runtime_update_config(&config, &BTreeSet::from(["typescript".to_string()]));
+        assert_eq!(narrowed.runtime_install_order().unwrap(), ["node", "typescript"]);
+        assert_eq!(unknown_update_names(&config, &["node".to_string(), "deno".to_string()]), ["deno"]);
+    }
+}
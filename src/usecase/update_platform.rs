@@ -0,0 +1,104 @@
+//! Locking another platform's versions with `devstrap update --platform`
+
+use super::lock_update::LockUpdate;
+use super::update::{package_updates, runtime_updates, selected_packages};
+use crate::domain::{Arch, Config, Distro, Lockfile, Os, Package, PackageManager, SystemInfo};
+use crate::service::package_manager::versions::registry_version;
+use crate::service::runtime::{manager_name_for, require_manager, RuntimeManager};
+use colored::Colorize;
+
+/// Re-resolve the named runtimes and packages for another platform
+///
+/// Runtimes are resolved with this machine's managers, except those whose
+/// versions are builds for this machine's platform (SDKMAN, dotnet, ghcup).
+/// Packages come from registries any machine can read; Linux system packages
+/// are skipped since their names depend on the distribution. Whatever is
+/// skipped is locked by the first sync on that platform. The lockfile must
+/// already point at the platform.
+#[must_use]
+pub fn plan_platform_updates(config: &Config, os: Os, arch: Arch, lockfile: &Lockfile, names: &[String]) -> Vec<LockUpdate> {
+    let selected = |name: &str| names.is_empty() || names.iter().any(|n| n == name);
+    let system_info = platform_system_info(os, arch);
+    let (packages, skipped): (Vec<_>, Vec<_>) = selected_packages(config, &system_info, &selected)
+        .into_iter()
+        .partition(|package| os == Os::MacOs || !is_distro_specific(package));
+    let mut host_specific: Vec<&String> =
+        config.runtimes.keys().filter(|name| selected(name) && is_host_specific(config, name)).collect();
+    host_specific.sort();
+    for name in host_specific.into_iter().chain(skipped.iter().map(|package| &package.id)) {
+        println!("  {} {} is locked by the first sync on this platform", "⚠".yellow(), name);
+    }
+
+    let portable = |name: &str| selected(name) && !is_host_specific(config, name);
+    let mut updates = runtime_updates(config, lockfile, &portable);
+    updates.extend(package_updates(config, &packages, lockfile, registry_package_version));
+    updates
+}
+
+/// Whether a runtime's manager only resolves builds for this machine's platform
+fn is_host_specific(config: &Config, name: &str) -> bool {
+    config
+        .runtimes
+        .get(name)
+        .and_then(|spec| require_manager(&manager_name_for(name, spec)).ok())
+        .is_some_and(RuntimeManager::lists_host_builds)
+}
+
+/// Stand-in for a machine of another platform with the usual package managers
+#[must_use]
+pub fn platform_system_info(os: Os, arch: Arch) -> SystemInfo {
+    let language_managers = [PackageManager::Cargo, PackageManager::Npm, PackageManager::Pipx, PackageManager::Uv];
+    let default_package_manager = (os == Os::MacOs).then_some(PackageManager::Brew);
+    SystemInfo {
+        os,
+        distro: Distro::Unknown,
+        arch,
+        default_package_manager,
+        available_package_managers: default_package_manager.into_iter().chain(language_managers).collect(),
+        is_wsl: false,
+        is_apple_silicon: os == Os::MacOs && arch == Arch::Arm64,
+    }
+}
+
+/// Whether a package would come from a Linux distribution's own repositories
+fn is_distro_specific(package: &Package) -> bool {
+    package
+        .config
+        .available_methods()
+        .iter()
+        .any(|method| matches!(method.as_str(), "apt" | "dnf" | "yum" | "pacman" | "aur"))
+}
+
+/// Version in the package method's public registry
+fn registry_package_version(package: &Package, name: &str) -> Option<String> {
+    registry_version(&package.preferred_method, name, &package.config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::package_locks::package_lock_key;
+
+    #[test]
+    fn test_platform_packages() {
+        let config: Config = toml::from_str("packages = [\"ripgrep\", \"ruff\"]\n").unwrap();
+
+        let macos = selected_packages(&config, &platform_system_info(Os::MacOs, Arch::Arm64), &|_| true);
+        let methods: Vec<_> = macos.iter().filter_map(package_lock_key).collect();
+        assert_eq!(methods, [("brew".to_string(), "ripgrep".to_string()), ("uv".to_string(), "ruff".to_string())]);
+
+        let linux = selected_packages(&config, &platform_system_info(Os::Linux, Arch::X86_64), &|_| true);
+        let portable: Vec<_> = linux.iter().filter(|p| !is_distro_specific(p)).map(|p| p.id.as_str()).collect();
+        assert_eq!(portable, ["ruff"]);
+    }
+
+    #[test]
+    fn test_host_specific_runtimes() {
+        let config: Config = toml::from_str("[runtimes]\njava = \"21\"\ndotnet = \"8.0\"\nnode = \"lts\"\n").unwrap();
+        let cases = [("java", true), ("dotnet", true), ("node", false), ("ruby", false)];
+
+        for (name, host_specific) in cases {
+            assert_eq!(is_host_specific(&config, name), host_specific, "{name}");
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Tests for bootstrapping version managers from a local HTTP server
+
+mod common;
+
+use common::serve;
+use devstrap::service::runtime::bootstrap::{bootstrap_manager, run_installer, InstallerScript};
+use devstrap::service::runtime::manager::require_manager;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tempfile::tempdir;
+
+const SCRIPT: &str = "#!/bin/sh\nset -e\n[ \"$1\" = \"-y\" ] || exit 3\nmkdir -p \"$DEST\"\nprintf '#!/bin/sh\\n' > \"$DEST/tool\"\n";
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn installer_server() -> String {
+    serve(HashMap::from([
+        ("/install.sh".to_string(), SCRIPT.as_bytes().to_vec()),
+        ("/noop.sh".to_string(), b"#!/bin/sh\nexit 0\n".to_vec()),
+    ]))
+}
+
+#[test]
+fn test_installer_runs_non_interactively_and_pins_checksum() {
+    let base = installer_server();
+    let dest = tempdir().unwrap();
+    let script = InstallerScript::new(format!("{base}/install.sh"), "sh", dest.path().join("tool"))
+        .args(&["-y"])
+        .env("DEST", dest.path().to_string_lossy());
+
+    let sha256 = run_installer("tool", &script, None).unwrap();
+
+    assert_eq!(sha256, sha256_hex(SCRIPT.as_bytes()));
+    assert!(dest.path().join("tool").is_file());
+    assert!(run_installer("tool", &script, Some(&sha256)).is_ok());
+}
+
+#[test]
+fn test_installer_rejects_checksum_mismatch() {
+    let base = installer_server();
+    let dest = tempdir().unwrap();
+    let script = InstallerScript::new(format!("{base}/install.sh"), "sh", dest.path().join("tool"))
+        .args(&["-y"])
+        .env("DEST", dest.path().to_string_lossy());
+
+    let error = run_installer("tool", &script, Some(&"0".repeat(64))).unwrap_err();
+
+    assert!(error.to_string().contains("Checksum mismatch"));
+    assert!(!dest.path().join("tool").exists());
+}
+
+#[test]
+fn test_installer_must_provide_binary() {
+    let base = installer_server();
+    let dest = tempdir().unwrap();
+    let script = InstallerScript::new(format!("{base}/noop.sh"), "sh", dest.path().join("tool"));
+
+    let error = run_installer("tool", &script, None).unwrap_err();
+
+    assert!(error.to_string().contains("was not created"));
+}
+
+#[test]
+fn test_installer_kept_as_manager() {
+    let base = installer_server();
+    let dest = tempdir().unwrap();
+    let target = dest.path().join("bin/install.sh");
+    let script = InstallerScript::new(format!("{base}/install.sh"), "bash", target.clone()).keep_as(target.clone());
+
+    run_installer("tool", &script, None).unwrap();
+
+    assert_eq!(std::fs::read_to_string(&target).unwrap(), SCRIPT);
+    assert!(!dest.path().join("tool").exists());
+}
+
+#[test]
+fn test_unpinned_installer_is_refused() {
+    let base = installer_server();
+    let manager = require_manager("zvm").unwrap();
+
+    let error = bootstrap_manager(manager, Some(&format!("{base}/noop.sh")), None, false).unwrap_err();
+
+    assert!(error.to_string().contains("has no pinned sha256"), "{error}");
+}
@@ -0,0 +1,34 @@
+//! Helpers shared by integration tests
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// Serve fixed responses by path until the test process exits
+#[allow(clippy::unwrap_used)]
+pub fn serve(routes: HashMap<String, Vec<u8>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request_line = String::new();
+            if BufReader::new(&stream).read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+            let (status, body) = routes
+                .get(path)
+                .map_or(("404 Not Found", &[][..]), |body| ("200 OK", body.as_slice()));
+            let header = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(header.as_bytes()).and_then(|()| stream.write_all(body));
+        }
+    });
+
+    base
+}
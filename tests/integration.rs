@@ -17,14 +17,19 @@ fn test_system_detection() {
         devstrap::Os::MacOs | devstrap::Os::Linux
     ));
 
+    // Should have at least one available package manager
+    assert!(!system_info.available_package_managers.is_empty());
+}
+
+#[test]
+fn test_architecture_detection() {
+    let system_info = SystemInfo::detect().expect("Failed to detect system info");
+
     // Should detect some architecture
     assert!(matches!(
         system_info.arch,
         devstrap::Arch::X86_64 | devstrap::Arch::Arm64 | devstrap::Arch::Armv7
     ));
-
-    // Should have at least one available package manager
-    assert!(!system_info.available_package_managers.is_empty());
 }
 
 #[test]
@@ -66,6 +71,473 @@ packages = ["ripgrep", "missing_package_that_doesnt_exist"]
     assert!(error.to_string().contains("not a supported package"));
 }
 
+const REPOSITORIES_CONFIG: &str = r#"
+packages = ["ripgrep"]
+
+[repositories.neovim]
+type = "ppa"
+ppa = "neovim-ppa/unstable"
+
+[repositories.docker]
+type = "apt"
+url = "https://download.docker.com/linux/ubuntu"
+key = "https://download.docker.com/linux/ubuntu/gpg"
+components = ["stable"]
+    "#;
+
+#[test]
+fn test_config_repositories() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, REPOSITORIES_CONFIG).unwrap();
+
+    let config = Config::from_file(&config_path).expect("Failed to parse config");
+    assert_eq!(config.repositories.len(), 2);
+    assert_eq!(config.repositories["neovim"].describe(), "ppa:neovim-ppa/unstable");
+}
+
+#[test]
+fn test_config_apt_repository_defaults() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, REPOSITORIES_CONFIG).unwrap();
+
+    let config = Config::from_file(&config_path).expect("Failed to parse config");
+    match &config.repositories["docker"] {
+        devstrap::domain::Repository::Apt { suite, components, .. } => {
+            assert_eq!((suite.as_str(), components.as_slice()), ("{codename}", ["stable".to_string()].as_slice()));
+        }
+        other => panic!("Unexpected repository: {other:?}"),
+    }
+}
+
+#[test]
+fn test_config_include() {
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir(temp_dir.path().join("team")).unwrap();
+    fs::write(temp_dir.path().join("team/base.toml"), "packages = [\"git\", \"curl\"]\n[runtimes]\nnode = \"lts\"\n").unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, "include = [\"team/base.toml\"]\nremove = [\"curl\"]\npackages = [\"ripgrep\"]\n").unwrap();
+
+    let config = Config::from_file(&config_path).unwrap();
+    assert_eq!(config.get_all_packages(), ["git", "ripgrep"]);
+    assert_eq!(config.runtimes["node"].get_default_version(), "lts");
+
+    fs::write(&config_path, "include = [\"missing.toml\"]\n").unwrap();
+    let error = format!("{:#}", Config::from_file(&config_path).unwrap_err());
+    assert!(error.contains("missing.toml (included from"), "{error}");
+}
+
+#[test]
+fn test_config_interpolation() {
+    let config_content = r#"
+[vars]
+node = "20"
+mirror = "https://mirror.example.com/${os}"
+
+[runtimes]
+node = "${var:node}"
+
+[settings.installers.fnm]
+url = "${var:mirror}/fnm-install.sh"
+    "#;
+
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, config_content).unwrap();
+    let system_info = SystemInfo::detect().unwrap();
+
+    let config = Config::from_file(&config_path).unwrap();
+    assert_eq!(config.runtimes["node"].get_default_version(), "20");
+    let url = config.settings.installers["fnm"].url.clone().unwrap();
+    assert_eq!(url, format!("https://mirror.example.com/{}/fnm-install.sh", system_info.os.as_str()));
+
+    fs::write(&config_path, "[runtimes]\nnode = \"${var:node_version}\"\n").unwrap();
+    let error = format!("{:#}", Config::from_file(&config_path).unwrap_err());
+    assert!(error.contains("Undefined variable `${var:node_version}` in runtimes.node"), "{error}");
+    assert!(error.contains("config.toml"), "{error}");
+}
+
+#[test]
+fn test_config_validation_bad_repository() {
+    let config_content = r#"
+[repositories.tools]
+type = "copr"
+copr = "not-a-project"
+    "#;
+
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, config_content).unwrap();
+
+    let error = Config::from_file(&config_path).unwrap_err();
+    assert!(error.to_string().contains("owner/name"));
+}
+
+#[test]
+fn test_config_validation_bad_runtime_constraint() {
+    let config_content = r#"
+[runtimes]
+python = "3.11"
+node = { versions = ["^20", "latest-1"], default = ">=20,<22" }
+    "#;
+
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, config_content).unwrap();
+    assert!(Config::from_file(&config_path).is_ok());
+
+    fs::write(&config_path, "[runtimes]
+python = \"^three\"\n").unwrap();
+    let error = format!("{:#}", Config::from_file(&config_path).unwrap_err());
+    assert!(error.contains("runtime 'python'"));
+    assert!(error.contains("^three"));
+}
+
+#[test]
+fn test_config_validation_unknown_runtime_manager() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, "[runtimes]\npython = { version = \"3.12\", manager = \"asdf\" }\n").unwrap();
+
+    let error = format!("{:#}", Config::from_file(&config_path).unwrap_err());
+    assert!(error.contains("runtime 'python'"));
+    assert!(error.contains("Unknown runtime manager 'asdf'"));
+}
+
+#[test]
+fn test_config_validation_installer_overrides() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let cases = [
+        ("rustup", "url = \"http://127.0.0.1:8080/rustup.sh\"", true),
+        ("rustup", "sha256 = \"abc\"", false),
+        ("asdf", "url = \"http://127.0.0.1:8080/asdf.sh\"", false),
+    ];
+
+    for (manager, entry, valid) in cases {
+        fs::write(&config_path, format!("[settings.installers.{manager}]\n{entry}\n")).unwrap();
+        assert_eq!(Config::from_file(&config_path).is_ok(), valid, "{manager}: {entry}");
+    }
+}
+
+#[test]
+fn test_config_rust_toolchain_options() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let cases = [
+        (
+            "rust",
+            "components = [\"clippy\", \"rustfmt\"]\ntargets = [\"wasm32-unknown-unknown\"]\nprofile = \"minimal\"",
+            true,
+        ),
+        ("rust", "profile = \"tiny\"", false),
+        ("python", "components = [\"clippy\"]", false),
+    ];
+
+    for (runtime, options, valid) in cases {
+        fs::write(&config_path, format!("[runtimes.{runtime}]\nversion = \"stable\"\n{options}\n")).unwrap();
+        assert_eq!(Config::from_file(&config_path).is_ok(), valid, "{runtime}: {options}");
+    }
+}
+
+#[test]
+fn test_config_runtime_global_packages() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let cases = [
+        ("node", r#"["typescript@5", "@angular/cli"]"#, true),
+        ("python", r#"["black>=24", "httpie"]"#, true),
+        ("go", r#"["golang.org/x/tools/gopls@latest"]"#, true),
+        ("node", r#"["typescript@"]"#, false),
+        ("java", r#"["lombok"]"#, false),
+    ];
+
+    for (runtime, packages, valid) in cases {
+        fs::write(&config_path, format!("[runtimes.{runtime}]\nversion = \"latest\"\nglobal_packages = {packages}\n")).unwrap();
+        assert_eq!(Config::from_file(&config_path).is_ok(), valid, "{runtime}: {packages}");
+    }
+}
+
+#[test]
+fn test_config_java_distribution() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let cases = [
+        ("java", "distribution = \"temurin\"", true),
+        ("java", "distribution = \"corretto\"\nmanager = \"sdkman\"", true),
+        ("java", "distribution = \"openj9\"", false),
+        ("java", "distribution = \"zulu\"\nmanager = \"mise\"", false),
+        ("kotlin", "distribution = \"zulu\"", false),
+    ];
+
+    for (runtime, options, valid) in cases {
+        fs::write(&config_path, format!("[runtimes.{runtime}]\nversions = [\"17\", \"21\"]\n{options}\n")).unwrap();
+        assert_eq!(Config::from_file(&config_path).is_ok(), valid, "{runtime}: {options}");
+    }
+}
+
+#[test]
+fn test_lockfile_distribution_change_drops_locks() {
+    let mut lockfile = devstrap::Lockfile::default();
+    lockfile.lock_distribution("java", Some("temurin"));
+    lockfile.set_runtime("java", "21", "21.0.5-tem", "sdkman");
+    lockfile.lock_distribution("java", Some("temurin"));
+    assert_eq!(lockfile.locked_version("java", "21"), Some("21.0.5-tem"));
+
+    lockfile.lock_distribution("java", Some("zulu"));
+    assert_eq!(lockfile.locked_version("java", "21"), None);
+}
+
+#[test]
+fn test_lockfile_package_mismatches() {
+    let system_info = SystemInfo {
+        os: devstrap::Os::Linux,
+        distro: devstrap::Distro::Ubuntu,
+        arch: devstrap::Arch::X86_64,
+        default_package_manager: Some(devstrap::PackageManager::Apt),
+        available_package_managers: vec![devstrap::PackageManager::Apt],
+        is_wsl: false,
+        is_apple_silicon: false,
+    };
+    let config = Config {
+        packages: vec![vec!["ripgrep".to_string(), "bat".to_string()]],
+        ..Config::default()
+    };
+
+    let temp_dir = tempdir().unwrap();
+    let lockfile_path = temp_dir.path().join("devstrap.lock");
+    let mut lockfile = devstrap::Lockfile::default();
+    lockfile.lock_package("ripgrep", "apt-get", "ripgrep", "14.1.0-1");
+    lockfile.lock_package("fzf", "apt-get", "fzf", "0.44.1-1");
+    lockfile.save(&lockfile_path).unwrap();
+
+    let lockfile = devstrap::Lockfile::from_file(&lockfile_path).unwrap();
+    assert_eq!(lockfile.locked_package_version("ripgrep", "apt-get", "ripgrep"), Some("14.1.0-1"));
+    assert_eq!(lockfile.locked_package_version("ripgrep", "cargo", "ripgrep"), None);
+
+    let mut mismatches = devstrap::usecase::package_lock_mismatches(&config, &system_info, &lockfile);
+    mismatches.sort();
+    assert_eq!(mismatches, ["bat is not locked", "fzf is locked but no longer in config"]);
+
+    let mut lockfile = lockfile;
+    lockfile.lock_package("ripgrep", "cargo", "ripgrep", "14.1.0");
+    lockfile.lock_package("bat", "apt-get", "bat", "0.24.0-1");
+    lockfile.platform_lock_mut().packages.remove("fzf");
+    let mismatches = devstrap::usecase::package_lock_mismatches(&config, &system_info, &lockfile);
+    assert_eq!(mismatches, ["ripgrep is locked as cargo ripgrep but config installs apt-get ripgrep"]);
+}
+
+/// Save a lockfile with a macOS and a Linux section
+fn save_two_platforms(path: &std::path::Path) -> devstrap::Result<()> {
+    let mut lockfile = devstrap::Lockfile::default().for_platform("macos-arm64");
+    lockfile.set_runtime("java", "21", "21.0.5-tem", "sdkman");
+    lockfile.lock_package("ripgrep", "brew", "ripgrep", "14.1.1");
+    let mut lockfile = lockfile.for_platform("linux-x86_64");
+    lockfile.set_runtime("java", "21", "21.0.5-tem", "sdkman");
+    lockfile.lock_package("ripgrep", "cargo", "ripgrep", "14.1.0");
+    lockfile.save(path)
+}
+
+#[test]
+fn test_lockfile_platform_sections() {
+    let temp_dir = tempdir().unwrap();
+    let lockfile_path = temp_dir.path().join("devstrap.lock");
+    save_two_platforms(&lockfile_path).unwrap();
+
+    let contents = fs::read_to_string(&lockfile_path).unwrap();
+    assert!(contents.contains("schema_version = 1"));
+    assert!(contents.contains("[platforms.macos-arm64.packages.ripgrep]"));
+}
+
+#[test]
+fn test_lockfile_platform_sections_reload() {
+    let temp_dir = tempdir().unwrap();
+    let lockfile_path = temp_dir.path().join("devstrap.lock");
+    save_two_platforms(&lockfile_path).unwrap();
+
+    let lockfile = devstrap::Lockfile::from_file(&lockfile_path).unwrap();
+    let macos = lockfile.clone().for_platform("macos-arm64");
+    assert_eq!(macos.locked_package_version("ripgrep", "brew", "ripgrep"), Some("14.1.1"));
+    let linux = lockfile.for_platform("linux-x86_64");
+    assert_eq!(linux.locked_package_version("ripgrep", "cargo", "ripgrep"), Some("14.1.0"));
+    assert_eq!(linux.locked_version("java", "21"), Some("21.0.5-tem"));
+}
+
+#[test]
+fn test_lockfile_clear_platform_keeps_others() {
+    let temp_dir = tempdir().unwrap();
+    let lockfile_path = temp_dir.path().join("devstrap.lock");
+    save_two_platforms(&lockfile_path).unwrap();
+
+    let mut lockfile = devstrap::Lockfile::from_file(&lockfile_path).unwrap().for_platform("linux-x86_64");
+    lockfile.lock_installer("fnm", "https://fnm.vercel.app/install", &"0".repeat(64));
+    lockfile.clear_platform();
+    assert_eq!(lockfile.locked_version("java", "21"), None);
+    assert!(lockfile.installer_sha256("fnm").is_some());
+    let macos = lockfile.for_platform("macos-arm64");
+    assert_eq!(macos.locked_package_version("ripgrep", "brew", "ripgrep"), Some("14.1.1"));
+}
+
+#[test]
+fn test_parse_platform() {
+    assert_eq!(devstrap::domain::parse_platform("linux-x86_64"), Some((devstrap::Os::Linux, devstrap::Arch::X86_64)));
+    assert_eq!(devstrap::domain::parse_platform("windows-x86_64"), None);
+}
+
+#[test]
+fn test_lockfile_staleness() {
+    let hash = devstrap::domain::config_hash(b"packages = [\"ripgrep\"]");
+    let mut lockfile = devstrap::Lockfile::default();
+    assert_eq!(lockfile.staleness(&hash), None);
+
+    lockfile.config_hash = Some(hash.clone());
+    lockfile.lock_version("node", "20", "20.11.0", "fnm");
+    assert_eq!(lockfile.staleness(&hash), None);
+
+    let changed = devstrap::domain::config_hash(b"packages = [\"bat\"]");
+    assert!(lockfile.staleness(&changed).unwrap().contains("config changed"));
+
+    let other = lockfile.for_platform("linux-armv7");
+    assert_eq!(other.staleness(&hash).unwrap(), "devstrap.lock has no locks for linux-armv7");
+}
+
+#[test]
+fn test_config_refresh_after() {
+    let config_content = r#"
+[settings]
+refresh_after = "7d"
+
+[runtimes]
+node = "lts"
+python = { version = "3.12", refresh_after = "12h" }
+    "#;
+
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, config_content).unwrap();
+    let config = Config::from_file(&config_path).unwrap();
+    assert_eq!(config.refresh_after_for("node").unwrap(), devstrap::domain::RefreshAfter::parse("1w").ok());
+    assert_eq!(config.refresh_after_for("python").unwrap(), devstrap::domain::RefreshAfter::parse("12h").ok());
+
+    fs::write(&config_path, "[runtimes]\nnode = { version = \"lts\", refresh_after = \"7 days\" }\n").unwrap();
+    let error = format!("{:#}", Config::from_file(&config_path).unwrap_err());
+    assert!(error.contains("runtime 'node'"));
+    assert!(error.contains("7 days"));
+}
+
+#[test]
+fn test_lockfile_reinstall_keeps_resolved_at() {
+    let mut lockfile = devstrap::Lockfile::default();
+    lockfile.set_runtime("node", "lts", "20.11.0", "fnm");
+    let resolved_at = lockfile.resolved_at("node").map(String::from);
+
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    lockfile.set_runtime("node", "lts", "20.11.0", "fnm");
+    assert_eq!(lockfile.resolved_at("node").map(String::from), resolved_at);
+
+    lockfile.set_runtime("node", "lts", "20.18.0", "fnm");
+    assert_ne!(lockfile.resolved_at("node").map(String::from), resolved_at);
+}
+
+#[test]
+fn test_lockfile_restore_runtime() {
+    let mut previous = devstrap::Lockfile::default();
+    previous.set_runtime("node", "lts", "20.11.0", "fnm");
+    let mut lockfile = previous.clone();
+    lockfile.lock_version("node", "lts", "22.11.0", "fnm");
+    lockfile.lock_version("python", "3.12", "3.12.7", "mise");
+
+    lockfile.restore_runtime("node", &previous);
+    lockfile.restore_runtime("python", &previous);
+    assert_eq!(lockfile.locked_version("node", "lts"), Some("20.11.0"));
+    assert_eq!(lockfile.locked_version("python", "3.12"), None);
+}
+
+#[test]
+fn test_state_file_recovers_from_backup() {
+    let temp_dir = tempdir().unwrap();
+    let state_path = temp_dir.path().join("devstrap.state");
+    let mut state = devstrap::config::StateFile::default();
+    state.add_tap("homebrew/cask-fonts".to_string());
+    state.save(&state_path).unwrap();
+    state.add_tap("hashicorp/tap".to_string());
+    state.save(&state_path).unwrap();
+
+    fs::write(&state_path, "taps = [").unwrap();
+    let recovered = devstrap::config::StateFile::load(&state_path).unwrap();
+    assert_eq!(recovered.taps.len(), 1);
+
+    fs::remove_file(temp_dir.path().join("devstrap.state.bak.1")).unwrap();
+    assert!(devstrap::config::StateFile::load(&state_path).is_err());
+}
+
+#[test]
+fn test_config_node_corepack() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let cases = [
+        ("node", "corepack = true\npackage_managers = { pnpm = \"9\", yarn = \"stable\" }", true),
+        ("node", "corepack = true\nmanager = \"mise\"", true),
+        ("node", "package_managers = { pnpm = \"9\" }", false),
+        ("node", "corepack = true\npackage_managers = { bun = \"1\" }", false),
+        ("python", "corepack = true", false),
+    ];
+
+    for (runtime, options, valid) in cases {
+        fs::write(&config_path, format!("[runtimes.{runtime}]\nversions = [\"20\", \"22\"]\n{options}\n")).unwrap();
+        assert_eq!(Config::from_file(&config_path).is_ok(), valid, "{runtime}: {options}");
+    }
+}
+
+#[test]
+fn test_config_runtime_requires_order() {
+    let config_content = r#"
+[runtimes]
+typescript = { version = "latest", requires = "node" }
+node = "lts"
+    "#;
+
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, config_content).unwrap();
+    let config = Config::from_file(&config_path).unwrap();
+    assert_eq!(config.runtime_install_order().unwrap(), ["node", "typescript"]);
+
+    fs::write(&config_path, "[runtimes]\ntypescript = { requires = \"node\" }\n").unwrap();
+    let error = format!("{:#}", Config::from_file(&config_path).unwrap_err());
+    assert!(error.contains("'node', which is not configured"));
+}
+
+#[test]
+fn test_lockfile_records_global_packages() {
+    let mut lockfile = devstrap::Lockfile::default();
+    lockfile.set_runtime("node", "20", "20.11.0", "fnm");
+    lockfile.lock_global_packages(
+        "node",
+        "20.11.0",
+        [("typescript@5".to_string(), "5.4.5".to_string())].into(),
+    );
+
+    assert_eq!(lockfile.locked_global_package("node", "20.11.0", "typescript@5"), Some("5.4.5"));
+    assert_eq!(lockfile.locked_global_package("node", "18.19.0", "typescript@5"), None);
+
+    lockfile.lock_global_packages("node", "20.11.0", std::collections::BTreeMap::new());
+    assert_eq!(lockfile.locked_global_package("node", "20.11.0", "typescript@5"), None);
+}
+
+#[test]
+fn test_lockfile_records_constraints() {
+    let mut lockfile = devstrap::Lockfile::default();
+    lockfile.lock_version("python", "3.11", "3.11.9", "mise");
+    lockfile.set_runtime("python", "3.12", "3.12.4", "mise");
+
+    assert_eq!(lockfile.locked_version("python", "3.11"), Some("3.11.9"));
+    assert_eq!(lockfile.locked_version("python", "3.12"), Some("3.12.4"));
+    assert_eq!(lockfile.get_runtime_version("python"), Some("3.12.4"));
+    assert!(lockfile.needs_resolution("python", "3.13"));
+}
+
 #[test]
 fn test_dry_run_installation() {
     let config_content = r#"
@@ -102,6 +574,7 @@ fn test_package_name_for_method() {
         apt: None,
         pacman: None,
         dnf: None,
+        ..PackageConfig::default()
     };
 
     assert_eq!(package.name_for_method("cargo"), Some("ripgrep"));
@@ -124,6 +597,7 @@ fn test_available_methods() {
         apt: None,
         pacman: None,
         dnf: None,
+        ..PackageConfig::default()
     };
 
     let methods = package.available_methods();
@@ -0,0 +1,63 @@
+//! Tests for pinning rustup channels against a local dist server
+
+mod common;
+
+use common::serve;
+use devstrap::domain::VersionConstraint;
+use devstrap::service::runtime::manager::rustup::resolve_with_server;
+use std::collections::HashMap;
+
+fn manifest(date: &str, version: &str) -> Vec<u8> {
+    format!(
+        "manifest-version = \"2\"\ndate = \"{date}\"\n\n[pkg.rust]\nversion = \"{version} (abcdef123 {date})\"\n"
+    )
+    .into_bytes()
+}
+
+fn dist_server() -> String {
+    let channels = [
+        ("stable", manifest("2026-09-18", "1.90.0")),
+        ("beta", manifest("2026-10-12", "1.91.0-beta.5")),
+        ("nightly", manifest("2026-10-17", "1.92.0-nightly")),
+        ("1.90", manifest("2026-09-18", "1.90.0")),
+        ("1.89", manifest("2026-08-07", "1.89.0")),
+        ("1.88", manifest("2026-06-26", "1.88.0")),
+        ("1.85", manifest("2025-03-18", "1.85.1")),
+    ];
+    serve(
+        channels
+            .into_iter()
+            .map(|(channel, body)| (format!("/dist/channel-rust-{channel}.toml"), body))
+            .collect::<HashMap<_, _>>(),
+    )
+}
+
+#[test]
+fn test_rustup_channels_resolve_to_concrete_toolchains() {
+    let server = dist_server();
+    let cases = [
+        ("stable", "1.90.0"),
+        ("latest", "1.90.0"),
+        ("beta", "beta-2026-10-12"),
+        ("nightly", "nightly-2026-10-17"),
+        ("latest-2", "1.88.0"),
+        ("<1.89", "1.88.0"),
+        ("~1.85", "1.85.1"),
+        ("1.82.0", "1.82.0"),
+        ("nightly-2026-01-01", "nightly-2026-01-01"),
+    ];
+
+    for (requested, expected) in cases {
+        let constraint = VersionConstraint::parse(requested).unwrap();
+        let resolved = resolve_with_server(&constraint, &server).unwrap();
+        assert_eq!(resolved, expected, "resolving {requested}");
+    }
+}
+
+#[test]
+fn test_rustup_rejects_unknown_channels() {
+    let server = dist_server();
+    let constraint = VersionConstraint::parse("lts").unwrap();
+
+    assert!(resolve_with_server(&constraint, &server).is_err());
+}
@@ -0,0 +1,142 @@
+//! Loading every historical format of devstrap.lock and devstrap.state
+//!
+//! Each file in `tests/fixtures/schema` is one format devstrap has written.
+
+use devstrap::config::{StateFile, STATE_SCHEMA_VERSION};
+use devstrap::domain::{host_platform, LOCKFILE_SCHEMA_VERSION};
+use devstrap::{Lockfile, Result};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tempfile::{tempdir, TempDir};
+
+/// Copy a fixture into a scratch directory, since saving it migrated leaves a backup next to it
+fn copy_fixture(dir: &TempDir, fixture: &str, file_name: &str) -> io::Result<PathBuf> {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/schema").join(fixture);
+    let path = dir.path().join(file_name);
+    fs::copy(source, &path)?;
+    Ok(path)
+}
+
+/// Check a loaded file keeps its pre-migration copy only once it is saved
+fn check_backup_on_save(path: &Path, migrated: bool, save: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".v0.bak");
+    let backup = path.with_file_name(name);
+    assert!(!backup.exists(), "{}", path.display());
+    save(path)?;
+    assert_eq!(backup.exists(), migrated, "{}", path.display());
+    Ok(())
+}
+
+/// Load a lockfile fixture and check what it locks on one platform
+fn check_lockfile_fixture(fixture: &str, migrated: bool, platform: &str, method: &str, ripgrep: Option<&str>) -> Result<()> {
+    let dir = tempdir()?;
+    let path = copy_fixture(&dir, fixture, "devstrap.lock")?;
+    let lockfile = Lockfile::from_file(&path)?.for_platform(platform);
+
+    assert_eq!(lockfile.schema_version, LOCKFILE_SCHEMA_VERSION, "{fixture}");
+    assert_eq!(lockfile.locked_version("node", "lts"), Some("20.11.0"), "{fixture}");
+    assert_eq!(lockfile.locked_package_version("ripgrep", method, "ripgrep"), ripgrep, "{fixture}");
+    check_backup_on_save(&path, migrated, |path| lockfile.save(path))
+}
+
+#[test]
+fn test_lockfile_fixtures() {
+    let host = host_platform();
+    let cases = [
+        ("lockfile-v0-runtimes.toml", true, host.as_str(), "cargo", None),
+        ("lockfile-v0-packages.toml", true, host.as_str(), "cargo", Some("14.1.0")),
+        ("lockfile-v1.toml", false, "linux-x86_64", "cargo", Some("14.1.0")),
+        ("lockfile-v1.toml", false, "macos-arm64", "brew", Some("14.1.1")),
+    ];
+
+    for (fixture, migrated, platform, method, ripgrep) in cases {
+        check_lockfile_fixture(fixture, migrated, platform, method, ripgrep).unwrap();
+    }
+}
+
+#[test]
+fn test_lockfile_v0_keeps_runtime_extras() {
+    let dir = tempdir().unwrap();
+    let path = copy_fixture(&dir, "lockfile-v0-packages.toml", "devstrap.lock").unwrap();
+    let lockfile = Lockfile::from_file(&path).unwrap();
+
+    assert_eq!(lockfile.locked_version("node", "18"), Some("18.19.0"));
+    assert_eq!(lockfile.locked_global_package("node", "20.11.0", "typescript@5"), Some("5.4.5"));
+    assert_eq!(lockfile.locked_package_manager("node", "20.11.0", "pnpm@9"), Some("9.12.1"));
+    assert_eq!(lockfile.locked_version("java", "21"), Some("21.0.5-tem"));
+}
+
+#[test]
+fn test_lockfile_v0_saves_as_current_schema() {
+    let dir = tempdir().unwrap();
+    let path = copy_fixture(&dir, "lockfile-v0-packages.toml", "devstrap.lock").unwrap();
+    let lockfile = Lockfile::from_file(&path).unwrap();
+    assert!(lockfile.installers.contains_key("fnm"));
+
+    lockfile.save(&path).unwrap();
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(saved.starts_with("schema_version = 1\n"));
+    assert!(saved.contains(&format!("[platforms.{}.runtimes.java]", host_platform())));
+}
+
+/// Load a state fixture and check the records every format carries
+fn load_state_fixture(dir: &TempDir, fixture: &str) -> Result<StateFile> {
+    let path = copy_fixture(dir, fixture, "devstrap.state")?;
+    let state = StateFile::from_file(&path)?;
+
+    assert_eq!(state.schema_version, STATE_SCHEMA_VERSION, "{fixture}");
+    assert_eq!(state.packages["ripgrep"].version.as_deref(), Some("14.1.0"), "{fixture}");
+    assert_eq!(state.runtimes["node"].manager, "fnm", "{fixture}");
+    assert!(state.taps.contains("hashicorp/tap"), "{fixture}");
+    Ok(state)
+}
+
+#[test]
+fn test_state_fixtures() {
+    let cases = [("state-v0.toml", true), ("state-v1.toml", false)];
+
+    for (fixture, migrated) in cases {
+        let dir = tempdir().unwrap();
+        let state = load_state_fixture(&dir, fixture).unwrap();
+        check_backup_on_save(&dir.path().join("devstrap.state"), migrated, |path| state.save(path)).unwrap();
+    }
+}
+
+#[test]
+fn test_state_v0_keeps_every_record() {
+    let dir = tempdir().unwrap();
+    let state = load_state_fixture(&dir, "state-v0.toml").unwrap();
+
+    assert!(state.repositories.contains_key("github-cli"));
+    assert!(state.managers.contains_key("fnm"));
+    assert_eq!(state.rustup.profile.as_deref(), Some("minimal"));
+}
+
+#[test]
+fn test_state_v0_keeps_runtime_extras() {
+    let dir = tempdir().unwrap();
+    let state = load_state_fixture(&dir, "state-v0.toml").unwrap();
+    assert!(state.global_packages["node"].contains("typescript@5"));
+    assert!(state.corepack["node"].package_managers.contains("pnpm@9"));
+
+    let path = dir.path().join("devstrap.state");
+    state.save(&path).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().starts_with("schema_version = 1\n"));
+}
+
+#[test]
+fn test_newer_schema_versions_are_refused() {
+    let dir = tempdir().unwrap();
+    let lockfile_path = dir.path().join("devstrap.lock");
+    let state_path = dir.path().join("devstrap.state");
+    fs::write(&lockfile_path, format!("schema_version = {}\n", LOCKFILE_SCHEMA_VERSION + 1)).unwrap();
+    fs::write(&state_path, format!("schema_version = {}\n", STATE_SCHEMA_VERSION + 1)).unwrap();
+    fs::write(dir.path().join("devstrap.state.bak.1"), "schema_version = 1\n").unwrap();
+
+    let lockfile_error = format!("{:#}", Lockfile::from_file(&lockfile_path).unwrap_err());
+    assert!(lockfile_error.contains("is newer than this devstrap supports"), "{lockfile_error}");
+    let state_error = format!("{:#}", StateFile::load(&state_path).unwrap_err());
+    assert!(state_error.contains("upgrade devstrap to read it"), "{state_error}");
+}
@@ -0,0 +1,110 @@
+//! Tests for the `url` installation method against a local HTTP server
+
+mod common;
+
+use common::serve;
+use devstrap::domain::{ArchiveType, UrlSource};
+use devstrap::service::package_manager::url::install_from_url;
+use devstrap::{Arch, Os};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+use tempfile::tempdir;
+
+const PLATFORM: (Os, Arch) = (Os::Linux, Arch::X86_64);
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[test]
+fn test_url_binary_with_checksum_file_and_arch_override() {
+    let binary = b"#!/bin/sh\necho kubectl\n".to_vec();
+    let sums = format!("{}  kubectl\n", sha256_hex(&binary));
+    let base = serve(HashMap::from([
+        ("/v1.2.3/linux/amd64/kubectl".to_string(), binary.clone()),
+        ("/v1.2.3/linux/amd64/SHA256SUMS".to_string(), sums.into_bytes()),
+    ]));
+
+    let source = UrlSource::new(format!("{base}/v{{version}}/{{os}}/{{arch}}/kubectl"))
+        .version("1.0.0")
+        .checksum_url(format!("{base}/v{{version}}/{{os}}/{{arch}}/SHA256SUMS"))
+        .arch_name(Arch::X86_64, "amd64");
+
+    let bin_dir = tempdir().unwrap();
+    let installed = install_from_url("kubectl", &source, Some("1.2.3"), PLATFORM, bin_dir.path()).unwrap();
+
+    assert_eq!(installed, bin_dir.path().join("kubectl"));
+    assert_eq!(fs::read(&installed).unwrap(), binary);
+}
+
+#[test]
+fn test_url_ignores_directory_at_predictable_path() {
+    let planted = std::env::temp_dir().join(format!("devstrap-url-tool-{}", std::process::id()));
+    fs::create_dir_all(planted.join("tool")).unwrap();
+    let base = serve(HashMap::from([("/tool".to_string(), b"bin".to_vec())]));
+    let source = UrlSource::new(format!("{base}/tool")).version("1.0.0").sha256("linux-x86_64", sha256_hex(b"bin"));
+
+    let bin_dir = tempdir().unwrap();
+    let installed = install_from_url("tool", &source, None, PLATFORM, bin_dir.path());
+    let planted_left = planted.join("tool").is_dir();
+    fs::remove_dir_all(&planted).unwrap();
+
+    assert_eq!(fs::read(installed.unwrap()).unwrap(), b"bin");
+    assert!(planted_left);
+}
+
+#[test]
+fn test_url_rejects_checksum_mismatch() {
+    let base = serve(HashMap::from([("/tool".to_string(), b"tampered".to_vec())]));
+    let source = UrlSource::new(format!("{base}/tool"))
+        .version("1.0.0")
+        .sha256("linux-x86_64", sha256_hex(b"original"));
+
+    let bin_dir = tempdir().unwrap();
+    let error = install_from_url("tool", &source, None, PLATFORM, bin_dir.path()).unwrap_err();
+
+    assert!(error.to_string().contains("Checksum mismatch"));
+    assert!(!bin_dir.path().join("tool").exists());
+}
+
+#[test]
+fn test_url_requires_checksum_for_pinned_version() {
+    let base = serve(HashMap::from([("/tool-2.0.0".to_string(), b"bin".to_vec())]));
+    let source = UrlSource::new(format!("{base}/tool-{{version}}"))
+        .version("1.0.0")
+        .sha256("linux-x86_64", sha256_hex(b"bin"));
+
+    let bin_dir = tempdir().unwrap();
+    let error = install_from_url("tool", &source, Some("2.0.0"), PLATFORM, bin_dir.path()).unwrap_err();
+
+    assert!(error.to_string().contains("no checksum_url"));
+}
+
+#[test]
+fn test_url_extracts_binary_from_tarball() {
+    let staging = tempdir().unwrap();
+    fs::create_dir(staging.path().join("linux-x86_64")).unwrap();
+    fs::write(staging.path().join("linux-x86_64/helm"), b"helm binary").unwrap();
+    let tarball = staging.path().join("helm.tar.gz");
+    let status = Command::new("tar")
+        .args(["-czf", &tarball.to_string_lossy(), "-C", &staging.path().to_string_lossy(), "linux-x86_64"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let archive = fs::read(&tarball).unwrap();
+    let digest = sha256_hex(&archive);
+    let base = serve(HashMap::from([("/helm-v3.0.0.tar.gz".to_string(), archive)]));
+    let source = UrlSource::new(format!("{base}/helm-v{{version}}.tar.gz"))
+        .version("3.0.0")
+        .sha256("linux-x86_64", digest)
+        .archive(ArchiveType::TarGz)
+        .binary("{os}-{arch}/helm");
+
+    let bin_dir = tempdir().unwrap();
+    let installed = install_from_url("helm", &source, None, PLATFORM, bin_dir.path()).unwrap();
+
+    assert_eq!(fs::read(installed).unwrap(), b"helm binary");
+}